@@ -0,0 +1,2 @@
+//! Exercises `#[derive(Slugify)]` from outside the `fileslug` crate, the way
+//! downstream users see it. See `tests/derive.rs`.
@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+use fileslug::{Slugify, SlugifyOptions, Style};
+
+#[derive(Slugify)]
+struct Filename(String);
+
+#[derive(Slugify)]
+struct BorrowedName<'a>(&'a str);
+
+#[derive(Slugify)]
+#[slug(style = "kebab")]
+struct KebabName(String);
+
+#[derive(Slugify)]
+#[slug(style = "snake")]
+struct SnakeName(String);
+
+#[derive(Slugify)]
+#[slug(style = "pascal")]
+struct PascalName(String);
+
+#[derive(Slugify)]
+#[slug(rename = "fixed")]
+struct FixedName(String);
+
+#[derive(Slugify)]
+struct FixedField(#[slug(rename = "fixed")] String);
+
+#[derive(Slugify)]
+#[slug(prefix = "my-", suffix = "-v2")]
+struct Affixed(String);
+
+#[derive(Slugify)]
+#[slug(style = "snake")]
+struct SnakePrefixed(#[slug(prefix = "mod_")] String);
+
+#[derive(Slugify)]
+#[slug(rename = "fixed", prefix = "a-", suffix = "-z")]
+struct FixedAffixed(String);
+
+#[test]
+fn test_derive_uses_given_options() {
+    let name = Filename("My Résumé (Final).pdf".to_string());
+    assert_eq!(name.to_slug(&SlugifyOptions::default()), "my-resume-final.pdf");
+
+    let snake = SlugifyOptions { style: Style::Snake, ..Default::default() };
+    assert_eq!(name.to_slug(&snake), "my_resume_final.pdf");
+}
+
+#[test]
+fn test_derive_borrowed_field() {
+    let name = BorrowedName(".gitignore");
+    // Clean dotfiles come back borrowed, straight from the wrapped value
+    assert!(matches!(name.to_slug(&SlugifyOptions::default()), Cow::Borrowed(".gitignore")));
+}
+
+#[test]
+fn test_derive_style_kebab() {
+    let snake = SlugifyOptions { style: Style::Snake, ..Default::default() };
+    assert_eq!(KebabName("My Cool File.txt".to_string()).to_slug(&snake), "my-cool-file.txt");
+}
+
+#[test]
+fn test_derive_style_snake() {
+    let name = SnakeName("My Cool File.txt".to_string());
+    assert_eq!(name.to_slug(&SlugifyOptions::default()), "my_cool_file.txt");
+}
+
+#[test]
+fn test_derive_style_pascal() {
+    let name = PascalName("my cool file.txt".to_string());
+    assert_eq!(name.to_slug(&SlugifyOptions::default()), "MyCoolFile.txt");
+}
+
+#[test]
+fn test_derive_style_keeps_other_options() {
+    let opts = SlugifyOptions { keep_unicode: true, ..Default::default() };
+    assert_eq!(SnakeName("Café Menu.txt".to_string()).to_slug(&opts), "café_menu.txt");
+}
+
+#[test]
+fn test_derive_as_trait_object() {
+    let names: Vec<Box<dyn Slugify>> = vec![
+        Box::new(Filename("A B.txt".to_string())),
+        Box::new(SnakeName("C D.txt".to_string())),
+    ];
+    let opts = SlugifyOptions::default();
+    let slugs: Vec<String> = names.iter().map(|n| n.to_slug(&opts).into_owned()).collect();
+    assert_eq!(slugs, vec!["a-b.txt", "c_d.txt"]);
+}
+
+#[test]
+fn test_derive_rename_ignores_value_and_options() {
+    let pascal = SlugifyOptions { style: Style::Pascal, ..Default::default() };
+    for value in ["My File.txt", "", "anything at all"] {
+        assert!(matches!(FixedName(value.to_string()).to_slug(&SlugifyOptions::default()), Cow::Borrowed("fixed")));
+        assert_eq!(FixedName(value.to_string()).to_slug(&pascal), "fixed");
+        assert_eq!(FixedField(value.to_string()).to_slug(&pascal), "fixed");
+    }
+}
+
+#[test]
+fn test_derive_prefix_and_suffix() {
+    let opts = SlugifyOptions::default();
+    assert_eq!(Affixed("Release Notes".to_string()).to_slug(&opts), "my-release-notes-v2");
+    // The suffix goes after the whole slug, extension included
+    assert_eq!(Affixed("Release Notes.md".to_string()).to_slug(&opts), "my-release-notes.md-v2");
+}
+
+#[test]
+fn test_derive_attributes_compose() {
+    let opts = SlugifyOptions::default();
+    assert_eq!(SnakePrefixed("Http Client.rs".to_string()).to_slug(&opts), "mod_http_client.rs");
+    assert_eq!(FixedAffixed("ignored".to_string()).to_slug(&opts), "a-fixed-z");
+}
@@ -0,0 +1,142 @@
+//! `#[derive(Slugify)]` for newtypes wrapping a string.
+//!
+//! Use through `fileslug` with the `derive` feature rather than depending on
+//! this crate directly:
+//!
+//! ```ignore
+//! use fileslug::{Slugify, SlugifyOptions};
+//!
+//! #[derive(Slugify)]
+//! struct Filename(String);
+//!
+//! #[derive(Slugify)]
+//! #[slug(style = "snake")]
+//! struct ModuleName(String);
+//!
+//! #[derive(Slugify)]
+//! #[slug(prefix = "post-", suffix = "-v2")]
+//! struct PostSlug(String);
+//!
+//! let opts = SlugifyOptions::default();
+//! assert_eq!(Filename("My File.txt".into()).to_slug(&opts), "my-file.txt");
+//! assert_eq!(ModuleName("My Module.rs".into()).to_slug(&opts), "my_module.rs");
+//! assert_eq!(PostSlug("Hello World".into()).to_slug(&opts), "post-hello-world-v2");
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitStr};
+
+/// Derive `fileslug::Slugify` for a single-field tuple struct whose field is
+/// `AsRef<str>` (e.g. `String` or `&str`).
+///
+/// `#[slug(...)]` goes on the struct or on its field and takes:
+///
+/// - `style = "kebab" | "snake" | "pascal"`: fix the style regardless of the
+///   options passed to `to_slug`
+/// - `rename = "literal"`: always return `literal`, ignoring the wrapped value
+/// - `prefix = "..."`, `suffix = "..."`: text put before and after the slug
+///   (after the extension, too) as is
+#[proc_macro_derive(Slugify, attributes(slug))]
+pub fn derive_slugify(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// The settings from `#[slug(...)]` attributes.
+#[derive(Default)]
+struct SlugAttrs {
+    style: Option<TokenStream2>,
+    rename: Option<LitStr>,
+    prefix: Option<LitStr>,
+    suffix: Option<LitStr>,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let field = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Unnamed(f) if f.unnamed.len() == 1 => &f.unnamed[0],
+            _ => return Err(not_a_newtype(input)),
+        },
+        _ => return Err(not_a_newtype(input)),
+    };
+
+    let attrs = parse_attrs(input.attrs.iter().chain(&field.attrs))?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let slug = match (&attrs.rename, &attrs.style) {
+        // Touch the field so a newtype used only for its literal doesn't warn as never read
+        (Some(rename), _) => quote! {{
+            let _ = &self.0;
+            ::std::borrow::Cow::Borrowed(#rename)
+        }},
+        (None, None) => quote! {
+            ::fileslug::slugify(::core::convert::AsRef::<str>::as_ref(&self.0), options)
+        },
+        (None, Some(style)) => quote! {{
+            let options = ::fileslug::SlugifyOptions { style: #style, ..::core::clone::Clone::clone(options) };
+            ::fileslug::slugify(::core::convert::AsRef::<str>::as_ref(&self.0), &options)
+        }},
+    };
+    let body = if attrs.prefix.is_none() && attrs.suffix.is_none() {
+        slug
+    } else {
+        let prefix = attrs.prefix.map_or_else(String::new, |p| p.value());
+        let suffix = attrs.suffix.map_or_else(String::new, |s| s.value());
+        quote!(::std::borrow::Cow::Owned(::std::format!("{}{}{}", #prefix, #slug, #suffix)))
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::fileslug::Slugify for #name #ty_generics #where_clause {
+            fn to_slug(&self, options: &::fileslug::SlugifyOptions) -> ::std::borrow::Cow<'_, str> {
+                #body
+            }
+        }
+    })
+}
+
+fn not_a_newtype(input: &DeriveInput) -> syn::Error {
+    syn::Error::new_spanned(
+        &input.ident,
+        "Slugify can only be derived for tuple structs with a single field, e.g. `struct Name(String);`",
+    )
+}
+
+/// Read every `#[slug(...)]` attribute. Each setting may be given once.
+fn parse_attrs<'a>(attrs: impl Iterator<Item = &'a Attribute>) -> syn::Result<SlugAttrs> {
+    let mut parsed = SlugAttrs::default();
+    for attr in attrs.filter(|a| a.path().is_ident("slug")) {
+        attr.parse_nested_meta(|meta| {
+            let Some(key) = ["style", "rename", "prefix", "suffix"].into_iter().find(|k| meta.path.is_ident(k)) else {
+                return Err(meta.error("unknown slug attribute; expected `style`, `rename`, `prefix`, or `suffix`"));
+            };
+            let value: LitStr = meta.value()?.parse()?;
+            let duplicate = match key {
+                "style" => parsed.style.replace(parse_style(&value)?).is_some(),
+                "rename" => parsed.rename.replace(value).is_some(),
+                "prefix" => parsed.prefix.replace(value).is_some(),
+                _ => parsed.suffix.replace(value).is_some(),
+            };
+            if duplicate {
+                return Err(meta.error(format!("duplicate slug attribute `{key}`")));
+            }
+            Ok(())
+        })?;
+    }
+    if let (Some(rename), Some(_)) = (&parsed.rename, &parsed.style) {
+        return Err(syn::Error::new_spanned(rename, "`rename` replaces the slug, so it cannot be combined with `style`"));
+    }
+    Ok(parsed)
+}
+
+/// The `Style` path for a `style = "..."` value.
+fn parse_style(value: &LitStr) -> syn::Result<TokenStream2> {
+    match value.value().as_str() {
+        "kebab" => Ok(quote!(::fileslug::Style::Kebab)),
+        "snake" => Ok(quote!(::fileslug::Style::Snake)),
+        "pascal" => Ok(quote!(::fileslug::Style::Pascal)),
+        _ => Err(syn::Error::new_spanned(value, "expected \"kebab\", \"snake\", or \"pascal\"")),
+    }
+}
@@ -0,0 +1,53 @@
+//! Sequential vs. parallel slugification of a 100,000-filename corpus.
+//!
+//! Run with `cargo bench -p fileslug --features parallel --bench batch_bench`.
+//! Set `RAYON_NUM_THREADS` to compare thread counts.
+
+use std::time::{Duration, Instant};
+
+use fileslug::{slugify, slugify_batch_rayon, SlugifyOptions};
+
+const NAMES: usize = 100_000;
+const RUNS: u32 = 5;
+
+/// Filenames with a mix of spaces, punctuation, unicode, versions, and extensions.
+fn corpus() -> Vec<String> {
+    const STEMS: [&str; 6] = [
+        "My Vacation Photo",
+        "Résumé – Final Draft",
+        "Übersicht der Änderungen",
+        "Quarterly Report (Q3)",
+        "日本語のファイル",
+        "app v2.1.0 release notes",
+    ];
+    const EXTENSIONS: [&str; 5] = [".jpg", ".pdf", ".tar.gz", ".md", ""];
+    (0..NAMES)
+        .map(|i| format!("{} {i}{}", STEMS[i % STEMS.len()], EXTENSIONS[i % EXTENSIONS.len()]))
+        .collect()
+}
+
+/// The fastest of several runs of `f`.
+fn best_of(mut f: impl FnMut() -> usize) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            assert_eq!(f(), NAMES);
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let corpus = corpus();
+    let names: Vec<&str> = corpus.iter().map(String::as_str).collect();
+    let opts = SlugifyOptions::default();
+
+    let sequential = best_of(|| names.iter().map(|name| slugify(name, &opts)).collect::<Vec<_>>().len());
+    let parallel = best_of(|| slugify_batch_rayon(&names, &opts).len());
+
+    println!("{NAMES} filenames, {} threads", rayon::current_num_threads());
+    println!("sequential slugify:    {:>8.1} ms", sequential.as_secs_f64() * 1e3);
+    println!("slugify_batch_rayon:   {:>8.1} ms", parallel.as_secs_f64() * 1e3);
+    println!("speedup:               {:>8.2}x", sequential.as_secs_f64() / parallel.as_secs_f64());
+}
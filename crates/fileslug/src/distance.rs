@@ -0,0 +1,61 @@
+//! Edit distance between names.
+//!
+//! Used to tell a real rename from a trivial one: `File.PDF` → `file.PDF`
+//! changes a single character.
+
+/// The Levenshtein distance between `a` and `b`: the fewest single-character
+/// insertions, deletions, and substitutions that turn one into the other.
+///
+/// Characters are compared as Unicode scalar values and case-sensitively, so
+/// `é` → `e` and `F` → `f` each count as one substitution. Computed with the
+/// Wagner–Fischer algorithm in `O(len(a) × len(b))` time, keeping one row.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::edit_distance;
+///
+/// assert_eq!(edit_distance("File.PDF", "file.PDF"), 1);
+/// assert_eq!(edit_distance("kitten", "sitting"), 3);
+/// assert_eq!(edit_distance("Café", "cafe"), 2);
+/// ```
+#[must_use]
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // row[j]: distance between the prefix of `a` seen so far and b[..j]
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("same.txt", "same.txt"), 0);
+        assert_eq!(edit_distance("My File.txt", "my-file.txt"), 3);
+        assert_eq!(edit_distance("flaw", "lawn"), 2);
+        assert_eq!(edit_distance("深圳.png", "shen-zhen.png"), 9);
+    }
+
+    #[test]
+    fn test_edit_distance_is_symmetric() {
+        for (a, b) in [("kitten", "sitting"), ("Report (2).pdf", "report-2.pdf"), ("ab", "ba")] {
+            assert_eq!(edit_distance(a, b), edit_distance(b, a), "{a:?} {b:?}");
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! [`arbitrary::Arbitrary`] for [`Style`] and [`SlugifyOptions`], for fuzz
+//! targets and property tests.
+//!
+//! Generated options always pass [`SlugifyOptions::validate`]: stop words,
+//! compound extensions, transliterations, and locales are drawn from small
+//! tables of valid values rather than from raw bytes, and `max_length` is at
+//! least 1. `script_policies` is left unset, and `warn_on_unchanged` off.
+
+use std::collections::BTreeMap;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{DotfilePolicy, ExtensionConfig, SlugifyOptions, SplitExtensionOptions, Style};
+
+const STOP_WORDS: &[&str] = &["a", "an", "the", "of", "and", "final", "copy"];
+const COMPOUND_EXTENSIONS: &[&str] = &[".tar.gz", ".tar.bz2", ".tar.xz", ".d.ts", ".min.js", ".nii.gz"];
+const TRANSLITERATIONS: &[(char, &str)] = &[('&', "and"), ('@', "at"), ('ä', "ae"), ('ß', "ss"), ('+', "plus"), ('ø', "")];
+#[cfg(feature = "locale")]
+const LOCALES: &[&str] = &["de", "de-AT", "sv", "tr", "sl", "fr"];
+
+impl<'a> Arbitrary<'a> for Style {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[Style::Kebab, Style::Snake, Style::Pascal])?)
+    }
+}
+
+/// A subset of `items`, in order.
+fn subset<T: Clone>(u: &mut Unstructured<'_>, items: &[T]) -> Result<Vec<T>> {
+    let mut chosen = Vec::new();
+    for item in items {
+        if u.arbitrary()? {
+            chosen.push(item.clone());
+        }
+    }
+    Ok(chosen)
+}
+
+impl<'a> Arbitrary<'a> for SlugifyOptions {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let split_options = if u.arbitrary()? {
+            let compound = subset(u, COMPOUND_EXTENSIONS)?;
+            Some(SplitExtensionOptions { compound_extensions: compound.into_iter().map(String::from).collect() })
+        } else {
+            None
+        };
+        let max_length = if u.arbitrary()? { Some(u.int_in_range(1..=300)?) } else { None };
+        let stop_words = subset(u, STOP_WORDS)?.into_iter().map(String::from).collect();
+        let custom_transliteration = if u.arbitrary()? {
+            let pairs = subset(u, TRANSLITERATIONS)?;
+            Some(pairs.into_iter().map(|(from, to)| (from, to.to_string())).collect::<BTreeMap<_, _>>())
+        } else {
+            None
+        };
+
+        Ok(SlugifyOptions {
+            style: u.arbitrary()?,
+            keep_unicode: u.arbitrary()?,
+            extensions: ExtensionConfig {
+                split_options,
+                dotfile_policy: *u.choose(&[
+                    DotfilePolicy::SplitAtLast,
+                    DotfilePolicy::KeepWhole,
+                    DotfilePolicy::SplitAtFirst,
+                ])?,
+                normalize: u.arbitrary()?,
+            },
+            max_length,
+            stop_words,
+            min_word_length: u.int_in_range(0..=3)?,
+            #[cfg(feature = "locale")]
+            locale: if u.arbitrary()? { Some((*u.choose(LOCALES)?).to_string()) } else { None },
+            custom_transliteration,
+            #[cfg(feature = "unicode-script")]
+            script_policies: None,
+            preserve_date: u.arbitrary()?,
+            preserve_version_numbers: u.arbitrary()?,
+            cross_platform: u.arbitrary()?,
+            sentence_case: u.arbitrary()?,
+            normalise_whitespace: u.arbitrary()?,
+            // Diagnostics pile up on the thread until drained, which fuzz targets don't do
+            warn_on_unchanged: false,
+            strip_punctuation_only: u.arbitrary()?,
+            preserve_leading_separator: u.arbitrary()?,
+            preserve_trailing_separator: u.arbitrary()?,
+            smart_case: u.arbitrary()?,
+            strip_copy_annotations: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{slug_length_estimate, slugify, slugify_string};
+
+    /// Deterministic pseudo-random bytes (xorshift), so failures reproduce.
+    fn bytes(seed: u64, len: usize) -> Vec<u8> {
+        let mut state = seed | 1;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state.to_le_bytes()[0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_arbitrary_style_covers_all() {
+        let styles: Vec<Style> = (0..=2u8).map(|b| Style::arbitrary(&mut Unstructured::new(&[b])).unwrap()).collect();
+        assert_eq!(styles, [Style::Kebab, Style::Snake, Style::Pascal]);
+    }
+
+    #[test]
+    fn test_arbitrary_options_are_valid() {
+        for seed in 0..500 {
+            let data = bytes(seed, 256);
+            let mut u = Unstructured::new(&data);
+            let opts = SlugifyOptions::arbitrary(&mut u).unwrap();
+            assert!(opts.validate().is_ok(), "seed {seed}: {opts:?}");
+
+            let name = String::arbitrary(&mut u).unwrap_or_default();
+            let slug = slugify(&name, &opts);
+            assert!(slug.len() <= 255, "seed {seed}: {name:?}");
+            assert!(slug_length_estimate(&name, &opts) >= slug.len(), "seed {seed}: {name:?}");
+            let _ = slugify_string(&name, &opts);
+        }
+    }
+}
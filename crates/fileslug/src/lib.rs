@@ -21,12 +21,134 @@
 //! assert_eq!(slugify_string("Café Résumé", &opts), "cafe-resume");
 //! ```
 
-use std::borrow::Cow;
+pub mod distance;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+#[cfg(feature = "locale")]
+pub mod locale;
+#[cfg(test)]
+mod test_helpers;
+#[cfg(feature = "unicode-script")]
+pub mod script;
+
+pub use distance::edit_distance;
+#[cfg(feature = "unicode-script")]
+pub use script::{ScriptPolicy, UnicodeScript};
+
+/// Derive [`Slugify`] for a newtype wrapping a string. Requires the `derive` feature.
+#[cfg(feature = "derive")]
+pub use fileslug_derive::Slugify;
+
+use std::borrow::{Borrow, Cow};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::fs;
+use std::hash::{BuildHasher, Hash};
+use std::io;
+use std::path::{Component, Path, PathBuf, MAIN_SEPARATOR};
+use std::str::FromStr;
+
+/// Compound extensions recognized by [`split_extension`].
+const DEFAULT_COMPOUND_EXTENSIONS: &[&str] = &[
+    // Compressed tarballs
+    ".tar.gz",
+    ".tar.bz2",
+    ".tar.xz",
+    ".tar.zst",
+    ".tar.lz4",
+    ".tar.lzma",
+    ".tar.lz",
+    ".tar.sz",
+    ".tar.Z",
+    // cpio archives
+    ".cpio.gz",
+    ".cpio.bz2",
+    // Compressed data files
+    ".nii.gz",
+    ".vcf.gz",
+    ".csv.gz",
+    ".json.gz",
+];
+
+/// Extension aliases and their canonical forms, without the leading dot,
+/// used by [`ExtensionConfig::normalize`]. Extensions not listed are
+/// taken to be canonical already.
+pub const DEFAULT_EXTENSION_ALIASES: &[(&str, &str)] = &[
+    ("jpeg", "jpg"),
+    ("jpe", "jpg"),
+    ("jfif", "jpg"),
+    ("tif", "tiff"),
+    ("htm", "html"),
+    ("yml", "yaml"),
+    ("markdown", "md"),
+    ("mkd", "md"),
+    ("mdown", "md"),
+    ("text", "txt"),
+    ("mpeg", "mpg"),
+    ("midi", "mid"),
+    ("aif", "aiff"),
+    ("wave", "wav"),
+    ("oga", "ogg"),
+    ("cc", "cpp"),
+    ("cxx", "cpp"),
+    ("hh", "hpp"),
+    ("hxx", "hpp"),
+    ("ndjson", "jsonl"),
+];
+
+/// The canonical form of `ext` (with its leading dot) from
+/// [`DEFAULT_EXTENSION_ALIASES`], in the same case as `ext`: all capitals
+/// (`.JPEG` → `.JPG`), a leading capital (`.Jpeg` → `.Jpg`), or lowercase.
+fn canonical_extension(ext: &str) -> Option<String> {
+    let alias = ext.strip_prefix('.')?;
+    let (_, canonical) = DEFAULT_EXTENSION_ALIASES.iter().find(|(from, _)| alias.eq_ignore_ascii_case(from))?;
+    let mut chars = alias.chars();
+    let canonical = if alias.chars().any(|c| c.is_ascii_uppercase()) && !alias.chars().any(|c| c.is_ascii_lowercase()) {
+        canonical.to_ascii_uppercase()
+    } else if chars.next().is_some_and(|c| c.is_ascii_uppercase()) {
+        let (first, rest) = canonical.split_at(1);
+        format!("{}{rest}", first.to_ascii_uppercase())
+    } else {
+        (*canonical).to_string()
+    };
+    Some(format!(".{canonical}"))
+}
+
+/// Options controlling how [`split_extension_with_opts`] splits a filename.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{split_extension_with_opts, SplitExtensionOptions};
+///
+/// let mut opts = SplitExtensionOptions::default();
+/// opts.compound_extensions.push(".warc.gz".to_string());
+/// assert_eq!(split_extension_with_opts("crawl.warc.gz", &opts), ("crawl", ".warc.gz"));
+/// assert_eq!(split_extension_with_opts("archive.tar.gz", &opts), ("archive", ".tar.gz"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SplitExtensionOptions {
+    /// Multi-part extensions kept together, including the leading dot (e.g. `.tar.gz`).
+    /// Matched case-insensitively; the first match wins.
+    pub compound_extensions: Vec<String>,
+}
+
+impl Default for SplitExtensionOptions {
+    /// The built-in compound extensions used by [`split_extension`].
+    fn default() -> Self {
+        Self {
+            compound_extensions: DEFAULT_COMPOUND_EXTENSIONS.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
 
 /// Split a filename into `(base, extension)`.
 ///
-/// Handles compound extensions (`.tar.gz`, `.tar.bz2`, `.tar.xz`, `.tar.zst`),
-/// dotfiles, and files with no extension. Dotfiles like `.gitignore` are treated
+/// Handles compound extensions (compressed tarballs such as `.tar.gz` and
+/// `.tar.zst`, `.cpio.gz`, and compressed data files such as `.nii.gz`,
+/// `.vcf.gz`, `.csv.gz`, `.json.gz`), dotfiles, and files with no extension. Dotfiles like `.gitignore` are treated
 /// as having no base — the entire name is the "extension".
 ///
 /// # Examples
@@ -41,20 +163,116 @@ use std::borrow::Cow;
 /// ```
 #[must_use]
 pub fn split_extension(filename: &str) -> (&str, &str) {
-    const COMPOUND: &[&str] = &[".tar.gz", ".tar.bz2", ".tar.xz", ".tar.zst"];
+    split_with_compound(filename, DEFAULT_COMPOUND_EXTENSIONS)
+}
+
+/// Split a filename into `(base, extension)` using a caller-provided list of
+/// compound extensions.
+///
+/// Behaves like [`split_extension`] otherwise.
+#[must_use]
+pub fn split_extension_with_opts<'a>(filename: &'a str, opts: &SplitExtensionOptions) -> (&'a str, &'a str) {
+    split_with_compound(filename, &opts.compound_extensions)
+}
+
+/// Read user-defined compound extensions from the plain-text file at `path`,
+/// one per line, such as `.warc.gz`. Blank lines and lines starting with `#`
+/// are skipped, and surrounding whitespace is ignored.
+///
+/// Each extension must be two dot-prefixed runs of ASCII letters and digits.
+/// Add the result to the built-in list with
+/// [`SlugifyOptions::with_extra_compound_extensions`].
+///
+/// # Errors
+///
+/// Fails if the file cannot be read, or with [`io::ErrorKind::InvalidData`]
+/// naming the first line that is not a compound extension.
+pub fn load_compound_extensions_file(path: &Path) -> io::Result<Vec<String>> {
+    parse_compound_extensions(&fs::read_to_string(path)?)
+}
 
+/// The compound extensions listed in `text`. See [`load_compound_extensions_file`].
+fn parse_compound_extensions(text: &str) -> io::Result<Vec<String>> {
+    let part = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric());
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            let valid = line.strip_prefix('.').and_then(|rest| rest.split_once('.')).is_some_and(|(a, b)| part(a) && part(b));
+            if valid {
+                Ok(line.to_string())
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {number}: {line:?} is not a compound extension like .tar.gz"),
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Split a filename that may contain U+FFFD replacement characters, as left
+/// by [`OsStr::to_string_lossy`] for bytes that are not valid UTF-8.
+///
+/// Like [`split_extension`], except that an extension containing U+FFFD is
+/// not taken as one: the whole name is returned as the base, where the
+/// replacement characters separate words. Otherwise the undecodable bytes
+/// would be kept verbatim in the extension, which slugify never changes.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::split_extension_lossy;
+///
+/// assert_eq!(split_extension_lossy("caf\u{FFFD}.pdf"), ("caf\u{FFFD}", ".pdf"));
+/// assert_eq!(split_extension_lossy("notes.t\u{FFFD}t"), ("notes.t\u{FFFD}t", ""));
+/// ```
+#[must_use]
+pub fn split_extension_lossy(filename: &str) -> (&str, &str) {
+    split_lossy_with_compound(filename, DEFAULT_COMPOUND_EXTENSIONS)
+}
+
+/// Split an OS filename into owned `(base, extension)` parts, decoding it as
+/// UTF-8 with [`OsStr::to_string_lossy`] and splitting as
+/// [`split_extension_lossy`] does.
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+/// use fileslug::split_extension_os;
+///
+/// let (base, ext) = split_extension_os(OsStr::new("archive.tar.gz"));
+/// assert_eq!((base.as_os_str(), ext.as_os_str()), (OsStr::new("archive"), OsStr::new(".tar.gz")));
+/// ```
+#[must_use]
+pub fn split_extension_os(filename: &OsStr) -> (OsString, OsString) {
+    let filename = filename.to_string_lossy();
+    let (base, ext) = split_extension_lossy(&filename);
+    (base.into(), ext.into())
+}
+
+fn split_lossy_with_compound<'a, E: AsRef<str>>(filename: &'a str, compound: &[E]) -> (&'a str, &'a str) {
+    match split_with_compound(filename, compound) {
+        (_, ext) if ext.contains(char::REPLACEMENT_CHARACTER) => (filename, ""),
+        split => split,
+    }
+}
+
+fn split_with_compound<'a, E: AsRef<str>>(filename: &'a str, compound: &[E]) -> (&'a str, &'a str) {
     // Dotfiles with no further extension: .gitignore, .env, .bashrc
     if filename.starts_with('.') && !filename[1..].contains('.') {
         return ("", filename);
     }
 
-    // Compound extensions (ASCII-only, so byte-level case comparison is safe)
-    for ext in COMPOUND {
-        if filename.len() >= ext.len()
-            && filename.as_bytes()[filename.len() - ext.len()..]
-                .eq_ignore_ascii_case(ext.as_bytes())
-        {
-            let base_end = filename.len() - ext.len();
+    // Compound extensions, matched case-insensitively
+    for ext in compound {
+        let ext = ext.as_ref();
+        if ext.is_empty() {
+            continue;
+        }
+        if let Some(base_end) = suffix_start_ignore_case(filename, ext) {
             return (&filename[..base_end], &filename[base_end..]);
         }
     }
@@ -66,6 +284,115 @@ pub fn split_extension(filename: &str) -> (&str, &str) {
     }
 }
 
+/// If `filename` ends with `suffix` (ignoring case), return the byte index
+/// where the suffix starts in `filename`.
+///
+/// Compares char by char from the end rather than by byte length, because
+/// some case pairs differ in UTF-8 length (`ẞ` is 3 bytes, `ß` is 2).
+fn suffix_start_ignore_case(filename: &str, suffix: &str) -> Option<usize> {
+    let mut name_chars = filename.char_indices().rev();
+    let mut start = filename.len();
+    for s in suffix.chars().rev() {
+        let (i, c) = name_chars.next()?;
+        if c != s && !c.to_lowercase().eq(s.to_lowercase()) {
+            return None;
+        }
+        start = i;
+    }
+    Some(start)
+}
+
+/// How [`slugify`] splits a dotfile that has a further dot, such as
+/// `.env.local`. Dotfiles without one (`.gitignore`) are always left whole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DotfilePolicy {
+    /// Split at the last dot, like any other name: `.env` + `.local` (default)
+    #[default]
+    SplitAtLast,
+    /// No extension: the whole name is a dotfile and is left as it is
+    KeepWhole,
+    /// Split at the first dot after the leading one: `.eslintrc` + `.old.json`
+    SplitAtFirst,
+}
+
+impl DotfilePolicy {
+    /// `filename` split into `(base, extension)` under this policy, or `None`
+    /// if the usual split applies: always for names that aren't a dotfile
+    /// with a further dot, and for names like `..foo` that have no stem.
+    fn split(self, filename: &str) -> Option<(&str, &str)> {
+        let dot = match filename.strip_prefix('.')?.find('.')? {
+            0 => return None,
+            i => i + 1,
+        };
+        match self {
+            DotfilePolicy::SplitAtLast => None,
+            DotfilePolicy::KeepWhole => Some(("", filename)),
+            DotfilePolicy::SplitAtFirst => Some(filename.split_at(dot)),
+        }
+    }
+}
+
+/// How [`slugify`] finds and rewrites a filename's extension, set with
+/// [`SlugifyOptions::with_extensions`]. The default reproduces the built-in
+/// behavior.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify, DotfilePolicy, ExtensionConfig, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default().with_extensions(ExtensionConfig {
+///     dotfile_policy: DotfilePolicy::KeepWhole,
+///     normalize: true,
+///     ..Default::default()
+/// });
+/// assert_eq!(slugify("Holiday Photo.jpeg", &opts), "holiday-photo.jpg");
+/// assert_eq!(slugify(".env.Local", &opts), ".env.Local");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ExtensionConfig {
+    /// Extension splitting rules. `None` uses the built-in compound extensions.
+    pub split_options: Option<SplitExtensionOptions>,
+    /// How to split dotfiles with a further dot, such as `.env.local`.
+    pub dotfile_policy: DotfilePolicy,
+    /// When `true`, replace an extension alias with its canonical form from
+    /// [`DEFAULT_EXTENSION_ALIASES`], matched case-insensitively and keeping
+    /// its case: `Photo.JPEG` becomes `photo.JPG`. Not applied by
+    /// [`slugify_stem`].
+    pub normalize: bool,
+}
+
+impl ExtensionConfig {
+    /// Split `filename` into `(base, extension)` using
+    /// [`dotfile_policy`](Self::dotfile_policy) and
+    /// [`split_options`](Self::split_options). An extension containing U+FFFD
+    /// (see [`split_extension_lossy`]) or `/` is not split off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{DotfilePolicy, ExtensionConfig};
+    ///
+    /// let config = ExtensionConfig { dotfile_policy: DotfilePolicy::SplitAtFirst, ..Default::default() };
+    /// assert_eq!(config.split_extension(".eslintrc.old.json"), (".eslintrc", ".old.json"));
+    /// assert_eq!(config.split_extension("backup.tar.gz"), ("backup", ".tar.gz"));
+    /// ```
+    #[must_use]
+    pub fn split_extension<'a>(&self, filename: &'a str) -> (&'a str, &'a str) {
+        let (base, ext) = match (self.dotfile_policy.split(filename), &self.split_options) {
+            (Some((_, ext)), _) if ext.contains(char::REPLACEMENT_CHARACTER) => (filename, ""),
+            (Some(split), _) => split,
+            (None, Some(opts)) => split_lossy_with_compound(filename, &opts.compound_extensions),
+            (None, None) => split_extension_lossy(filename),
+        };
+        if ext.contains('/') {
+            (filename, "")
+        } else {
+            (base, ext)
+        }
+    }
+}
+
 /// Word separator style for slugified filenames.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Style {
@@ -74,10 +401,143 @@ pub enum Style {
     Kebab,
     /// `my_cool_file.txt`
     Snake,
-    /// `MyCoolFile.txt`
+    /// `MyCoolFile.txt`. Capitals in the input start new words, so a Pascal
+    /// slug reads back as itself.
     Pascal,
 }
 
+impl Style {
+    /// The character placed between words, if any.
+    fn separator(self) -> Option<char> {
+        match self {
+            Style::Kebab => Some('-'),
+            Style::Snake => Some('_'),
+            Style::Pascal => None,
+        }
+    }
+}
+
+/// How the words of a name are joined, as found by [`detect_case_convention`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaseConvention {
+    /// `myFileName`
+    CamelCase,
+    /// `MyFileName`
+    PascalCase,
+    /// `my_file_name`
+    SnakeCase,
+    /// `my-file-name`
+    KebabCase,
+    /// A single word, words separated by spaces or punctuation, or a mix of conventions
+    Unknown,
+}
+
+/// The convention joining the words of `s`, which should be a name without
+/// its extension.
+///
+/// Only names made of letters and digits plus a single kind of separator
+/// match: `_` for snake case and `-` for kebab case, or none for camel and
+/// Pascal case, which need at least one capital starting a new word.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{detect_case_convention, CaseConvention};
+///
+/// assert_eq!(detect_case_convention("myFileName"), CaseConvention::CamelCase);
+/// assert_eq!(detect_case_convention("MyFileName"), CaseConvention::PascalCase);
+/// assert_eq!(detect_case_convention("my_file_name"), CaseConvention::SnakeCase);
+/// assert_eq!(detect_case_convention("my-file-name"), CaseConvention::KebabCase);
+/// assert_eq!(detect_case_convention("My File Name"), CaseConvention::Unknown);
+/// ```
+#[must_use]
+pub fn detect_case_convention(s: &str) -> CaseConvention {
+    if !s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        return CaseConvention::Unknown;
+    }
+    let words = |sep: char| s.split(sep).filter(|word| !word.is_empty()).count();
+    match (s.contains('_'), s.contains('-')) {
+        (true, false) if words('_') > 1 => CaseConvention::SnakeCase,
+        (false, true) if words('-') > 1 => CaseConvention::KebabCase,
+        (false, false) if split_case_transitions(s).len() > 1 => {
+            if s.starts_with(char::is_uppercase) {
+                CaseConvention::PascalCase
+            } else {
+                CaseConvention::CamelCase
+            }
+        }
+        _ => CaseConvention::Unknown,
+    }
+}
+
+/// Common English articles, prepositions, and conjunctions, for
+/// [`SlugifyOptions::stop_words`].
+pub const ENGLISH_STOP_WORDS: &[&str] = &["a", "an", "the", "and", "but", "or", "in", "on", "at", "to", "for", "of", "with", "by"];
+
+/// The 20 most common French stop words, as transliterated to ASCII
+/// (`à` → `a`), for [`SlugifyOptions::stop_words`].
+pub const FRENCH_STOP_WORDS: &[&str] = &[
+    "le", "la", "les", "de", "des", "du", "un", "une", "et", "a", "au", "aux", "en", "dans", "pour", "par", "sur", "avec",
+    "ou", "ce",
+];
+
+/// The 20 most common German stop words, for [`SlugifyOptions::stop_words`].
+pub const GERMAN_STOP_WORDS: &[&str] = &[
+    "der", "die", "das", "den", "dem", "des", "ein", "eine", "einen", "und", "oder", "in", "im", "mit", "von", "zu", "zum",
+    "zur", "auf", "aus",
+];
+
+/// The 20 most common Spanish stop words, for [`SlugifyOptions::stop_words`].
+pub const SPANISH_STOP_WORDS: &[&str] = &[
+    "el", "la", "los", "las", "un", "una", "unos", "unas", "de", "del", "al", "y", "o", "en", "con", "por", "para", "sin",
+    "a", "que",
+];
+
+/// A built-in stop word list.
+///
+/// Stop words are matched after transliteration, so with
+/// [`SlugifyOptions::keep_unicode`] words with accents (`à`) are kept.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_string, SlugifyOptions, StopWords};
+///
+/// let opts = SlugifyOptions { stop_words: StopWords::Spanish.to_vec(), ..Default::default() };
+/// assert_eq!(slugify_string("La Casa de Papel", &opts), "casa-papel");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StopWords {
+    /// [`ENGLISH_STOP_WORDS`]
+    English,
+    /// [`FRENCH_STOP_WORDS`]
+    French,
+    /// [`GERMAN_STOP_WORDS`]
+    German,
+    /// [`SPANISH_STOP_WORDS`]
+    Spanish,
+}
+
+impl StopWords {
+    /// The words in this list.
+    #[must_use]
+    pub fn words(self) -> &'static [&'static str] {
+        match self {
+            StopWords::English => ENGLISH_STOP_WORDS,
+            StopWords::French => FRENCH_STOP_WORDS,
+            StopWords::German => GERMAN_STOP_WORDS,
+            StopWords::Spanish => SPANISH_STOP_WORDS,
+        }
+    }
+
+    /// The words in this list, as [`SlugifyOptions::stop_words`] takes them.
+    #[must_use]
+    pub fn to_vec(self) -> Vec<String> {
+        self.words().iter().map(ToString::to_string).collect()
+    }
+}
+
 /// Options controlling the [`slugify`] pipeline.
 ///
 /// # Examples
@@ -93,580 +553,4691 @@ pub enum Style {
 /// let opts = SlugifyOptions { keep_unicode: true, ..Default::default() };
 /// assert_eq!(slugify("Café Menu.txt", &opts), "café-menu.txt");
 /// ```
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SlugifyOptions {
     /// Word separator style (kebab, snake, or pascal).
     pub style: Style,
     /// When `true`, skip ASCII transliteration and preserve unicode characters.
     pub keep_unicode: bool,
+    /// How the extension is split off and rewritten. See [`ExtensionConfig`].
+    pub extensions: ExtensionConfig,
+    /// Maximum slug length in bytes (extension included). Never exceeds the
+    /// built-in ceilings of 255 bytes for filenames and 1024 for plain text.
+    pub max_length: Option<usize>,
+    /// Words dropped from the slug, matched case-insensitively (e.g. `"the"`).
+    pub stop_words: Vec<String>,
+    /// Words shorter than this many characters are dropped. `0` keeps everything.
+    pub min_word_length: usize,
+    /// Language tag (e.g. `"de"`) selecting language-specific transliteration.
+    /// Ignored when `keep_unicode` is set. See [`locale::locale_transliterate`].
+    #[cfg(feature = "locale")]
+    pub locale: Option<String>,
+    /// Per-character replacements applied before transliteration (and even
+    /// with `keep_unicode`). Uppercase characters without their own entry use
+    /// the entry for their lowercase form.
+    ///
+    /// The maps in these options are `BTreeMap`s so that `SlugifyOptions` can
+    /// derive `Hash` and be used as a cache key, and so they iterate (and
+    /// serialize) in a fixed order. Lookups are `O(log n)` instead of a hash
+    /// map's `O(1)`, which is immaterial for maps of a few dozen entries.
+    pub custom_transliteration: Option<BTreeMap<char, String>>,
+    /// Per-script handling in place of uniform transliteration: keep or remove
+    /// whole scripts (e.g. keep Arabic, romanize Cyrillic). Scripts without an
+    /// entry are transliterated. Ignored when `keep_unicode` is set.
+    #[cfg(feature = "unicode-script")]
+    pub script_policies: Option<BTreeMap<UnicodeScript, ScriptPolicy>>,
+    /// When `true`, keep the dots in `YYYY.MM.DD` dates (e.g. `2024.03.15`)
+    /// even with `preserve_version_numbers` off. With it on, dates already
+    /// keep their dots as version-like numbers. In Pascal case, a number
+    /// right before or after a date is kept apart from it with `-`.
+    pub preserve_date: bool,
+    /// When `true` (the default), keep the dots in version numbers such as
+    /// `1.2.3`. When `false`, they become separators: `app-1-2-3`.
+    pub preserve_version_numbers: bool,
+    /// When `true`, make the result pass [`is_safe_for_all_platforms`] as a
+    /// last step: characters that Windows or FAT32 reject are dropped (even
+    /// from the extension), trailing dots are removed, and Windows device
+    /// names such as `con` get a trailing `_`. Not applied by [`slugify_stem`].
+    pub cross_platform: bool,
+    /// When `true`, capitalize the first letter of a kebab or snake case slug
+    /// (`My-great-blog-post`). [`Style::Pascal`] already capitalizes every word.
+    pub sentence_case: bool,
+    /// When `true` (the default), collapse runs of Unicode whitespace (no-break
+    /// space U+00A0, thin space U+2009, ideographic space U+3000, ...) into a
+    /// single ASCII space before anything else, so that a
+    /// [`custom_transliteration`](Self::custom_transliteration) entry for `' '`
+    /// covers them all.
+    ///
+    /// Any whitespace separates words either way, because only alphanumeric
+    /// characters make up words: `"My\u{A0}File.txt"` becomes `my-file.txt`
+    /// with or without this option.
+    pub normalise_whitespace: bool,
+    /// When `true`, [`slugify`] records a [`SlugDiagnostic::Unchanged`] for
+    /// input that is already clean, to be collected with [`take_diagnostics`].
+    /// Off by default, so nothing accumulates unless a caller drains it.
+    pub warn_on_unchanged: bool,
+    /// When `true`, `-` and `_` already in the name are kept where they are
+    /// instead of being replaced by the style's separator, so only other
+    /// punctuation is stripped: `my_file.txt` stays `my_file.txt` in kebab
+    /// case. Leading and trailing `-` and `_` are still dropped.
+    pub strip_punctuation_only: bool,
+    /// When `true`, a name starting with `_` or `-` keeps one of it at the
+    /// start of the slug, as in the "private" convention: `_internal_tool.sh`
+    /// becomes `_internal-tool.sh`. A slug starting with `-` reads as an
+    /// option to most commands, so it has to be passed after `--`.
+    pub preserve_leading_separator: bool,
+    /// When `true`, a name ending with `_` or `-` (before any extension)
+    /// keeps one of it at the end of the slug: `module_.py` stays `module_.py`.
+    pub preserve_trailing_separator: bool,
+    /// When `true`, a camel case or Pascal case name (see
+    /// [`detect_case_convention`]) is split into words at its capitals, so
+    /// `myFileName.txt` becomes `my-file-name.txt` rather than
+    /// `myfilename.txt`. Names with spaces or punctuation are left as they
+    /// are, and snake and kebab case names are split at their separators
+    /// either way.
+    pub smart_case: bool,
+    /// When `true`, the annotations file managers add to duplicated files —
+    /// ` (copy)`, ` - Copy`, ` copy`, ` (1)`, and ` copy 2` — are removed
+    /// from the end of the name before slugifying, so `Report (2).pdf`
+    /// becomes `report.pdf`. See [`strip_copy_annotations`].
+    pub strip_copy_annotations: bool,
 }
 
-/// Placeholder byte used to protect dots inside version numbers.
-const VERSION_DOT: char = '\x01';
+impl Default for SlugifyOptions {
+    fn default() -> Self {
+        Self {
+            style: Style::default(),
+            keep_unicode: false,
+            extensions: ExtensionConfig::default(),
+            max_length: None,
+            stop_words: Vec::new(),
+            min_word_length: 0,
+            #[cfg(feature = "locale")]
+            locale: None,
+            custom_transliteration: None,
+            #[cfg(feature = "unicode-script")]
+            script_policies: None,
+            preserve_date: false,
+            preserve_version_numbers: true,
+            cross_platform: false,
+            sentence_case: false,
+            normalise_whitespace: true,
+            warn_on_unchanged: false,
+            strip_punctuation_only: false,
+            preserve_leading_separator: false,
+            preserve_trailing_separator: false,
+            smart_case: false,
+            strip_copy_annotations: false,
+        }
+    }
+}
 
-/// Replace dots inside version-like sequences (e.g. "0.8.34") with a placeholder
-/// so the word-splitting step doesn't break them apart.
+/// Settings to layer over a [`SlugifyOptions`] with [`SlugifyOptions::merge`].
 ///
-/// A version sequence is `\d+(\.\d+)+` — two or more digit groups separated by dots.
-fn preserve_version_dots(input: &str) -> String {
-    let bytes = input.as_bytes();
-    let len = bytes.len();
-    let mut result = String::with_capacity(len);
-    let mut i = 0;
+/// Each field mirrors the one in `SlugifyOptions`: `Some` replaces it and
+/// `None` keeps it. For fields that are already optional, `Some(None)`
+/// clears the setting (e.g. removes a length limit).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SlugifyOptionsOverride {
+    pub style: Option<Style>,
+    pub keep_unicode: Option<bool>,
+    pub extensions: Option<ExtensionConfig>,
+    pub max_length: Option<Option<usize>>,
+    pub stop_words: Option<Vec<String>>,
+    pub min_word_length: Option<usize>,
+    #[cfg(feature = "locale")]
+    pub locale: Option<Option<String>>,
+    pub custom_transliteration: Option<Option<BTreeMap<char, String>>>,
+    #[cfg(feature = "unicode-script")]
+    pub script_policies: Option<Option<BTreeMap<UnicodeScript, ScriptPolicy>>>,
+    pub preserve_date: Option<bool>,
+    pub preserve_version_numbers: Option<bool>,
+    pub cross_platform: Option<bool>,
+    pub sentence_case: Option<bool>,
+    pub normalise_whitespace: Option<bool>,
+    pub warn_on_unchanged: Option<bool>,
+    pub strip_punctuation_only: Option<bool>,
+    pub preserve_leading_separator: Option<bool>,
+    pub preserve_trailing_separator: Option<bool>,
+    pub smart_case: Option<bool>,
+    pub strip_copy_annotations: Option<bool>,
+}
 
-    while i < len {
-        if bytes[i].is_ascii_digit() {
-            let start = i;
+/// The problems found by [`SlugifyOptions::validate`], one message each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlugifyOptionsValidationError {
+    /// Human-readable descriptions of each invalid setting.
+    pub problems: Vec<String>,
+}
 
-            // Consume first digit group
-            while i < len && bytes[i].is_ascii_digit() {
-                i += 1;
-            }
+impl fmt::Display for SlugifyOptionsValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid slugify options: {}", self.problems.join("; "))
+    }
+}
 
-            // Try to consume one or more .digits groups
-            let mut dot_count = 0;
-            while i < len && bytes[i] == b'.' {
-                let dot_pos = i;
-                i += 1;
-                if i < len && bytes[i].is_ascii_digit() {
-                    while i < len && bytes[i].is_ascii_digit() {
-                        i += 1;
-                    }
-                    dot_count += 1;
-                } else {
-                    i = dot_pos; // backtrack
-                    break;
-                }
-            }
+impl std::error::Error for SlugifyOptionsValidationError {}
 
-            if dot_count >= 1 {
-                for &b in &bytes[start..i] {
-                    if b == b'.' {
-                        result.push(VERSION_DOT);
-                    } else {
-                        result.push(b as char);
-                    }
-                }
-            } else {
-                // All bytes in this range are ASCII digits, safe to push as char
-                for &b in &bytes[start..i] {
-                    result.push(b as char);
-                }
-            }
-        } else {
-            // Non-digit character: may be multi-byte UTF-8
-            let ch = input[i..].chars().next().unwrap();
-            result.push(ch);
-            i += ch.len_utf8();
+/// Errors from the slugify functions that can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SlugifyError {
+    /// Nothing usable was left after slugification.
+    EmptyResult,
+}
+
+impl fmt::Display for SlugifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlugifyError::EmptyResult => f.write_str("input slugifies to an empty string"),
         }
     }
-
-    result
 }
 
-/// Restore placeholder bytes back to dots after slugification.
-fn restore_version_dots(input: &str) -> String {
-    input.replace(VERSION_DOT, ".")
-}
+impl std::error::Error for SlugifyError {}
 
-/// Maximum filename length in bytes (common filesystem limit).
-const MAX_FILENAME_BYTES: usize = 255;
+impl SlugifyOptions {
+    /// Default options with custom character replacements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::with_transliteration_override(&[('ä', "ae"), ('&', "and")]);
+    /// assert_eq!(slugify("Hämisch & Äpfel.txt", &opts), "haemisch-and-aepfel.txt");
+    /// ```
+    #[must_use]
+    pub fn with_transliteration_override(pairs: &[(char, &str)]) -> Self {
+        Self {
+            custom_transliteration: Some(pairs.iter().map(|&(c, s)| (c, s.to_string())).collect()),
+            ..Default::default()
+        }
+    }
 
-/// Maximum slug length in bytes for plain text slugification.
-/// Generous ceiling — no real slug should approach this, but it guards
-/// against unbounded output if someone feeds in very large input.
-const MAX_SLUG_BYTES: usize = 1024;
+    /// Default options with [`ENGLISH_STOP_WORDS`] as the stop words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::with_english_stop_words();
+    /// assert_eq!(slugify("The Lord of the Rings.epub", &opts), "lord-rings.epub");
+    /// ```
+    #[must_use]
+    pub fn with_english_stop_words() -> Self {
+        Self { stop_words: StopWords::English.to_vec(), ..Default::default() }
+    }
 
-/// Truncate `base` so that `base + ext` fits within `max_bytes`.
-/// Prefers cutting at a separator boundary (dash, underscore) to avoid broken words.
-/// Returns the base unchanged if it already fits.
-fn truncate_base(base: &str, ext: &str, max_bytes: usize) -> String {
-    let budget = max_bytes.saturating_sub(ext.len());
-    if base.len() <= budget {
-        return base.to_string();
+    /// These options with extension handling replaced by `extensions`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify, ExtensionConfig, SlugifyOptions};
+    ///
+    /// let extensions = ExtensionConfig { normalize: true, ..Default::default() };
+    /// let opts = SlugifyOptions::preset_filesystem().with_extensions(extensions);
+    /// assert_eq!(slugify("Site Config.yml", &opts), "site-config.yaml");
+    /// ```
+    #[must_use]
+    pub fn with_extensions(self, extensions: ExtensionConfig) -> Self {
+        Self { extensions, ..self }
     }
 
-    // Floor to a char boundary to avoid slicing mid-character (keep_unicode mode)
-    let budget = (0..=budget)
-        .rev()
-        .find(|&i| base.is_char_boundary(i))
-        .unwrap_or(0);
+    /// These options with `exts` added to the compound extensions kept
+    /// together (the built-in ones, unless others were set), skipping any
+    /// already listed. See [`load_compound_extensions_file`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::default().with_extra_compound_extensions(&[".warc.gz".to_string()]);
+    /// assert_eq!(slugify("Site Crawl.warc.gz", &opts), "site-crawl.warc.gz");
+    /// assert_eq!(slugify("Backup.tar.gz", &opts), "backup.tar.gz");
+    /// ```
+    #[must_use]
+    pub fn with_extra_compound_extensions(mut self, exts: &[String]) -> Self {
+        let split = self.extensions.split_options.get_or_insert_with(SplitExtensionOptions::default);
+        for ext in exts {
+            if !split.compound_extensions.iter().any(|known| known.eq_ignore_ascii_case(ext)) {
+                split.compound_extensions.push(ext.clone());
+            }
+        }
+        self
+    }
 
-    // Truncate, then look for the last separator within budget
-    let truncated = &base[..budget];
-    if let Some(pos) = truncated.rfind(['-', '_']) {
-        if pos > 0 {
-            return truncated[..pos].to_string();
+    /// The extension handling in these options.
+    #[must_use]
+    pub fn extension_config(&self) -> &ExtensionConfig {
+        &self.extensions
+    }
+
+    /// Options for URL slugs: kebab-case, ASCII only, at most 100 bytes,
+    /// with the articles `a`, `an`, and `the` removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify_string, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::preset_url();
+    /// assert_eq!(slugify_string("The Quick Guide to Café Culture", &opts), "quick-guide-to-cafe-culture");
+    /// ```
+    #[must_use]
+    pub fn preset_url() -> Self {
+        Self {
+            style: Style::Kebab,
+            max_length: Some(100),
+            stop_words: ["a", "an", "the"].iter().map(ToString::to_string).collect(),
+            ..Default::default()
         }
     }
 
-    // No separator found — hard truncate at budget
-    truncated.to_string()
-}
+    /// Options for programming identifiers: `snake_case`, ASCII only, at most
+    /// 64 bytes, with version dots turned into separators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify_string, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::preset_identifier();
+    /// assert_eq!(slugify_string("User Account (Primary)", &opts), "user_account_primary");
+    /// assert_eq!(slugify_string("Parser v2.1", &opts), "parser_v2_1");
+    /// ```
+    #[must_use]
+    pub fn preset_identifier() -> Self {
+        Self {
+            style: Style::Snake,
+            max_length: Some(64),
+            preserve_version_numbers: false,
+            ..Default::default()
+        }
+    }
 
-/// Core slugification pipeline shared by [`slugify`] and [`slugify_string`].
-///
-/// Transliterates, strips brackets, preserves version dots, normalizes words,
-/// joins with the chosen separator, and restores version dots.
-fn slugify_core(input: &str, options: &SlugifyOptions) -> String {
-    // Step 1: Transliterate
-    let text = if options.keep_unicode {
-        input.to_string()
-    } else {
-        any_ascii::any_ascii(input)
-    };
+    /// Options for filenames — the same as [`SlugifyOptions::default`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::preset_filesystem();
+    /// assert_eq!(opts, SlugifyOptions::default());
+    /// assert_eq!(slugify("My Résumé (Final).pdf", &opts), "my-resume-final.pdf");
+    /// ```
+    #[must_use]
+    pub fn preset_filesystem() -> Self {
+        Self::default()
+    }
 
-    // Step 2: Strip bracket characters, keep contents
-    let text = text.replace(['(', ')', '[', ']', '{', '}'], " ");
+    /// Options for short tags: kebab-case, at most 30 bytes, single-character
+    /// words dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify_string, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::preset_tag();
+    /// assert_eq!(slugify_string("Rust & C Interop", &opts), "rust-interop");
+    /// ```
+    #[must_use]
+    pub fn preset_tag() -> Self {
+        Self {
+            style: Style::Kebab,
+            max_length: Some(30),
+            min_word_length: 2,
+            ..Default::default()
+        }
+    }
 
-    // Step 3: Preserve dots in version numbers (e.g. "0.8.34")
-    let text = preserve_version_dots(&text);
+    /// Options for git branch names: kebab-case, ASCII only, at most 255 bytes.
+    /// Use with [`slugify_git_branch`], which also enforces git's other rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify_git_branch, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::preset_git_branch();
+    /// assert_eq!(slugify_git_branch("Fix: crash on save (#123)", &opts), "fix-crash-on-save-123");
+    /// ```
+    #[must_use]
+    pub fn preset_git_branch() -> Self {
+        Self {
+            style: Style::Kebab,
+            max_length: Some(MAX_GIT_BRANCH_BYTES),
+            ..Default::default()
+        }
+    }
 
-    // Step 4: Normalize — collect words (sequences of alphanumeric/unicode chars)
-    let words: Vec<String> = if options.keep_unicode {
-        text.split(|c: char| !c.is_alphanumeric() && c != VERSION_DOT)
-            .filter(|s| !s.is_empty())
-            .map(str::to_lowercase)
-            .collect()
-    } else {
-        text.split(|c: char| !c.is_ascii_alphanumeric() && c != VERSION_DOT)
-            .filter(|s| !s.is_empty())
-            .map(str::to_lowercase)
-            .collect()
-    };
+    /// Options for Docker image tags: kebab-case, ASCII only, at most 128 bytes.
+    /// Use with [`slugify_for_docker_tag`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{slugify_for_docker_tag, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::preset_docker_tag();
+    /// assert_eq!(slugify_for_docker_tag("Release 2.1 (RC)", &opts).unwrap(), "release-2.1-rc");
+    /// ```
+    #[must_use]
+    pub fn preset_docker_tag() -> Self {
+        Self {
+            style: Style::Kebab,
+            max_length: Some(MAX_DOCKER_TAG_BYTES),
+            ..Default::default()
+        }
+    }
 
-    if words.is_empty() {
-        return String::new();
+    /// Options for filenames that are valid on Linux, macOS, Windows, and
+    /// FAT32 alike: kebab-case, ASCII only, at most 255 bytes, with
+    /// [`cross_platform`](Self::cross_platform) cleanup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{is_safe_for_all_platforms, slugify, SlugifyOptions};
+    ///
+    /// let opts = SlugifyOptions::preset_cross_platform();
+    /// assert_eq!(slugify("CON.txt", &opts), "con_.txt");
+    /// assert_eq!(slugify("Notes.c++", &opts), "notes.c");
+    /// assert!(is_safe_for_all_platforms(&slugify("Café: Menu?.txt", &opts)).is_safe_everywhere());
+    /// ```
+    #[must_use]
+    pub fn preset_cross_platform() -> Self {
+        Self {
+            style: Style::Kebab,
+            keep_unicode: false,
+            max_length: Some(MAX_FILENAME_BYTES),
+            cross_platform: true,
+            ..Default::default()
+        }
     }
 
-    // Step 5: Join with chosen separator
-    let slugified = match options.style {
-        Style::Kebab => words.join("-"),
-        Style::Snake => words.join("_"),
-        Style::Pascal => {
-            let mut result = String::new();
-            for word in &words {
-                let mut chars = word.chars();
-                if let Some(first) = chars.next() {
-                    result.extend(first.to_uppercase());
-                    result.push_str(chars.as_str());
+    /// Check for settings that are invalid or contradict each other.
+    ///
+    /// Every problem is reported, not just the first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SlugifyOptionsValidationError`] listing each problem:
+    ///
+    /// - `max_length` is `Some(0)`, which leaves no room for a slug
+    /// - a stop word is empty or contains whitespace, so it can never match a word
+    /// - a compound extension does not start with a dot (empty entries are ignored)
+    /// - a custom transliteration produces control characters
+    /// - `locale` is set to an empty string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::SlugifyOptions;
+    ///
+    /// assert!(SlugifyOptions::default().validate().is_ok());
+    ///
+    /// let opts = SlugifyOptions { max_length: Some(0), ..Default::default() };
+    /// let err = opts.validate().unwrap_err();
+    /// assert_eq!(err.problems, ["max_length must be at least 1"]);
+    /// ```
+    pub fn validate(&self) -> Result<(), SlugifyOptionsValidationError> {
+        let mut problems = Vec::new();
+
+        if self.max_length == Some(0) {
+            problems.push("max_length must be at least 1".to_string());
+        }
+        for word in &self.stop_words {
+            if word.is_empty() || word.contains(char::is_whitespace) {
+                problems.push(format!("stop word {word:?} can never match a single word"));
+            }
+        }
+        if let Some(split) = &self.extensions.split_options {
+            for ext in split.compound_extensions.iter().filter(|e| !e.is_empty()) {
+                if !ext.starts_with('.') || ext.len() == 1 {
+                    problems.push(format!("compound extension {ext:?} must start with a dot"));
                 }
             }
-            result
         }
-    };
+        if let Some(map) = &self.custom_transliteration {
+            let mut bad: Vec<char> = map
+                .iter()
+                .filter(|(_, to)| to.contains(char::is_control))
+                .map(|(&from, _)| from)
+                .collect();
+            bad.sort_unstable();
+            for from in bad {
+                problems.push(format!("transliteration of {from:?} contains control characters"));
+            }
+        }
+        #[cfg(feature = "locale")]
+        if self.locale.as_deref() == Some("") {
+            problems.push("locale must not be empty".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(SlugifyOptionsValidationError { problems })
+        }
+    }
+
+    /// These options with every field set in `overrides` replaced.
+    ///
+    /// Merging several override sets in turn layers them, the last one
+    /// winning: defaults, then a config file, then the environment, then
+    /// command-line flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{SlugifyOptions, SlugifyOptionsOverride, Style};
+    ///
+    /// let config = SlugifyOptionsOverride { style: Some(Style::Snake), max_length: Some(Some(40)), ..Default::default() };
+    /// let flags = SlugifyOptionsOverride { max_length: Some(None), ..Default::default() };
+    ///
+    /// let opts = SlugifyOptions::default().merge(&config).merge(&flags);
+    /// assert_eq!(opts.style, Style::Snake);
+    /// assert_eq!(opts.max_length, None);
+    /// ```
+    #[must_use]
+    pub fn merge(&self, overrides: &SlugifyOptionsOverride) -> SlugifyOptions {
+        fn pick<T: Clone>(base: &T, value: Option<&T>) -> T {
+            value.unwrap_or(base).clone()
+        }
+
+        SlugifyOptions {
+            style: pick(&self.style, overrides.style.as_ref()),
+            keep_unicode: pick(&self.keep_unicode, overrides.keep_unicode.as_ref()),
+            extensions: pick(&self.extensions, overrides.extensions.as_ref()),
+            max_length: pick(&self.max_length, overrides.max_length.as_ref()),
+            stop_words: pick(&self.stop_words, overrides.stop_words.as_ref()),
+            min_word_length: pick(&self.min_word_length, overrides.min_word_length.as_ref()),
+            #[cfg(feature = "locale")]
+            locale: pick(&self.locale, overrides.locale.as_ref()),
+            custom_transliteration: pick(&self.custom_transliteration, overrides.custom_transliteration.as_ref()),
+            #[cfg(feature = "unicode-script")]
+            script_policies: pick(&self.script_policies, overrides.script_policies.as_ref()),
+            preserve_date: pick(&self.preserve_date, overrides.preserve_date.as_ref()),
+            preserve_version_numbers: pick(&self.preserve_version_numbers, overrides.preserve_version_numbers.as_ref()),
+            cross_platform: pick(&self.cross_platform, overrides.cross_platform.as_ref()),
+            sentence_case: pick(&self.sentence_case, overrides.sentence_case.as_ref()),
+            normalise_whitespace: pick(&self.normalise_whitespace, overrides.normalise_whitespace.as_ref()),
+            warn_on_unchanged: pick(&self.warn_on_unchanged, overrides.warn_on_unchanged.as_ref()),
+            strip_punctuation_only: pick(&self.strip_punctuation_only, overrides.strip_punctuation_only.as_ref()),
+            preserve_leading_separator: pick(
+                &self.preserve_leading_separator,
+                overrides.preserve_leading_separator.as_ref(),
+            ),
+            preserve_trailing_separator: pick(
+                &self.preserve_trailing_separator,
+                overrides.preserve_trailing_separator.as_ref(),
+            ),
+            smart_case: pick(&self.smart_case, overrides.smart_case.as_ref()),
+            strip_copy_annotations: pick(&self.strip_copy_annotations, overrides.strip_copy_annotations.as_ref()),
+        }
+    }
+
+    /// Whether [`slugify`] leaves every input unchanged with these options.
+    ///
+    /// Always `false`: words are always lowercased (or capitalized) and
+    /// rejoined with the style's separator, so `My File.txt` changes under any
+    /// options. Use [`is_noop_for`](Self::is_noop_for) to check a given name.
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn is_noop(&self) -> bool {
+        false
+    }
 
-    // Step 6: Restore version dots
-    restore_version_dots(&slugified)
+    /// Whether [`slugify`] leaves `input` unchanged with these options, e.g. to
+    /// skip names that are already clean.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fileslug::{SlugifyOptions, Style};
+    ///
+    /// let opts = SlugifyOptions::default();
+    /// assert!(opts.is_noop_for("my-file.txt"));
+    /// assert!(!opts.is_noop_for("My File.txt"));
+    ///
+    /// let pascal = SlugifyOptions { style: Style::Pascal, ..Default::default() };
+    /// assert!(pascal.is_noop_for("Notes.txt"));
+    /// ```
+    #[must_use]
+    pub fn is_noop_for(&self, input: &str) -> bool {
+        slugify_traced(input, self, false, &mut Trace::off()) == input
+    }
+
+    /// Whether words may contain non-ASCII characters after transliteration.
+    fn unicode_words(&self) -> bool {
+        #[cfg(feature = "unicode-script")]
+        if let Some(policies) = &self.script_policies {
+            return self.keep_unicode || policies.values().any(|p| *p == ScriptPolicy::Keep);
+        }
+        self.keep_unicode
+    }
 }
 
-/// Slugify a filename according to the given options.
+/// Remove C0 control characters (U+0000–U+001F), DEL (U+007F) and C1 control
+/// characters (U+0080–U+009F) from `s`.
 ///
-/// Converts a filename to a clean, shell-safe slug while preserving its
-/// extension, dotfile status, and any embedded version numbers. Returns
-/// [`Cow::Borrowed`] when the input is already clean (e.g. dotfiles).
+/// Linux filenames may contain any byte except `/` and NUL-terminated C APIs
+/// truncate at an embedded null, so these never belong in a slug. Tabs and
+/// newlines are removed too. ([`slugify`] and [`slugify_string`] instead treat
+/// whitespace controls as word separators and strip the rest, before any
+/// other processing.)
 ///
-/// Names exceeding 255 bytes are silently truncated at a word boundary.
+/// Returns [`Cow::Borrowed`] when there is nothing to strip.
 ///
 /// # Examples
 ///
 /// ```
-/// use fileslug::{slugify, SlugifyOptions};
+/// use fileslug::strip_control_chars;
 ///
-/// let opts = SlugifyOptions::default();
-/// assert_eq!(slugify("My Résumé (Final).pdf", &opts), "my-resume-final.pdf");
-/// assert_eq!(slugify(".gitignore", &opts), ".gitignore");
-/// assert_eq!(slugify("app-1.2.3.dmg", &opts), "app-1.2.3.dmg");
-/// assert_eq!(slugify("Photo 2024_01.JPG", &opts), "photo-2024-01.JPG");
+/// assert_eq!(strip_control_chars("report\0.pdf"), "report.pdf");
+/// assert_eq!(strip_control_chars("\x1b[31mred"), "[31mred");
 /// ```
 #[must_use]
-pub fn slugify<'a>(filename: &'a str, options: &SlugifyOptions) -> Cow<'a, str> {
-    if filename.is_empty() {
-        return Cow::Borrowed("");
+pub fn strip_control_chars(s: &str) -> Cow<'_, str> {
+    if s.contains(char::is_control) {
+        Cow::Owned(s.chars().filter(|c| !c.is_control()).collect())
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Turn whitespace control characters (tab, newline, ...) into spaces and
+/// drop all other control characters.
+fn sanitize_control_chars(s: &str) -> Cow<'_, str> {
+    if s.contains(char::is_control) {
+        Cow::Owned(
+            s.chars()
+                .filter_map(|c| match c {
+                    c if c.is_control() && c.is_whitespace() => Some(' '),
+                    c if c.is_control() => None,
+                    c => Some(c),
+                })
+                .collect(),
+        )
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Collapse each run of whitespace into one ASCII space, if it contains any
+/// whitespace other than an ASCII space.
+fn normalise_whitespace(s: &str) -> Cow<'_, str> {
+    if !s.contains(|c: char| c.is_whitespace() && c != ' ') {
+        return Cow::Borrowed(s);
+    }
+    let mut result = String::with_capacity(s.len());
+    let mut in_space = false;
+    for c in s.chars() {
+        if c.is_whitespace() {
+            if !in_space {
+                result.push(' ');
+            }
+            in_space = true;
+        } else {
+            result.push(c);
+            in_space = false;
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Unicode bidirectional formatting characters: embeddings and overrides
+/// (U+202A–U+202E), isolates (U+2066–U+2069), and the LRM, RLM and ALM marks.
+fn is_direction_control(c: char) -> bool {
+    matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{200E}' | '\u{200F}' | '\u{061C}')
+}
+
+/// Remove Unicode bidirectional control characters from `s`.
+///
+/// These characters are invisible but reorder how the surrounding text is
+/// displayed, which makes them a classic filename spoofing vector:
+/// `"invoice\u{202E}fdp.exe"` shows up as `invoiceexe.pdf` in many file
+/// managers. [`slugify`] and [`slugify_string`] run this before any other
+/// processing, so the extension that is kept is the real one.
+///
+/// Returns [`Cow::Borrowed`] when there is nothing to strip.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::strip_direction_overrides;
+///
+/// assert_eq!(strip_direction_overrides("file\u{202E}gpj.pdf"), "filegpj.pdf");
+/// assert_eq!(strip_direction_overrides("plain.txt"), "plain.txt");
+/// ```
+#[must_use]
+pub fn strip_direction_overrides(s: &str) -> Cow<'_, str> {
+    if s.contains(is_direction_control) {
+        Cow::Owned(s.chars().filter(|&c| !is_direction_control(c)).collect())
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Replace each backslash in `s` with `/`, so a Windows path such as
+/// `path\to\file.txt` reads the same on every platform. [`slugify`] runs
+/// this before splitting off the extension; either separator then splits
+/// words, and neither can end up in the kept extension.
+///
+/// Returns [`Cow::Borrowed`] when there are no backslashes.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{normalize_path_separators, slugify, SlugifyOptions};
+///
+/// assert_eq!(normalize_path_separators("path\\to\\file.txt"), "path/to/file.txt");
+/// assert_eq!(slugify("path\\to\\file.txt", &SlugifyOptions::default()), "path-to-file.txt");
+/// ```
+#[must_use]
+pub fn normalize_path_separators(s: &str) -> Cow<'_, str> {
+    if s.contains('\\') {
+        Cow::Owned(s.replace('\\', "/"))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Remove the annotations that file managers append to duplicated files
+/// from the end of `filename`'s base, keeping the extension as `options`
+/// splits it: ` (copy)`, ` - Copy`, and ` copy` (in any case), numbers
+/// ` (1)` to ` (99)`, and numbered copies ` copy 2` to ` copy 99`. Stacked
+/// annotations are all removed (`Report (2) (copy).pdf` → `Report.pdf`),
+/// but never the whole base.
+///
+/// A bare trailing number is part of the name (`Chapter 3.pdf`), and
+/// annotations at the start, such as `Copy of`, are kept.
+///
+/// Returns [`Cow::Borrowed`] when there is nothing to remove.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{strip_copy_annotations, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default();
+/// assert_eq!(strip_copy_annotations("Budget (1).xlsx", &opts), "Budget.xlsx");
+/// assert_eq!(strip_copy_annotations("Notes - Copy (2).txt", &opts), "Notes.txt");
+/// assert_eq!(strip_copy_annotations("photo copy 2.jpg", &opts), "photo.jpg");
+/// assert_eq!(strip_copy_annotations("Season 2.mkv", &opts), "Season 2.mkv");
+/// assert_eq!(strip_copy_annotations("(1).txt", &opts), "(1).txt");
+/// ```
+#[must_use]
+pub fn strip_copy_annotations<'a>(filename: &'a str, options: &SlugifyOptions) -> Cow<'a, str> {
+    let (base, ext) = options.extension_config().split_extension(filename);
+    let stripped = strip_copy_suffixes(base);
+    if stripped.len() == base.len() {
+        Cow::Borrowed(filename)
+    } else {
+        Cow::Owned(format!("{stripped}{ext}"))
+    }
+}
+
+/// `base` without any trailing copy annotations. See [`strip_copy_annotations`].
+fn strip_copy_suffixes(mut base: &str) -> &str {
+    while let Some(head) = strip_copy_suffix(base) {
+        base = head;
+    }
+    base
+}
+
+/// `base` without its last copy annotation, or `None` if it has none or
+/// nothing but whitespace would be left.
+fn strip_copy_suffix(base: &str) -> Option<&str> {
+    // A copy number has one or two digits and no leading zero
+    let copy_number = |n: &str, min: u8| {
+        !n.starts_with('0') && (1..=2).contains(&n.len()) && n.parse::<u8>().is_ok_and(|n| n >= min)
+    };
+
+    let head = [" (copy)", " - copy", "- copy", " copy"]
+        .iter()
+        .find_map(|suffix| {
+            let start = base.len().checked_sub(suffix.len())?;
+            base.get(start..).filter(|end| end.eq_ignore_ascii_case(suffix)).map(|_| &base[..start])
+        })
+        .or_else(|| {
+            let (head, n) = base.strip_suffix(')')?.rsplit_once(" (")?;
+            copy_number(n, 1).then_some(head)
+        })
+        .or_else(|| {
+            // A bare number only counts after "copy", as in macOS's "name copy 2"
+            let (head, n) = base.rsplit_once(' ')?;
+            let start = head.len().checked_sub(" copy".len())?;
+            let marked = head.get(start..).is_some_and(|end| end.eq_ignore_ascii_case(" copy"));
+            (marked && copy_number(n, 2)).then(|| &head[..start])
+        })?;
+
+    (!head.trim().is_empty()).then_some(head)
+}
+
+/// Placeholder byte used to protect dots inside version numbers.
+const VERSION_DOT: char = '\x01';
+
+/// Replace dots inside version-like sequences (e.g. "0.8.34") with a placeholder
+/// so the word-splitting step doesn't break them apart.
+///
+/// A version sequence is `\d+(\.\d+)+` — two or more digit groups separated by dots.
+fn preserve_version_dots(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    // Byte offset up to which input has already been copied by a digit run
+    let mut resume = 0;
+
+    for (i, ch) in input.char_indices() {
+        if i < resume {
+            continue;
+        }
+        if !ch.is_ascii_digit() {
+            result.push(ch);
+            continue;
+        }
+
+        // Consume the first digit group, then one or more .digits groups.
+        // Only ASCII digits and dots are consumed, so `end` always lands on
+        // a char boundary.
+        let mut end = ascii_digits_end(input, i);
+        let mut dot_count = 0;
+        while input[end..].starts_with('.') {
+            let next = ascii_digits_end(input, end + 1);
+            if next == end + 1 {
+                break; // dot not followed by a digit
+            }
+            end = next;
+            dot_count += 1;
+        }
+
+        let run = &input[i..end];
+        if dot_count >= 1 {
+            result.extend(run.chars().map(|c| if c == '.' { VERSION_DOT } else { c }));
+        } else {
+            result.push_str(run);
+        }
+        resume = end;
+    }
+
+    result
+}
+
+/// Placeholder protecting the hyphen in a version range such as `2.10-2.12.26`.
+const VERSION_RANGE_DASH: char = '\x02';
+
+/// Replace the hyphen between two version numbers (`\d+(\.\d+)+`), as in
+/// `2.10-2.12.26`, with a placeholder so the range stays one word and keeps
+/// its hyphen in every style. Chains such as `1.0-2.0-3.0` are protected
+/// throughout. Run before [`preserve_version_dots`].
+fn preserve_version_range(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut copied = 0;
+    let mut i = 0;
+
+    while let Some(offset) = input[i..].find(|c: char| c.is_ascii_digit()) {
+        let start = i + offset;
+        let Some(end) = version_end(input, start) else {
+            i = ascii_digits_end(input, start);
+            continue;
+        };
+        if input[end..].starts_with('-') && version_end(input, end + 1).is_some() {
+            result.push_str(&input[copied..end]);
+            result.push(VERSION_RANGE_DASH);
+            copied = end + 1;
+        }
+        i = end;
+    }
+
+    result.push_str(&input[copied..]);
+    result
+}
+
+/// If a version number (`\d+(\.\d+)+`) starts at `start`, the byte offset
+/// just past it.
+fn version_end(input: &str, start: usize) -> Option<usize> {
+    let mut end = ascii_digits_end(input, start);
+    if end == start {
+        return None;
+    }
+    let mut groups = 1;
+    while input[end..].starts_with('.') {
+        let next = ascii_digits_end(input, end + 1);
+        if next == end + 1 {
+            break;
+        }
+        end = next;
+        groups += 1;
+    }
+    (groups > 1).then_some(end)
+}
+
+/// Byte offset just past the run of ASCII digits starting at `start`.
+fn ascii_digits_end(input: &str, start: usize) -> usize {
+    start + input[start..].bytes().take_while(u8::is_ascii_digit).count()
+}
+
+/// A version number such as `0.8.34`, `7.20`, or `1.2.3-rc1`.
+///
+/// The form is `major.minor[.patch][-pre]`: two or three dot-separated
+/// numbers without leading zeros, optionally followed by a hyphen and a
+/// pre-release tag of ASCII letters and digits that starts with a letter.
+/// Leading zeros are rejected so that [`Display`](fmt::Display) reproduces
+/// the parsed text exactly; this also keeps dates like `2024.03.15` out.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::Version;
+///
+/// let v: Version = "1.2.3-rc1".parse().unwrap();
+/// assert_eq!((v.major, v.minor, v.patch), (1, 2, Some(3)));
+/// assert_eq!(v.pre.as_deref(), Some("rc1"));
+/// assert_eq!(v.to_string(), "1.2.3-rc1");
+///
+/// assert_eq!("7.20".parse::<Version>().unwrap().patch, None);
+/// assert!("1.2.3.4".parse::<Version>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    /// `None` for a two-part version like `7.20`.
+    pub patch: Option<u32>,
+    /// The pre-release tag after the hyphen, without the hyphen.
+    pub pre: Option<String>,
+}
+
+/// Why a string could not be parsed as a [`Version`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseVersionError {
+    /// Not of the form `major.minor[.patch][-pre]`.
+    Malformed,
+    /// A number does not fit in a `u32`.
+    NumberTooLarge,
+}
+
+impl fmt::Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseVersionError::Malformed => f.write_str("not a version of the form major.minor[.patch][-pre]"),
+            ParseVersionError::NumberTooLarge => f.write_str("version number too large"),
+        }
+    }
+}
+
+impl std::error::Error for ParseVersionError {}
+
+impl FromStr for Version {
+    type Err = ParseVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (numbers, pre) = match s.split_once('-') {
+            Some((numbers, pre)) => {
+                if pre.is_empty() || pre_release_len(pre) != pre.len() {
+                    return Err(ParseVersionError::Malformed);
+                }
+                (numbers, Some(pre.to_string()))
+            }
+            None => (s, None),
+        };
+        let (major, minor, patch) = parse_version_numbers(numbers)?;
+        Ok(Version { major, minor, patch, pre })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if let Some(patch) = self.patch {
+            write!(f, ".{patch}")?;
+        }
+        if let Some(pre) = &self.pre {
+            write!(f, "-{pre}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the `major.minor[.patch]` part of a version.
+fn parse_version_numbers(numbers: &str) -> Result<(u32, u32, Option<u32>), ParseVersionError> {
+    let parts: Vec<&str> = numbers.split('.').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return Err(ParseVersionError::Malformed);
+    }
+    let mut values = [0; 3];
+    for (value, part) in values.iter_mut().zip(&parts) {
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) || (part.len() > 1 && part.starts_with('0')) {
+            return Err(ParseVersionError::Malformed);
+        }
+        *value = part.parse().map_err(|_| ParseVersionError::NumberTooLarge)?;
+    }
+    Ok((values[0], values[1], (parts.len() == 3).then_some(values[2])))
+}
+
+/// Length of the pre-release tag at the start of `s`: ASCII letters and
+/// digits, starting with a letter. Zero if there is none.
+fn pre_release_len(s: &str) -> usize {
+    if s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        s.bytes().take_while(u8::is_ascii_alphanumeric).count()
+    } else {
+        0
+    }
+}
+
+/// Find the [`Version`] numbers in `s`, as `(start, end, version)` with
+/// `start..end` the byte range each one covers.
+///
+/// Dotted digit runs are matched the same way as for
+/// [`preserve_version_numbers`](SlugifyOptions::preserve_version_numbers);
+/// those that are not valid versions (`1.2.3.4`, `2024.03.15`) are skipped
+/// whole. A hyphen followed by a letter continues into a pre-release tag,
+/// while a hyphen followed by a digit, as in the range `2.10-2.12`, does not.
+/// Any word counts as a tag, so `app-1.2-linux` yields `1.2-linux`.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::extract_versions;
+///
+/// let found = extract_versions("app-v1.2.3-rc1 (was 0.9)");
+/// let spans: Vec<_> = found.iter().map(|(start, end, v)| (*start, *end, v.to_string())).collect();
+/// assert_eq!(spans, [(5, 14, "1.2.3-rc1".to_string()), (20, 23, "0.9".to_string())]);
+/// ```
+#[must_use]
+pub fn extract_versions(s: &str) -> Vec<(usize, usize, Version)> {
+    let mut versions = Vec::new();
+    let mut i = 0;
+
+    while let Some(offset) = s[i..].find(|c: char| c.is_ascii_digit()) {
+        let start = i + offset;
+        let Some(mut end) = version_end(s, start) else {
+            i = ascii_digits_end(s, start);
+            continue;
+        };
+        i = end;
+        let Ok((major, minor, patch)) = parse_version_numbers(&s[start..end]) else {
+            continue;
+        };
+        let mut pre = None;
+        if s[end..].starts_with('-') {
+            let len = pre_release_len(&s[end + 1..]);
+            if len > 0 {
+                pre = Some(s[end + 1..end + 1 + len].to_string());
+                end += 1 + len;
+                i = end;
+            }
+        }
+        versions.push((start, end, Version { major, minor, patch, pre }));
+    }
+
+    versions
+}
+
+/// Replace dots inside `YYYY.MM.DD` date sequences (e.g. "2024.03.15") with
+/// the same placeholder used for version numbers.
+///
+/// The year must be exactly four digits and month/day exactly two, with no
+/// digits directly before or after the sequence.
+fn preserve_date_dots(input: &str) -> String {
+    const DATE_LEN: usize = 10; // YYYY.MM.DD
+
+    let bytes = input.as_bytes();
+    let is_date_at = |i: usize| {
+        let Some(window) = bytes.get(i..i + DATE_LEN) else {
+            return false;
+        };
+        let shape_ok = window
+            .iter()
+            .enumerate()
+            .all(|(j, &b)| if j == 4 || j == 7 { b == b'.' } else { b.is_ascii_digit() });
+        let before_ok = i == 0 || !bytes[i - 1].is_ascii_digit();
+        let after_ok = bytes.get(i + DATE_LEN).is_none_or(|b| !b.is_ascii_digit());
+        shape_ok && before_ok && after_ok
+    };
+
+    let mut result = String::with_capacity(input.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if is_date_at(i) {
+            for &b in &bytes[i..i + DATE_LEN] {
+                result.push(if b == b'.' { VERSION_DOT } else { b as char });
+            }
+            i += DATE_LEN;
+        } else {
+            // May be multi-byte UTF-8
+            let ch = input[i..].chars().next().unwrap();
+            result.push(ch);
+            i += ch.len_utf8();
+        }
+    }
+
+    result
+}
+
+/// Restore placeholder bytes back to dots (and version range hyphens) after
+/// slugification.
+fn restore_version_dots(input: &str) -> String {
+    input.replace(VERSION_DOT, ".").replace(VERSION_RANGE_DASH, "-")
+}
+
+/// Maximum filename length in bytes (common filesystem limit).
+const MAX_FILENAME_BYTES: usize = 255;
+
+/// Maximum slug length in bytes for plain text slugification.
+/// Generous ceiling — no real slug should approach this, but it guards
+/// against unbounded output if someone feeds in very large input.
+const MAX_SLUG_BYTES: usize = 1024;
+
+/// Truncate `base` so that `base + ext` fits within `max_bytes`.
+///
+/// Prefers cutting at a separator boundary (dash, underscore) to avoid broken
+/// words, and falls back to a hard cut when there is no separator. Returns the
+/// base unchanged if it already fits. `ext` itself is never shortened.
+///
+/// When `base` is truncated, the result:
+///
+/// - is valid UTF-8 (never cut mid-character),
+/// - satisfies `result.len() + ext.len() <= max_bytes`, unless `ext` alone is
+///   longer than `max_bytes`, in which case the result is empty,
+/// - never ends with a separator or a dot.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::truncate_base;
+///
+/// assert_eq!(truncate_base("my-long-file-name", ".txt", 18), "my-long-file");
+/// assert_eq!(truncate_base("short", ".txt", 16), "short");
+/// assert_eq!(truncate_base("café-crème", "", 5), "café"); // "é" is 2 bytes
+/// ```
+#[must_use]
+pub fn truncate_base(base: &str, ext: &str, max_bytes: usize) -> String {
+    let budget = max_bytes.saturating_sub(ext.len());
+    if base.len() <= budget {
+        return base.to_string();
+    }
+    // A cut inside a version number can leave its dot behind (`app-7.` + `.zip`)
+    truncate_at_separator(base, budget, |c| c == '-' || c == '_').trim_end_matches(['-', '_', '.']).to_string()
+}
+
+/// [`truncate_base`], except that a Pascal case slug is cut before its last
+/// capital that fits, the word boundary it has instead of separators.
+fn truncate_styled(base: &str, ext: &str, max_bytes: usize, style: Style) -> String {
+    let budget = max_bytes.saturating_sub(ext.len());
+    if style != Style::Pascal || base.len() <= budget {
+        return truncate_base(base, ext, max_bytes);
+    }
+    let word_start = base.char_indices().rev().find(|&(i, c)| i > 0 && i <= budget && c.is_uppercase());
+    match word_start {
+        Some((i, _)) => base[..i].trim_end_matches(['-', '.']).to_string(),
+        None => truncate_base(base, ext, max_bytes),
+    }
+}
+
+/// Truncate a joined slug to at most `max_bytes`, cutting at the last `sep`
+/// that fits.
+///
+/// The result is valid UTF-8, at most `max_bytes` long, and never ends with
+/// `sep` — trailing separators are trimmed even when no truncation is needed.
+/// A slug with no `sep` within the budget is hard-truncated at a char
+/// boundary.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::truncate_slug;
+///
+/// assert_eq!(truncate_slug("the-quick-brown-fox", '-', 12), "the-quick");
+/// assert_eq!(truncate_slug("the_quick_brown_fox", '_', 12), "the_quick");
+/// assert_eq!(truncate_slug("TheQuickBrownFox", '-', 8), "TheQuick");
+/// ```
+#[must_use]
+pub fn truncate_slug(slug: &str, sep: char, max_bytes: usize) -> String {
+    if slug.len() <= max_bytes {
+        return slug.trim_end_matches(sep).to_string();
+    }
+    truncate_at_separator(slug, max_bytes, |c| c == sep).to_string()
+}
+
+/// Cut `s` to at most `budget` bytes, preferring the last separator within
+/// the budget. Trailing separators are trimmed from the result.
+fn truncate_at_separator(s: &str, budget: usize, is_sep: impl Fn(char) -> bool) -> &str {
+    // Floor to a char boundary to avoid slicing mid-character (keep_unicode mode)
+    let budget = (0..=budget.min(s.len())).rev().find(|&i| s.is_char_boundary(i)).unwrap_or(0);
+
+    // Truncate, then look for the last separator within budget
+    let truncated = &s[..budget];
+    let cut = match truncated.rfind(&is_sep) {
+        Some(pos) if pos > 0 => &truncated[..pos],
+        // No separator found — hard truncate at budget
+        _ => truncated,
+    };
+    cut.trim_end_matches(is_sep)
+}
+
+/// Split `word` before each capital that follows a letter or digit that
+/// isn't a capital: `MyFile2Go` → `My`, `File2`, `Go`. Runs of capitals stay
+/// together.
+fn split_case_transitions(word: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    for (i, c) in word.char_indices() {
+        if c.is_uppercase() && prev.is_some_and(|p| p.is_alphanumeric() && !p.is_uppercase()) {
+            parts.push(&word[start..i]);
+            start = i;
+        }
+        prev = Some(c);
+    }
+    parts.push(&word[start..]);
+    parts
+}
+
+/// Attach each single-letter word to the word before it, and a leading one
+/// to the word after it, so Pascal case never puts two capitals side by side
+/// (`it's my file` → `ItsMyFile`, not `ItSMyFile`). Two adjacent capitals
+/// would read back as one word when the slug is slugified again.
+fn join_single_letters(words: Vec<String>) -> Vec<String> {
+    let is_single_letter = |w: &str| {
+        let mut chars = w.chars();
+        chars.next().is_some_and(char::is_alphabetic) && chars.next().is_none()
+    };
+    let mut joined: Vec<String> = Vec::with_capacity(words.len());
+    for word in words {
+        let attach = match joined.as_slice() {
+            [] => false,
+            [first] => is_single_letter(first) || is_single_letter(&word),
+            _ => is_single_letter(&word),
+        };
+        match joined.last_mut() {
+            Some(last) if attach => last.push_str(&word),
+            _ => joined.push(word),
+        }
+    }
+    joined
+}
+
+/// Drop stop words and words below the minimum length.
+/// Falls back to the unfiltered words if nothing would be left.
+fn filter_words(words: Vec<String>, options: &SlugifyOptions) -> Vec<String> {
+    if options.stop_words.is_empty() && options.min_word_length <= 1 {
+        return words;
+    }
+
+    let stop: HashSet<String> = options.stop_words.iter().map(|w| w.to_lowercase()).collect();
+    let kept: Vec<String> = words
+        .iter()
+        .filter(|w| w.chars().count() >= options.min_word_length && !stop.contains(w.as_str()))
+        .cloned()
+        .collect();
+
+    if kept.is_empty() {
+        words
+    } else {
+        kept
+    }
+}
+
+/// Replace characters that have an entry in `map`, falling back to the
+/// lowercase form's entry for uppercase characters.
+fn apply_custom_transliteration(input: &str, map: &BTreeMap<char, String>) -> String {
+    let mut result = String::with_capacity(input.len());
+    for c in input.chars() {
+        let replacement = map.get(&c).or_else(|| {
+            let mut lower = c.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(l), None) if l != c => map.get(&l),
+                _ => None,
+            }
+        });
+        match replacement {
+            Some(r) => result.push_str(r),
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+/// Transliterate to ASCII, applying per-script policies when the
+/// `unicode-script` feature is enabled.
+fn transliterate(input: &str, options: &SlugifyOptions) -> String {
+    #[cfg(feature = "unicode-script")]
+    if let Some(policies) = &options.script_policies {
+        return script::apply_script_policies(input, policies, |run| transliterate_run(run, options));
+    }
+    transliterate_run(input, options)
+}
+
+/// Transliterate to ASCII, honoring the locale when the `locale` feature is enabled.
+fn transliterate_run(input: &str, options: &SlugifyOptions) -> String {
+    #[cfg(feature = "locale")]
+    if let Some(locale) = &options.locale {
+        return locale::locale_transliterate(input, locale);
+    }
+    #[cfg(not(feature = "locale"))]
+    let _ = options;
+    any_ascii::any_ascii(input)
+}
+
+/// One stage of the slugification pipeline, as reported by [`explain_slug`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlugStep {
+    /// Stage name, e.g. `"transliterate"`.
+    pub stage: String,
+    /// The intermediate value after this stage.
+    pub value: String,
+}
+
+/// Collects [`SlugStep`]s when enabled; recording is a no-op otherwise.
+struct Trace(Option<Vec<SlugStep>>);
+
+impl Trace {
+    fn off() -> Self {
+        Self(None)
+    }
+
+    fn on() -> Self {
+        Self(Some(Vec::new()))
+    }
+
+    /// Record a stage. `value` is only evaluated when tracing is enabled.
+    fn record(&mut self, stage: &str, value: impl FnOnce() -> String) {
+        if let Some(steps) = &mut self.0 {
+            steps.push(SlugStep { stage: stage.to_string(), value: value() });
+        }
+    }
+}
+
+/// Core slugification pipeline shared by [`slugify`] and [`slugify_string`].
+///
+/// Transliterates, strips brackets, preserves version dots, normalizes words,
+/// joins with the chosen separator, and restores version dots.
+fn slugify_core(input: &str, options: &SlugifyOptions, trace: &mut Trace) -> String {
+    let text = transliterate_step(input, options, trace);
+    let words = collect_words(&text, options, trace);
+    if words.is_empty() {
+        return String::new();
+    }
+
+    let words = filter_words(words, options);
+    trace.record("filter words", || format!("{words:?}"));
+
+    let slugified = join_words(&words, options, trace);
+
+    // Step 7: Keep a leading or trailing `_`/`-` from the original, if asked
+    let boundary = |c: Option<char>, keep: bool| c.filter(|c| keep && matches!(c, '_' | '-'));
+    let leading = boundary(input.chars().next(), options.preserve_leading_separator);
+    let trailing = boundary(input.chars().next_back(), options.preserve_trailing_separator);
+    if leading.is_none() && trailing.is_none() {
+        return slugified;
+    }
+    let slugified: String = leading.into_iter().chain(slugified.chars()).chain(trailing).collect();
+    trace.record("preserve boundary separators", || slugified.clone());
+    slugified
+}
+
+/// Step 1 of [`slugify_core`]: transliterate, after splitting camel case
+/// names if asked, collapsing whitespace, and any custom per-character
+/// overrides.
+fn transliterate_step(input: &str, options: &SlugifyOptions, trace: &mut Trace) -> String {
+    let input = match detect_case_convention(input) {
+        CaseConvention::CamelCase | CaseConvention::PascalCase if options.smart_case => {
+            let split = split_case_transitions(input).join(" ");
+            trace.record("smart case", || split.clone());
+            Cow::Owned(split)
+        }
+        _ => Cow::Borrowed(input),
+    };
+    let input = match normalise_whitespace(&input) {
+        Cow::Owned(normalised) if options.normalise_whitespace => {
+            trace.record("normalise whitespace", || normalised.clone());
+            Cow::Owned(normalised)
+        }
+        _ => input,
+    };
+    let input = match &options.custom_transliteration {
+        Some(map) => Cow::Owned(apply_custom_transliteration(&input, map)),
+        None => input,
+    };
+    let text = if options.keep_unicode {
+        input.into_owned()
+    } else {
+        transliterate(&input, options)
+    };
+    trace.record("transliterate", || text.clone());
+    text
+}
+
+/// Steps 2 to 4 of [`slugify_core`]: split transliterated text into
+/// lowercase words. Dots and hyphens inside dates and version numbers are
+/// left as placeholders for [`join_words`] to restore.
+fn collect_words(text: &str, options: &SlugifyOptions, trace: &mut Trace) -> Vec<String> {
+    // Step 2: Strip bracket characters, keep contents
+    let text = text.replace(['(', ')', '[', ']', '{', '}'], " ");
+    trace.record("strip brackets", || text.clone());
+
+    // Step 3: Preserve dots in dates (opt-in) and version numbers (e.g. "0.8.34")
+    let text = if options.preserve_date {
+        let text = preserve_date_dots(&text);
+        trace.record("preserve date dots", || text.clone());
+        text
+    } else {
+        text
+    };
+    let text = if options.preserve_version_numbers {
+        let text = preserve_version_dots(&preserve_version_range(&text));
+        trace.record("preserve version dots", || text.clone());
+        text
+    } else {
+        text
+    };
+
+    // Step 4: Normalize — collect words (sequences of alphanumeric/unicode chars,
+    // plus existing `-` and `_` when those are kept)
+    let kept_separator = |c: char| options.strip_punctuation_only && matches!(c, '-' | '_');
+    let words: Vec<&str> = if options.unicode_words() {
+        text.split(|c: char| !c.is_alphanumeric() && c != VERSION_DOT && c != VERSION_RANGE_DASH && !kept_separator(c))
+            .map(|s| s.trim_matches(['-', '_']))
+            .filter(|s| !s.is_empty())
+            .collect()
+    } else {
+        text.split(|c: char| {
+            !c.is_ascii_alphanumeric() && c != VERSION_DOT && c != VERSION_RANGE_DASH && !kept_separator(c)
+        })
+        .map(|s| s.trim_matches(['-', '_']))
+        .filter(|s| !s.is_empty())
+        .collect()
+    };
+    // A Pascal case slug is one run of letters, so read its capitals back as word
+    // starts; otherwise slugifying `MyFile` would give `Myfile`
+    let words: Vec<String> = if options.style == Style::Pascal {
+        join_single_letters(words.into_iter().flat_map(split_case_transitions).map(str::to_lowercase).collect())
+    } else {
+        words.into_iter().map(str::to_lowercase).collect()
+    };
+    trace.record("collect words", || format!("{words:?}"));
+    words
+}
+
+/// Steps 5 and 6 of [`slugify_core`]: join words with the style's separator
+/// and restore version (and date) dots.
+fn join_words(words: &[String], options: &SlugifyOptions, trace: &mut Trace) -> String {
+    // Step 5: Join with chosen separator
+    let slugified = match options.style {
+        Style::Kebab | Style::Snake if options.sentence_case => {
+            let joined = words.join(if options.style == Style::Kebab { "-" } else { "_" });
+            let mut chars = joined.chars();
+            chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+        }
+        Style::Kebab => words.join("-"),
+        Style::Snake => words.join("_"),
+        Style::Pascal => {
+            let mut result = String::new();
+            let mut prev: Option<&str> = None;
+            for word in words {
+                // A number glued onto a protected date changes its digit widths, so the
+                // next pass would no longer read a date there: keep them apart with `-`
+                let next_to_date = prev.is_some_and(|prev| {
+                    prev.ends_with(|c: char| c.is_ascii_digit())
+                        && word.starts_with(|c: char| c.is_ascii_digit())
+                        && (prev.contains(VERSION_DOT) || word.contains(VERSION_DOT))
+                });
+                if options.preserve_date && next_to_date {
+                    result.push(VERSION_RANGE_DASH);
+                }
+                prev = Some(word);
+                let mut chars = word.chars();
+                if let Some(first) = chars.next() {
+                    // Title case: `ß` becomes `Ss`, not `SS`, which would read back as one word
+                    let mut upper = first.to_uppercase();
+                    result.extend(upper.next());
+                    result.extend(upper.flat_map(char::to_lowercase));
+                    result.push_str(chars.as_str());
+                }
+            }
+            result
+        }
+    };
+    trace.record("join", || slugified.clone());
+
+    // Step 6: Restore version (and date) dots
+    if !options.preserve_version_numbers && !options.preserve_date {
+        return slugified;
+    }
+    let slugified = restore_version_dots(&slugified);
+    trace.record("restore version dots", || slugified.clone());
+    slugified
+}
+
+/// Catch invalid options early in debug builds.
+fn debug_assert_valid(options: &SlugifyOptions) {
+    if cfg!(debug_assertions) {
+        if let Err(e) = options.validate() {
+            panic!("{e}");
+        }
+    }
+}
+
+/// Slugify a filename according to the given options.
+///
+/// Converts a filename to a clean, shell-safe slug while preserving its
+/// extension, dotfile status, and any embedded version numbers. Returns
+/// [`Cow::Borrowed`] when the input is already clean (e.g. dotfiles).
+///
+/// Names exceeding 255 bytes are silently truncated at a word boundary.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default();
+/// assert_eq!(slugify("My Résumé (Final).pdf", &opts), "my-resume-final.pdf");
+/// assert_eq!(slugify(".gitignore", &opts), ".gitignore");
+/// assert_eq!(slugify("app-1.2.3.dmg", &opts), "app-1.2.3.dmg");
+/// assert_eq!(slugify("Photo 2024_01.JPG", &opts), "photo-2024-01.JPG");
+/// ```
+#[must_use]
+pub fn slugify<'a>(filename: &'a str, options: &SlugifyOptions) -> Cow<'a, str> {
+    debug_assert_valid(options);
+    let slug = slugify_traced(filename, options, false, &mut Trace::off());
+    if options.warn_on_unchanged && slug == filename {
+        DIAGNOSTICS.with_borrow_mut(|d| d.push(SlugDiagnostic::Unchanged { input: filename.to_string() }));
+    }
+    slug
+}
+
+/// A note about a [`slugify`] call, collected with [`take_diagnostics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SlugDiagnostic {
+    /// The input was already clean, so the slug is the input itself.
+    /// Recorded with [`SlugifyOptions::warn_on_unchanged`].
+    Unchanged { input: String },
+}
+
+thread_local! {
+    static DIAGNOSTICS: RefCell<Vec<SlugDiagnostic>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Remove and return the diagnostics recorded on this thread, oldest first.
+///
+/// Diagnostics are kept per thread: those from [`slugify`] calls on other
+/// threads, such as the workers of `slugify_batch_rayon` with the `parallel`
+/// feature, stay on those threads.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify, take_diagnostics, SlugDiagnostic, SlugifyOptions};
+///
+/// let opts = SlugifyOptions { warn_on_unchanged: true, ..Default::default() };
+/// let _ = slugify("clean-name.txt", &opts);
+/// let _ = slugify("Messy Name.txt", &opts);
+/// assert_eq!(take_diagnostics(), [SlugDiagnostic::Unchanged { input: "clean-name.txt".into() }]);
+/// assert!(take_diagnostics().is_empty());
+/// ```
+#[must_use]
+pub fn take_diagnostics() -> Vec<SlugDiagnostic> {
+    DIAGNOSTICS.take()
+}
+
+/// Slugify an OS filename, which need not be valid UTF-8.
+///
+/// Undecodable bytes become U+FFFD (as with [`OsStr::to_string_lossy`]) and
+/// then act as word breaks, so they never reach the result. An extension
+/// containing them is not kept as an extension; see [`split_extension_os`].
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+/// use fileslug::{slugify_os, SlugifyOptions};
+///
+/// assert_eq!(slugify_os(OsStr::new("My File.txt"), &SlugifyOptions::default()), "my-file.txt");
+/// ```
+#[must_use]
+pub fn slugify_os(filename: &OsStr, options: &SlugifyOptions) -> OsString {
+    slugify(&filename.to_string_lossy(), options).into_owned().into()
+}
+
+/// Slugify every component of `path` without touching the filesystem, e.g.
+/// to normalize URLs or virtual paths.
+///
+/// Directory components are slugified as plain text with [`slugify_string`]
+/// (keeping a leading `.` on hidden directories), and the last component as
+/// a filename with [`slugify`]. The root, prefixes, `.` and `..` are kept,
+/// as is any component that slugifies to nothing, so the path keeps its
+/// shape. Use the CLI's recursive mode to rename real directory trees.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use fileslug::{slugify_path_components, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default();
+/// assert_eq!(
+///     slugify_path_components(Path::new("/My Documents/Sub Folder/My File.txt"), &opts),
+///     Path::new("/my-documents/sub-folder/my-file.txt"),
+/// );
+/// assert_eq!(slugify_path_components(Path::new("../Old.Notes/Draft v1.2.md"), &opts), Path::new("../old-notes/draft-v1.2.md"));
+/// ```
+#[must_use]
+pub fn slugify_path_components(path: &Path, options: &SlugifyOptions) -> PathBuf {
+    let mut components = path.components().peekable();
+    let mut result = PathBuf::new();
+    while let Some(component) = components.next() {
+        let Component::Normal(name) = component else {
+            result.push(component);
+            continue;
+        };
+        let slug: OsString = if components.peek().is_none() {
+            slugify_os(name, options)
+        } else {
+            let name = name.to_string_lossy();
+            let slug = slugify_string(&name, options);
+            match name.strip_prefix('.') {
+                Some(_) if !slug.is_empty() => format!(".{slug}").into(),
+                _ => slug.into(),
+            }
+        };
+        result.push(if slug.is_empty() { name } else { &slug });
+    }
+    result
+}
+
+/// [`slugify_path_components`] as a string for display, with the platform's
+/// separator (`\` on Windows, so `C:/My Docs/A.txt` shows as
+/// `C:\my-docs\a.txt`). Names that aren't valid UTF-8 are shown lossily.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::{Path, MAIN_SEPARATOR as SEP};
+/// use fileslug::{slugify_path_display, SlugifyOptions};
+///
+/// let shown = slugify_path_display(Path::new("/My Projects/Sub Work/File.txt"), &SlugifyOptions::default());
+/// assert_eq!(shown, format!("{SEP}my-projects{SEP}sub-work{SEP}file.txt"));
+/// ```
+#[must_use]
+pub fn slugify_path_display(path: &Path, options: &SlugifyOptions) -> String {
+    let slugified = slugify_path_components(path, options);
+    join_for_display(slugified.components().map(|c| (c, c.as_os_str().to_string_lossy().into_owned())))
+}
+
+/// `original` and `slugified` side by side, component by component: a
+/// component that changed is shown as `[-old-]{+new+}`, in the markup of
+/// [`format_slug_diff`], and one that didn't as it is.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::{Path, MAIN_SEPARATOR as SEP};
+/// use fileslug::paths_diff_display;
+///
+/// let shown = paths_diff_display(Path::new("/My Projects/docs/File.txt"), Path::new("/my-projects/docs/file.txt"));
+/// assert_eq!(shown, format!("{SEP}[-My Projects-]{{+my-projects+}}{SEP}docs{SEP}[-File.txt-]{{+file.txt+}}"));
+/// ```
+#[must_use]
+pub fn paths_diff_display(original: &Path, slugified: &Path) -> String {
+    let text = |c: Component<'_>| escape_controls(&c.as_os_str().to_string_lossy());
+    let mut old = original.components();
+    let mut new = slugified.components();
+    join_for_display(std::iter::from_fn(|| match (old.next(), new.next()) {
+        (Some(a), Some(b)) if a == b => Some((b, text(b))),
+        (Some(a), Some(b)) => Some((b, format!("[-{}-]{{+{}+}}", text(a), text(b)))),
+        (Some(a), None) => Some((a, format!("[-{}-]", text(a)))),
+        (None, Some(b)) => Some((b, format!("{{+{}+}}", text(b)))),
+        (None, None) => None,
+    }))
+}
+
+/// Join rendered path components with the platform's separator. A prefix
+/// (`C:`) and the root already end where the next component starts.
+fn join_for_display<'a>(parts: impl IntoIterator<Item = (Component<'a>, String)>) -> String {
+    let mut out = String::new();
+    let mut needs_separator = false;
+    for (component, text) in parts {
+        match component {
+            Component::Prefix(_) => {
+                out.push_str(&text);
+                needs_separator = false;
+            }
+            Component::RootDir => {
+                out.push(MAIN_SEPARATOR);
+                needs_separator = false;
+            }
+            _ => {
+                if needs_separator {
+                    out.push(MAIN_SEPARATOR);
+                }
+                out.push_str(&text);
+                needs_separator = true;
+            }
+        }
+    }
+    out
+}
+
+/// Slugify only the stem of a filename and reattach the extension verbatim.
+///
+/// The extension is whatever [`split_extension`] (or the configured
+/// [`SplitExtensionOptions`]) returns, and is never transformed: this is
+/// [`slugify`] with [`ExtensionConfig::normalize`] and
+/// [`SlugifyOptions::cross_platform`] turned off.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_stem, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default();
+/// assert_eq!(slugify_stem("My Document.PDF", &opts), "my-document.PDF");
+/// ```
+#[must_use]
+pub fn slugify_stem<'a>(filename: &'a str, options: &SlugifyOptions) -> Cow<'a, str> {
+    // Without the two settings that change the extension, slugify leaves it as it is
+    if !options.extensions.normalize && !options.cross_platform {
+        return slugify(filename, options);
+    }
+    let mut options = options.clone();
+    options.extensions.normalize = false;
+    options.cross_platform = false;
+    slugify(filename, &options)
+}
+
+/// Slugify a filename including its extension.
+///
+/// Each dot-separated extension segment is transliterated, lowercased and
+/// stripped of separators. Segments that normalize to nothing are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_ext_too, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default();
+/// assert_eq!(slugify_ext_too("My Document.PDF", &opts), "my-document.pdf");
+/// assert_eq!(slugify_ext_too("Backup.Tar.GZ", &opts), "backup.tar.gz");
+/// ```
+#[must_use]
+pub fn slugify_ext_too<'a>(filename: &'a str, options: &SlugifyOptions) -> Cow<'a, str> {
+    debug_assert_valid(options);
+    slugify_traced(filename, options, true, &mut Trace::off())
+}
+
+/// Run each dot-separated segment of `ext` through the slug pipeline.
+/// Segments always come out lowercase with no separators, whatever the style.
+fn slugify_extension(ext: &str, options: &SlugifyOptions) -> String {
+    let options = SlugifyOptions { style: Style::Snake, ..options.clone() };
+    let mut result = String::with_capacity(ext.len());
+    for segment in ext.split('.').filter(|s| !s.is_empty()) {
+        let segment = slugify_core(segment, &options, &mut Trace::off()).replace('_', "");
+        if !segment.is_empty() {
+            result.push('.');
+            result.push_str(&segment);
+        }
+    }
+    result
+}
+
+fn slugify_traced<'a>(
+    filename: &'a str,
+    options: &SlugifyOptions,
+    normalize_ext: bool,
+    trace: &mut Trace,
+) -> Cow<'a, str> {
+    if !options.cross_platform {
+        return slugify_pipeline(filename, options, normalize_ext, trace);
+    }
+    // Windows drops trailing dots and spaces; do it before the extension is
+    // split off, or `notes 1.2 .` would keep `1.2` whole now but split off
+    // `.2` as its extension when slugified again
+    let filename = match filename.trim_end_matches(['.', ' ']) {
+        "" => filename,
+        trimmed => trimmed,
+    };
+    let slug = slugify_pipeline(filename, options, normalize_ext, trace);
+    match make_cross_platform(&slug) {
+        Cow::Borrowed(_) => slug,
+        Cow::Owned(safe) => {
+            trace.record("cross-platform", || safe.clone());
+            Cow::Owned(safe)
+        }
+    }
+}
+
+fn slugify_pipeline<'a>(
+    filename: &'a str,
+    options: &SlugifyOptions,
+    normalize_ext: bool,
+    trace: &mut Trace,
+) -> Cow<'a, str> {
+    if filename.is_empty() {
+        return Cow::Borrowed("");
+    }
+
+    // Null bytes and other controls never belong in a slug, and a stray
+    // U+0001 would collide with the version-dot placeholder
+    if let Cow::Owned(clean) = sanitize_control_chars(filename) {
+        trace.record("strip control characters", || clean.clone());
+        return Cow::Owned(slugify_pipeline(&clean, options, normalize_ext, trace).into_owned());
+    }
+
+    // Bidi controls can disguise the real extension, so drop them before splitting
+    if let Cow::Owned(clean) = strip_direction_overrides(filename) {
+        trace.record("strip direction overrides", || clean.clone());
+        return Cow::Owned(slugify_pipeline(&clean, options, normalize_ext, trace).into_owned());
+    }
+
+    // A backslash after the last dot would otherwise be kept in the extension
+    if let Cow::Owned(normalized) = normalize_path_separators(filename) {
+        trace.record("normalize path separators", || normalized.clone());
+        return Cow::Owned(slugify_pipeline(&normalized, options, normalize_ext, trace).into_owned());
+    }
+
+    let extensions = options.extension_config();
+    let (base, ext) = extensions.split_extension(filename);
+    trace.record("split extension", || format!("{base:?} + {ext:?}"));
+
+    let base = if options.strip_copy_annotations {
+        let stripped = strip_copy_suffixes(base);
+        if stripped.len() != base.len() {
+            trace.record("strip copy annotations", || stripped.to_string());
+        }
+        stripped
+    } else {
+        base
+    };
+
+    let ext: Cow<'_, str> = if normalize_ext {
+        let normalized = slugify_extension(ext, options);
+        trace.record("normalize extension", || normalized.clone());
+        Cow::Owned(normalized)
+    } else {
+        Cow::Borrowed(ext)
+    };
+    let ext = match canonical_extension(&ext) {
+        Some(canonical) if extensions.normalize => {
+            trace.record("canonical extension", || canonical.clone());
+            Cow::Owned(canonical)
+        }
+        _ => ext,
+    };
+
+    // Dotfiles with no base: return as-is
+    if base.is_empty() {
+        return Cow::Borrowed(filename);
+    }
+
+    let is_dotfile = base.starts_with('.');
+
+    let slugified = slugify_core(base, options, trace);
+
+    if slugified.is_empty() {
+        return Cow::Owned(ext.to_string());
+    }
+
+    // Restore leading dot for dotfiles (e.g. .env.local → .env.local)
+    let slugified = if is_dotfile {
+        format!(".{slugified}")
+    } else {
+        slugified
+    };
+
+    // Truncate if filename would exceed filesystem limit
+    let max_bytes = options.max_length.map_or(MAX_FILENAME_BYTES, |max| max.min(MAX_FILENAME_BYTES));
+    let slugified = truncate_styled(&slugified, &ext, max_bytes, options.style);
+    trace.record("truncate", || slugified.clone());
+
+    let result = format!("{slugified}{ext}");
+    trace.record("reattach extension", || result.clone());
+    Cow::Owned(result)
+}
+
+/// Run [`slugify`] and return each intermediate pipeline stage.
+///
+/// Stages are: split extension, transliterate, strip brackets, preserve
+/// version dots, collect words, filter words, join, restore version dots,
+/// truncate, and reattach extension, with "canonical extension" after the
+/// split if [`ExtensionConfig::normalize`] replaced an alias, and
+/// "cross-platform" at the end if
+/// [`SlugifyOptions::cross_platform`] changed anything. Names containing bidirectional control
+/// or control characters get a leading "strip direction overrides" or "strip
+/// control characters" stage, names with backslashes a "normalize path
+/// separators" stage, and names with Unicode whitespace a "normalise
+/// whitespace" stage before transliterating. With
+/// [`SlugifyOptions::smart_case`], camel and Pascal case names get a "smart
+/// case" stage first. With [`SlugifyOptions::strip_copy_annotations`], a
+/// "strip copy annotations" stage follows the split when one was removed. With
+/// [`preserve_leading_separator`](SlugifyOptions::preserve_leading_separator)
+/// or its trailing counterpart, a "preserve boundary separators" stage
+/// follows the join when a separator was kept. The trace stops
+/// early when the pipeline does (e.g. for dotfiles, or names with no words).
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{explain_slug, SlugifyOptions};
+///
+/// let steps = explain_slug("Café (Final).pdf", &SlugifyOptions::default());
+/// assert_eq!(steps[1].stage, "transliterate");
+/// assert_eq!(steps[1].value, "Cafe (Final)");
+/// assert_eq!(steps.last().unwrap().value, "cafe-final.pdf");
+/// ```
+#[must_use]
+pub fn explain_slug(filename: &str, options: &SlugifyOptions) -> Vec<SlugStep> {
+    let mut trace = Trace::on();
+    let _ = slugify_traced(filename, options, false, &mut trace);
+    trace.0.unwrap_or_default()
+}
+
+/// The stages of [`slugify`], callable one at a time, so a caller can replace
+/// or adjust one stage and still use the others.
+///
+/// Chaining [`split`](Self::split), [`transliterate`](Self::transliterate),
+/// [`collect_words`](Self::collect_words), [`join`](Self::join), and
+/// [`reassemble`](Self::reassemble) gives the same slug as [`slugify`] for
+/// ordinary names. `slugify` also strips control characters, leaves dotfiles
+/// alone, keeps boundary separators if
+/// [`preserve_leading_separator`](SlugifyOptions::preserve_leading_separator)
+/// or its trailing counterpart is set, and applies
+/// [`SlugifyOptions::extensions`] and [`SlugifyOptions::cross_platform`] to
+/// the result.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify, SlugPipeline, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default();
+/// let pipeline = SlugPipeline::new(&opts);
+///
+/// let (base, ext) = pipeline.split("Café (Final) v1.2.txt");
+/// let mut words = pipeline.collect_words(&pipeline.transliterate(base));
+/// words.retain(|word| word != "final");
+/// assert_eq!(pipeline.reassemble(&pipeline.join(&words), ext), "cafe-v1.2.txt");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SlugPipeline<'a> {
+    options: &'a SlugifyOptions,
+}
+
+impl<'a> SlugPipeline<'a> {
+    /// A pipeline running each stage with `options`.
+    #[must_use]
+    pub fn new(options: &'a SlugifyOptions) -> Self {
+        debug_assert_valid(options);
+        SlugPipeline { options }
+    }
+
+    /// Split `filename` into `(base, extension)`, as set by
+    /// [`SlugifyOptions::extensions`], dropping copy annotations from the
+    /// base under [`SlugifyOptions::strip_copy_annotations`].
+    ///
+    /// ```
+    /// # use fileslug::{SlugPipeline, SlugifyOptions};
+    /// let opts = SlugifyOptions::default();
+    /// assert_eq!(SlugPipeline::new(&opts).split("My File.txt"), ("My File", ".txt"));
+    /// ```
+    #[must_use]
+    pub fn split<'s>(&self, filename: &'s str) -> (&'s str, &'s str) {
+        let (base, ext) = self.options.extensions.split_extension(filename);
+        if self.options.strip_copy_annotations {
+            (strip_copy_suffixes(base), ext)
+        } else {
+            (base, ext)
+        }
+    }
+
+    /// Transliterate `text` to ASCII, unless
+    /// [`keep_unicode`](SlugifyOptions::keep_unicode) is set, after collapsing
+    /// whitespace and applying any custom transliteration.
+    ///
+    /// ```
+    /// # use fileslug::{SlugPipeline, SlugifyOptions};
+    /// let opts = SlugifyOptions::default();
+    /// assert_eq!(SlugPipeline::new(&opts).transliterate("Café"), "Cafe");
+    /// ```
+    #[must_use]
+    pub fn transliterate(&self, text: &str) -> String {
+        transliterate_step(text, self.options, &mut Trace::off())
+    }
+
+    /// The lowercase words of `text`, without stop words and words shorter
+    /// than [`min_word_length`](SlugifyOptions::min_word_length). Version
+    /// numbers and (optionally) dates stay whole.
+    ///
+    /// ```
+    /// # use fileslug::{SlugPipeline, SlugifyOptions};
+    /// let opts = SlugifyOptions::default();
+    /// let pipeline = SlugPipeline::new(&opts);
+    /// assert_eq!(pipeline.collect_words("My File"), ["my", "file"]);
+    /// assert_eq!(pipeline.collect_words("Release [v2.0.1]"), ["release", "v2.0.1"]);
+    /// ```
+    #[must_use]
+    pub fn collect_words(&self, text: &str) -> Vec<String> {
+        let words = collect_words(text, self.options, &mut Trace::off());
+        filter_words(words, self.options).iter().map(|word| restore_version_dots(word)).collect()
+    }
+
+    /// Join `words` in the options' [`Style`].
+    ///
+    /// ```
+    /// # use fileslug::{SlugPipeline, SlugifyOptions, Style};
+    /// let words = ["my".to_string(), "file".to_string()];
+    /// let opts = SlugifyOptions::default();
+    /// assert_eq!(SlugPipeline::new(&opts).join(&words), "my-file");
+    /// let opts = SlugifyOptions { style: Style::Pascal, ..Default::default() };
+    /// assert_eq!(SlugPipeline::new(&opts).join(&words), "MyFile");
+    /// ```
+    #[must_use]
+    pub fn join(&self, words: &[String]) -> String {
+        join_words(words, self.options, &mut Trace::off())
+    }
+
+    /// Put a slugified `base` and its extension back together, shortening
+    /// `base` to respect [`max_length`](SlugifyOptions::max_length) and the
+    /// 255-byte filename limit.
+    ///
+    /// ```
+    /// # use fileslug::{SlugPipeline, SlugifyOptions};
+    /// let opts = SlugifyOptions { max_length: Some(12), ..Default::default() };
+    /// let pipeline = SlugPipeline::new(&opts);
+    /// assert_eq!(pipeline.reassemble("my-file", ".txt"), "my-file.txt");
+    /// assert_eq!(pipeline.reassemble("my-long-file", ".txt"), "my-long.txt");
+    /// ```
+    #[must_use]
+    pub fn reassemble(&self, base: &str, ext: &str) -> String {
+        if base.is_empty() {
+            return ext.to_string();
+        }
+        let max_bytes = self.options.max_length.map_or(MAX_FILENAME_BYTES, |max| max.min(MAX_FILENAME_BYTES));
+        format!("{}{ext}", truncate_styled(base, ext, max_bytes, self.options.style))
+    }
+}
+
+/// Format a trace from [`explain_slug`] as aligned, human-readable lines.
+/// Control characters (such as the version-dot placeholder) are escaped.
+#[must_use]
+pub fn format_slug_trace(steps: &[SlugStep]) -> String {
+    use std::fmt::Write as _;
+
+    let width = steps.iter().map(|s| s.stage.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for step in steps {
+        // Writing to a String cannot fail
+        let _ = writeln!(out, "{:<width$}  {}", step.stage, escape_controls(&step.value));
+    }
+    out
+}
+
+/// `s` with control characters replaced by their escape sequences.
+fn escape_controls(s: &str) -> String {
+    s.chars()
+        .map(|c| if c.is_control() { c.escape_default().to_string() } else { c.to_string() })
+        .collect()
+}
+
+/// One run of characters in a [`slug_diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlugDiffOp {
+    /// Present in both the original and the slug.
+    Retained(String),
+    /// Only in the slug.
+    Added(String),
+    /// Only in the original.
+    Removed(String),
+}
+
+/// Character-level diff from `original` to `slugified`, as runs of retained,
+/// added, and removed text. Within a change, removals come before additions.
+///
+/// Uses a longest-common-subsequence diff, which is quadratic in the input
+/// length; filenames are short enough for that not to matter.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slug_diff, SlugDiffOp};
+///
+/// let diff = slug_diff("My File.txt", "my-file.txt");
+/// assert_eq!(diff[0], SlugDiffOp::Removed("M".into()));
+/// assert_eq!(diff[1], SlugDiffOp::Added("m".into()));
+/// assert_eq!(diff[2], SlugDiffOp::Retained("y".into()));
+/// ```
+#[must_use]
+pub fn slug_diff(original: &str, slugified: &str) -> Vec<SlugDiffOp> {
+    let a: Vec<char> = original.chars().collect();
+    let b: Vec<char> = slugified.chars().collect();
+
+    // lcs[i][j]: length of the longest common subsequence of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    // Removals and additions between two retained runs, emitted in that order
+    let mut added = Vec::new();
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            for c in added.drain(..) {
+                push_diff_char(&mut ops, SlugDiffOp::Added, c);
+            }
+            push_diff_char(&mut ops, SlugDiffOp::Retained, a[i]);
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] > lcs[i + 1][j]) {
+            added.push(b[j]);
+            j += 1;
+        } else {
+            push_diff_char(&mut ops, SlugDiffOp::Removed, a[i]);
+            i += 1;
+        }
+    }
+    for c in added {
+        push_diff_char(&mut ops, SlugDiffOp::Added, c);
+    }
+    ops
+}
+
+/// Append `c` to the last op if it is of the same kind, else start a new run.
+fn push_diff_char(ops: &mut Vec<SlugDiffOp>, kind: fn(String) -> SlugDiffOp, c: char) {
+    let op = kind(c.to_string());
+    match (ops.last_mut(), &op) {
+        (Some(SlugDiffOp::Retained(s)), SlugDiffOp::Retained(_))
+        | (Some(SlugDiffOp::Added(s)), SlugDiffOp::Added(_))
+        | (Some(SlugDiffOp::Removed(s)), SlugDiffOp::Removed(_)) => s.push(c),
+        _ => ops.push(op),
+    }
+}
+
+/// Format a [`slug_diff`] on one line, marking removed text as `[-text-]`
+/// and added text as `{+text+}` (the markers `git diff --word-diff=plain`
+/// uses). Control characters are escaped.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{format_slug_diff, slug_diff};
+///
+/// let diff = slug_diff("My File.txt", "my-file.txt");
+/// assert_eq!(format_slug_diff(&diff), "[-M-]{+m+}y[- F-]{+-f+}ile.txt");
+/// ```
+#[must_use]
+pub fn format_slug_diff(diff: &[SlugDiffOp]) -> String {
+    let mut out = String::new();
+    for op in diff {
+        match op {
+            SlugDiffOp::Retained(s) => out.push_str(&escape_controls(s)),
+            SlugDiffOp::Added(s) => {
+                out.push_str("{+");
+                out.push_str(&escape_controls(s));
+                out.push_str("+}");
+            }
+            SlugDiffOp::Removed(s) => {
+                out.push_str("[-");
+                out.push_str(&escape_controls(s));
+                out.push_str("-]");
+            }
+        }
+    }
+    out
+}
+
+/// Slugify an arbitrary string (not a filename).
+///
+/// Unlike [`slugify`], this treats the entire input as plain text — no
+/// extension splitting, no dotfile preservation. Use this for generating
+/// URL slugs, identifiers, or other non-filename use cases.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_string, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default();
+/// assert_eq!(slugify_string("My Blog Post Title!", &opts), "my-blog-post-title");
+/// assert_eq!(slugify_string("Café Résumé", &opts), "cafe-resume");
+/// ```
+#[must_use]
+pub fn slugify_string(input: &str, options: &SlugifyOptions) -> String {
+    debug_assert_valid(options);
+    if input.is_empty() {
+        return String::new();
+    }
+
+    let input = sanitize_control_chars(input);
+    let input = strip_direction_overrides(&input);
+    let slugified = slugify_core(&input, options, &mut Trace::off());
+
+    if slugified.is_empty() {
+        return String::new();
+    }
+
+    let max_bytes = options.max_length.map_or(MAX_SLUG_BYTES, |max| max.min(MAX_SLUG_BYTES));
+    let slugified = truncate_styled(&slugified, "", max_bytes, options.style);
+    if options.cross_platform {
+        make_cross_platform(&slugified).into_owned()
+    } else {
+        slugified
+    }
+}
+
+/// Like [`slugify_string`], but in sentence case: the first letter of the
+/// slug is capitalized and the rest is lowercase, as for blog post titles or
+/// commit subjects. Equivalent to setting
+/// [`sentence_case`](SlugifyOptions::sentence_case); with [`Style::Pascal`]
+/// every word is capitalized as usual.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_sentence, SlugifyOptions, Style};
+///
+/// let opts = SlugifyOptions::default();
+/// assert_eq!(slugify_sentence("MY GREAT BLOG POST", &opts), "My-great-blog-post");
+///
+/// let opts = SlugifyOptions { style: Style::Snake, ..Default::default() };
+/// assert_eq!(slugify_sentence("fix the parser", &opts), "Fix_the_parser");
+/// ```
+#[must_use]
+pub fn slugify_sentence<'a>(input: &'a str, options: &SlugifyOptions) -> Cow<'a, str> {
+    let options = SlugifyOptions { sentence_case: true, ..options.clone() };
+    let slug = slugify_string(input, &options);
+    if slug == input {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(slug)
+    }
+}
+
+/// A platform whose filename rules [`is_safe_for_all_platforms`] checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    Linux,
+    MacOs,
+    Windows,
+    /// FAT32 volumes such as USB sticks and SD cards, on any OS.
+    Fat32,
+}
+
+/// One problem found by [`is_safe_for_all_platforms`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FileSafetyIssue {
+    /// The name is empty.
+    Empty,
+    /// The name is `.` or `..`.
+    DotEntry,
+    /// The name contains a character that is not allowed.
+    ForbiddenChar(char),
+    /// The name contains a control character (U+0001 to U+001F).
+    ControlChar(char),
+    /// The name contains a character outside ASCII.
+    NonAscii(char),
+    /// The name, ignoring any extension, is a DOS device name such as `CON` or `LPT1`.
+    ReservedName(String),
+    /// The name ends with a dot or a space, which Windows strips.
+    TrailingDotOrSpace,
+    /// The name is longer than `platform` allows.
+    TooLong { platform: Platform, length: usize, max: usize },
+}
+
+impl FileSafetyIssue {
+    /// The platforms on which this issue makes the name unusable.
+    #[must_use]
+    pub fn platforms(&self) -> &'static [Platform] {
+        use Platform::{Fat32, Linux, MacOs, Windows};
+        match self {
+            Self::Empty | Self::DotEntry | Self::ForbiddenChar('\0' | '/') => &[Linux, MacOs, Windows, Fat32],
+            Self::ForbiddenChar(':') => &[MacOs, Windows, Fat32],
+            Self::ForbiddenChar(c) if WINDOWS_FORBIDDEN.contains(c) => &[Windows, Fat32],
+            Self::ForbiddenChar(_) | Self::NonAscii(_) | Self::TooLong { platform: Fat32, .. } => &[Fat32],
+            Self::ControlChar(_) | Self::ReservedName(_) | Self::TrailingDotOrSpace => &[Windows, Fat32],
+            Self::TooLong { platform: Linux, .. } => &[Linux],
+            Self::TooLong { platform: MacOs, .. } => &[MacOs],
+            Self::TooLong { platform: Windows, .. } => &[Windows],
+        }
+    }
+}
+
+impl fmt::Display for FileSafetyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("name is empty"),
+            Self::DotEntry => f.write_str("name is '.' or '..'"),
+            Self::ForbiddenChar('\0') => f.write_str("contains a NUL byte"),
+            Self::ForbiddenChar(c) => write!(f, "contains '{c}'"),
+            Self::ControlChar(c) => write!(f, "contains control character U+{:04X}", u32::from(*c)),
+            Self::NonAscii(c) => write!(f, "contains non-ASCII '{c}'"),
+            Self::ReservedName(name) => write!(f, "'{name}' is a reserved device name"),
+            Self::TrailingDotOrSpace => f.write_str("ends with a dot or space"),
+            Self::TooLong { platform, length, max } => write!(f, "{length} long, {platform:?} allows {max}"),
+        }
+    }
+}
+
+/// Which platforms accept a filename, and why the others do not.
+/// Returned by [`is_safe_for_all_platforms`].
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSafetyReport {
+    pub linux: bool,
+    pub macos: bool,
+    pub windows: bool,
+    pub fat32: bool,
+    /// Every problem found, each listing the platforms it affects.
+    pub issues: Vec<FileSafetyIssue>,
+}
+
+impl FileSafetyReport {
+    /// Whether the name is usable on every checked platform.
+    #[must_use]
+    pub fn is_safe_everywhere(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Characters Windows (and FAT32) reject in names, besides `/` and controls.
+const WINDOWS_FORBIDDEN: &[char] = &['<', '>', ':', '"', '\\', '|', '?', '*'];
+
+/// Characters FAT32 rejects on top of Windows' list.
+const FAT32_FORBIDDEN: &[char] = &['+', ',', ';', '=', '[', ']'];
+
+/// DOS device names, reserved on Windows with or without an extension.
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "LPT1", "LPT2",
+    "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Longest name accepted by Linux and FAT32 (bytes) and macOS (UTF-16 units).
+const MAX_NAME_LENGTH: usize = 255;
+
+/// Longest name accepted by Windows, in UTF-16 units (`MAX_PATH`).
+const MAX_WINDOWS_NAME_LENGTH: usize = 260;
+
+/// The part of `name` checked against [`WINDOWS_RESERVED_NAMES`]: everything
+/// before the first dot, ignoring trailing spaces (`con .txt` is reserved too).
+fn reserved_stem(name: &str) -> Option<&str> {
+    let stem = name.split('.').next().unwrap_or(name).trim_end_matches(' ');
+    WINDOWS_RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(stem)).then_some(stem)
+}
+
+/// Check a single filename (not a path) against the naming rules of Linux,
+/// macOS, Windows, and FAT32.
+///
+/// - Linux: no NUL or `/`, at most 255 bytes
+/// - macOS: additionally no `:`, at most 255 UTF-16 units
+/// - Windows: no `<>:"/\|?*` or control characters, not a device name such
+///   as `CON` or `LPT1` (with or without an extension), no trailing dot or
+///   space, at most 260 UTF-16 units
+/// - FAT32: Windows' rules, plus ASCII only and no `+,;=[]`, at most 255 bytes
+///
+/// Every name is rejected everywhere if it is empty, `.`, or `..`.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{is_safe_for_all_platforms, FileSafetyIssue, Platform};
+///
+/// assert!(is_safe_for_all_platforms("my-file.txt").is_safe_everywhere());
+///
+/// let report = is_safe_for_all_platforms("aux.log");
+/// assert!(report.linux && report.macos && !report.windows && !report.fat32);
+/// assert_eq!(report.issues, [FileSafetyIssue::ReservedName("aux".into())]);
+/// assert_eq!(report.issues[0].platforms(), [Platform::Windows, Platform::Fat32]);
+/// ```
+#[must_use]
+pub fn is_safe_for_all_platforms(filename: &str) -> FileSafetyReport {
+    let mut issues = Vec::new();
+
+    if filename.is_empty() {
+        issues.push(FileSafetyIssue::Empty);
+    } else if filename == "." || filename == ".." {
+        issues.push(FileSafetyIssue::DotEntry);
+    }
+
+    let mut seen = HashSet::new();
+    for c in filename.chars().filter(|&c| seen.insert(c)) {
+        if c == '\0' || c == '/' || WINDOWS_FORBIDDEN.contains(&c) || FAT32_FORBIDDEN.contains(&c) {
+            issues.push(FileSafetyIssue::ForbiddenChar(c));
+        } else if c.is_ascii_control() && c != '\x7f' {
+            issues.push(FileSafetyIssue::ControlChar(c));
+        } else if !c.is_ascii() {
+            issues.push(FileSafetyIssue::NonAscii(c));
+        }
+    }
+
+    if let Some(stem) = reserved_stem(filename) {
+        issues.push(FileSafetyIssue::ReservedName(stem.to_string()));
+    }
+    if !matches!(filename, "." | "..") && filename.ends_with(['.', ' ']) {
+        issues.push(FileSafetyIssue::TrailingDotOrSpace);
+    }
+
+    let bytes = filename.len();
+    let utf16 = filename.encode_utf16().count();
+    for (platform, length, max) in [
+        (Platform::Linux, bytes, MAX_NAME_LENGTH),
+        (Platform::MacOs, utf16, MAX_NAME_LENGTH),
+        (Platform::Windows, utf16, MAX_WINDOWS_NAME_LENGTH),
+        (Platform::Fat32, bytes, MAX_NAME_LENGTH),
+    ] {
+        if length > max {
+            issues.push(FileSafetyIssue::TooLong { platform, length, max });
+        }
+    }
+
+    let ok = |platform| !issues.iter().any(|issue| issue.platforms().contains(&platform));
+    FileSafetyReport {
+        linux: ok(Platform::Linux),
+        macos: ok(Platform::MacOs),
+        windows: ok(Platform::Windows),
+        fat32: ok(Platform::Fat32),
+        issues,
+    }
+}
+
+/// Fix what [`is_safe_for_all_platforms`] would flag in a slug: transliterate
+/// non-ASCII, drop forbidden and control characters, strip trailing dots and
+/// spaces, and append `_` to a reserved device name (`con.txt` → `con_.txt`).
+/// Empty, `.`, and `..` are returned unchanged.
+fn make_cross_platform(slug: &str) -> Cow<'_, str> {
+    if matches!(slug, "" | "." | "..") || is_safe_for_all_platforms(slug).is_safe_everywhere() {
+        return Cow::Borrowed(slug);
+    }
+
+    // Transliterated dots are dropped: they would move the extension split
+    // (`.é҃З` → `.e.Z`) the next time the name is slugified
+    let ascii = if slug.is_ascii() {
+        Cow::Borrowed(slug)
+    } else {
+        let mut ascii = String::with_capacity(slug.len());
+        for c in slug.chars() {
+            if c.is_ascii() {
+                ascii.push(c);
+            } else {
+                ascii.extend(any_ascii::any_ascii_char(c).chars().filter(|&t| t != '.'));
+            }
+        }
+        Cow::Owned(ascii)
+    };
+    let mut safe: String = ascii
+        .chars()
+        .filter(|c| {
+            !c.is_ascii_control() && *c != '/' && !WINDOWS_FORBIDDEN.contains(c) && !FAT32_FORBIDDEN.contains(c)
+        })
+        .collect();
+    safe.truncate(safe.trim_end_matches(['.', ' ']).len());
+
+    if let Some(stem) = reserved_stem(&safe) {
+        let end = stem.len();
+        safe.insert(end, '_');
+    }
+    // Transliteration can lengthen the name
+    if safe.len() > MAX_NAME_LENGTH {
+        let (base, ext) = split_extension(&safe);
+        safe = truncate_base(base, ext, MAX_NAME_LENGTH) + ext;
+    }
+    Cow::Owned(safe)
+}
+
+/// Longest branch name produced by [`slugify_git_branch`], in bytes.
+const MAX_GIT_BRANCH_BYTES: usize = 255;
+
+/// Characters git rejects anywhere in a ref name (see `git check-ref-format`).
+const GIT_REF_FORBIDDEN: &[char] = &['~', '^', ':', '?', '*', '[', '\\', '/', '@', '{', '}'];
+
+/// Slugify `name` into a valid git branch name.
+///
+/// Runs the [`slugify_string`] pipeline, then enforces git's ref rules: no
+/// `..`, none of `~^:?*[\`, no control characters or whitespace, no leading
+/// or trailing `.` or separator, no `.lock` suffix, and at most 255 bytes.
+/// `/` becomes a separator; use [`slugify_git_branch_path`] to keep it.
+/// Returns an empty string if nothing usable is left.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_git_branch, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::preset_git_branch();
+/// assert_eq!(slugify_git_branch("feature: My New Feature (WIP).lock", &opts), "feature-my-new-feature-wip");
+/// assert_eq!(slugify_git_branch("release v1.2..3", &opts), "release-v1.2-3");
+/// ```
+#[must_use]
+pub fn slugify_git_branch(name: &str, options: &SlugifyOptions) -> String {
+    git_branch_component(name, options, MAX_GIT_BRANCH_BYTES)
+}
+
+/// Like [`slugify_git_branch`], but `/` is kept as the branch hierarchy
+/// separator and each component is slugified on its own. Empty components
+/// are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_git_branch_path, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::preset_git_branch();
+/// assert_eq!(slugify_git_branch_path("Feature/My New Feature", &opts), "feature/my-new-feature");
+/// assert_eq!(slugify_git_branch_path("/users//.hidden/", &opts), "users/hidden");
+/// ```
+#[must_use]
+pub fn slugify_git_branch_path(name: &str, options: &SlugifyOptions) -> String {
+    let mut out = String::new();
+    for component in name.split('/') {
+        let used = if out.is_empty() { 0 } else { out.len() + 1 };
+        let slug = git_branch_component(component, options, MAX_GIT_BRANCH_BYTES.saturating_sub(used));
+        if slug.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('/');
+        }
+        out.push_str(&slug);
+    }
+    out
+}
+
+/// One `/`-free component of a git branch name, at most `max_bytes` long.
+fn git_branch_component(name: &str, options: &SlugifyOptions, max_bytes: usize) -> String {
+    let name = name.strip_suffix(".lock").unwrap_or(name);
+    let sep = options.style.separator().unwrap_or('-');
+    let mut slug: String = slugify_string(name, options)
+        .chars()
+        .map(|c| if GIT_REF_FORBIDDEN.contains(&c) || c.is_control() || c.is_whitespace() { sep } else { c })
+        .collect();
+    while slug.contains("..") {
+        slug = slug.replace("..", ".");
+    }
+
+    let is_edge = |c: char| c == '.' || c == '-' || c == '_';
+    let fitted = if slug.len() > max_bytes { truncate_at_separator(&slug, max_bytes, |c| c == sep) } else { &slug };
+    let mut slug = fitted.trim_matches(is_edge).to_string();
+    while let Some(stripped) = slug.strip_suffix(".lock") {
+        slug = stripped.trim_end_matches(is_edge).to_string();
+    }
+    slug
+}
+
+/// Longest tag produced by [`slugify_for_docker_tag`], in bytes.
+const MAX_DOCKER_TAG_BYTES: usize = 128;
+
+/// Slugify `s` into a Docker image tag: `[A-Za-z0-9_.-]`, at most 128
+/// characters, starting with a letter or digit (`v` is prepended otherwise).
+///
+/// A file extension made of letters and digits (`.txt`, `.tar.gz`) is
+/// dropped first, so filenames become tags; purely numeric ones, as in
+/// `v1.2.3`, are kept. Any character outside the tag alphabet that survives
+/// the pipeline (e.g. with `keep_unicode`) becomes a separator.
+///
+/// # Errors
+///
+/// Returns [`SlugifyError::EmptyResult`] if nothing usable is left.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_for_docker_tag, SlugifyError, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::preset_docker_tag();
+/// assert_eq!(slugify_for_docker_tag("My App (latest).txt", &opts).unwrap(), "my-app-latest");
+/// assert_eq!(slugify_for_docker_tag("v1.2.3", &opts).unwrap(), "v1.2.3");
+/// assert_eq!(slugify_for_docker_tag("(!)", &opts), Err(SlugifyError::EmptyResult));
+/// ```
+pub fn slugify_for_docker_tag(s: &str, options: &SlugifyOptions) -> Result<String, SlugifyError> {
+    let (base, ext) = split_extension(s);
+    let is_file_ext = ext.len() > 1
+        && ext.chars().all(|c| c == '.' || c.is_ascii_alphanumeric())
+        && ext.chars().any(|c| c.is_ascii_alphabetic());
+    let input = if is_file_ext && base.chars().any(char::is_alphanumeric) { base } else { s };
+
+    let sep = options.style.separator().unwrap_or('-');
+    let tag: String = slugify_string(input, options)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') { c } else { sep })
+        .collect();
+    let tag = tag.trim_matches(|c| c == '.' || c == '-');
+    if tag.is_empty() {
+        return Err(SlugifyError::EmptyResult);
+    }
+
+    let mut tag = if tag.starts_with(|c: char| c.is_ascii_alphanumeric()) { tag.to_string() } else { format!("v{tag}") };
+    if tag.len() > MAX_DOCKER_TAG_BYTES {
+        tag = truncate_at_separator(&tag, MAX_DOCKER_TAG_BYTES, |c| c == sep).to_string();
+    }
+    Ok(tag)
+}
+
+/// Slugify `name` into a Python distribution name in its PEP 503 normalized
+/// form: lowercase ASCII letters and digits, with each run of `-`, `_`, `.`
+/// or whitespace collapsed to a single `-`.
+///
+/// Unlike [`slugify`], existing separators are all treated alike, so
+/// `my_package`, `my.package` and `My-Package` name the same distribution.
+/// Non-ASCII letters are transliterated and other characters dropped. The
+/// result may be empty; check it with [`is_valid_python_package_name`].
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::slugify_for_python_package;
+///
+/// assert_eq!(slugify_for_python_package("FrIeNdLy-._.-bArD"), "friendly-bard");
+/// assert_eq!(slugify_for_python_package("Café Tools!"), "cafe-tools");
+/// ```
+#[must_use]
+pub fn slugify_for_python_package(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in any_ascii::any_ascii(name).chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if (matches!(c, '-' | '_' | '.') || c.is_whitespace()) && !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    out.trim_end_matches('-').to_string()
+}
+
+/// Whether `name` is a valid Python distribution name: ASCII letters and
+/// digits, with `-`, `_` or `.` allowed only between them (the core metadata
+/// rule, case-insensitive). It need not be normalized.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::is_valid_python_package_name;
+///
+/// assert!(is_valid_python_package_name("Friendly.Bard"));
+/// assert!(!is_valid_python_package_name("-friendly-bard"));
+/// ```
+#[must_use]
+pub fn is_valid_python_package_name(name: &str) -> bool {
+    let is_edge_ok = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+    is_edge_ok(name.chars().next())
+        && is_edge_ok(name.chars().next_back())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Tidy up a slug that is almost clean, without re-running the full pipeline.
+///
+/// In the part before the extension, this collapses runs of the style's
+/// separator, strips leading and trailing separators, and lowercases
+/// (except for [`Style::Pascal`]). Nothing is transliterated, no words are
+/// split, and the extension and any leading dotfile dot are left alone.
+/// Returns [`Cow::Borrowed`] if the slug is already normalized.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{normalize_slug, Style};
+///
+/// assert_eq!(normalize_slug("my--file.txt", &Style::Kebab), "my-file.txt");
+/// assert_eq!(normalize_slug("my-file-.txt", &Style::Kebab), "my-file.txt");
+/// assert_eq!(normalize_slug("_My__File_", &Style::Snake), "my_file");
+/// ```
+#[must_use]
+pub fn normalize_slug<'a>(slug: &'a str, style: &Style) -> Cow<'a, str> {
+    let (base, ext) = split_extension(slug);
+    let (dot, base) = match base.strip_prefix('.') {
+        Some(rest) => (".", rest),
+        None => ("", base),
+    };
+
+    let mut normalized = String::with_capacity(slug.len());
+    normalized.push_str(dot);
+    match style.separator() {
+        Some(sep) => {
+            for (i, word) in base.split(sep).filter(|w| !w.is_empty()).enumerate() {
+                if i > 0 {
+                    normalized.push(sep);
+                }
+                normalized.push_str(&word.to_lowercase());
+            }
+        }
+        None => normalized.push_str(base),
+    }
+    normalized.push_str(ext);
+
+    if normalized == slug {
+        Cow::Borrowed(slug)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// Whether [`normalize_slug`] would leave `slug` unchanged.
+#[must_use]
+pub fn is_normalized_slug(slug: &str, style: &Style) -> bool {
+    matches!(normalize_slug(slug, style), Cow::Borrowed(_))
+}
+
+/// Byte ranges of the word separators in `slug`: `-` or `_` for kebab and
+/// snake case, or an empty range before each capital for Pascal case.
+/// Separators at either end and hyphens inside version ranges such as
+/// `2.10-2.12.26` are not word boundaries.
+fn word_breaks(slug: &str, style: Style) -> Vec<(usize, usize)> {
+    let breaks: Vec<(usize, usize)> = match style.separator() {
+        Some(sep) => {
+            // Same byte offsets as `slug`, with protected range hyphens replaced
+            let ranges = if sep == '-' { preserve_version_range(slug) } else { String::new() };
+            slug.char_indices()
+                .filter(|&(i, c)| c == sep && ranges.as_bytes().get(i) != Some(&(VERSION_RANGE_DASH as u8)))
+                .map(|(i, c)| (i, i + c.len_utf8()))
+                .collect()
+        }
+        None => slug.char_indices().filter(|(_, c)| c.is_uppercase()).map(|(i, _)| (i, i)).collect(),
+    };
+    breaks.into_iter().filter(|&(start, end)| start > 0 && end < slug.len()).collect()
+}
+
+/// Split `slug` at its last word boundary for `style` into `(head, tail)`,
+/// without the separator, e.g. to shorten it for display. Pascal case
+/// splits before the last capital. Dots in version numbers and the hyphen
+/// in a version range (`2.10-2.12.26`) don't count as boundaries, and an
+/// extension is part of the last word.
+///
+/// Returns `None` for a single word.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{split_at_last_separator, Style};
+///
+/// assert_eq!(split_at_last_separator("my-long-document-name", &Style::Kebab), Some(("my-long-document", "name")));
+/// assert_eq!(split_at_last_separator("MyLongDocument", &Style::Pascal), Some(("MyLong", "Document")));
+/// assert_eq!(split_at_last_separator("app-v1.2.3", &Style::Kebab), Some(("app", "v1.2.3")));
+/// assert_eq!(split_at_last_separator("document", &Style::Kebab), None);
+/// ```
+#[must_use]
+pub fn split_at_last_separator<'a>(slug: &'a str, style: &Style) -> Option<(&'a str, &'a str)> {
+    let (start, end) = word_breaks(slug, *style).pop()?;
+    Some((&slug[..start], &slug[end..]))
+}
+
+/// The number of words in `slug` for `style`, counted as
+/// [`split_at_last_separator`] splits them. An empty slug has none.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slug_word_count, Style};
+///
+/// assert_eq!(slug_word_count("my-long-document-name", &Style::Kebab), 4);
+/// assert_eq!(slug_word_count("launch_schedule_2.10-2.12.26", &Style::Snake), 3);
+/// assert_eq!(slug_word_count("MyLongDocument", &Style::Pascal), 3);
+/// ```
+#[must_use]
+pub fn slug_word_count(slug: &str, style: &Style) -> usize {
+    let mut count = 0;
+    let mut word_start = 0;
+    for (start, end) in word_breaks(slug, *style).into_iter().chain([(slug.len(), slug.len())]) {
+        if start > word_start {
+            count += 1;
+        }
+        word_start = end;
+    }
+    count
+}
+
+/// Types that can produce a filename slug of themselves.
+///
+/// Usually derived with `#[derive(Slugify)]` (the `derive` feature) on a
+/// newtype such as `struct Filename(String)`.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use fileslug::{slugify, Slugify, SlugifyOptions};
+///
+/// struct Filename(String);
+///
+/// impl Slugify for Filename {
+///     fn to_slug(&self, options: &SlugifyOptions) -> Cow<'_, str> {
+///         slugify(&self.0, options)
+///     }
+/// }
+///
+/// let name = Filename("My File.txt".to_string());
+/// assert_eq!(name.to_slug(&SlugifyOptions::default()), "my-file.txt");
+/// ```
+pub trait Slugify {
+    /// Slugify `self` as a filename — see [`slugify`].
+    fn to_slug(&self, options: &SlugifyOptions) -> Cow<'_, str>;
+}
+
+/// Slugify a filename, appending `-2`, `-3`, etc. if the slug is already in `seen`.
+///
+/// The suffix goes before the extension (`my-file-2.txt`), or after the name
+/// for pure dotfiles (`.env-2`). The chosen slug is inserted into `seen`, which
+/// the caller keeps across calls. This is the in-memory counterpart to checking
+/// the filesystem for collisions. Empty slugs are returned as-is and not recorded.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use fileslug::{slugify_unique, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default();
+/// let mut seen = HashSet::new();
+/// assert_eq!(slugify_unique("My File.txt", &opts, &mut seen), "my-file.txt");
+/// assert_eq!(slugify_unique("My_File.txt", &opts, &mut seen), "my-file-2.txt");
+/// ```
+#[must_use]
+pub fn slugify_unique<'a, S: BuildHasher>(
+    filename: &'a str,
+    options: &SlugifyOptions,
+    seen: &mut HashSet<String, S>,
+) -> Cow<'a, str> {
+    let slug = slugify(filename, options);
+    if slug.is_empty() {
+        return slug;
+    }
+    if !seen.contains(slug.as_ref()) {
+        seen.insert(slug.to_string());
+        return slug;
+    }
+
+    let (base, ext) = options.extensions.split_extension(&slug);
+    let mut n = 2u32;
+    let unique = loop {
+        let candidate = if base.is_empty() {
+            format!("{ext}-{n}")
+        } else {
+            format!("{base}-{n}{ext}")
+        };
+        if !seen.contains(&candidate) {
+            break candidate;
+        }
+        n += 1;
+    };
+    seen.insert(unique.clone());
+    Cow::Owned(unique)
+}
+
+/// An upper bound on the byte length of [`slugify`]`(filename, options)`,
+/// for sizing buffers before slugifying a batch.
+///
+/// Never less than the actual length, and usually close to it: each
+/// character is counted at the longest it can become (its transliteration,
+/// a custom replacement, or its own upper- or lowercase form), and the
+/// total is capped by the length limit unless the name is long enough to
+/// be returned as is (a long dotfile) or to keep a long extension.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slug_length_estimate, slugify, SlugifyOptions};
+///
+/// let opts = SlugifyOptions::default();
+/// for name in ["My File.txt", "Café Résumé.pdf", "深圳.png"] {
+///     assert!(slug_length_estimate(name, &opts) >= slugify(name, &opts).len());
+/// }
+/// ```
+#[must_use]
+pub fn slug_length_estimate(filename: &str, options: &SlugifyOptions) -> usize {
+    // Without a custom entry, the most bytes `c` can turn into
+    let char_bound = |c: char| -> usize {
+        if c.is_ascii() {
+            return 1;
+        }
+        let cased = c.len_utf8().max(c.to_lowercase().map(char::len_utf8).sum()).max(c.to_uppercase().map(char::len_utf8).sum());
+        // Locale tables replace a character with at most two ASCII letters
+        cased.max(any_ascii::any_ascii_char(c).len()).max(2)
+    };
+    let bound: usize = filename
+        .chars()
+        .map(|c| {
+            let custom = options.custom_transliteration.as_ref().and_then(|map| {
+                map.get(&c).or_else(|| c.to_lowercase().next().and_then(|lower| map.get(&lower)))
+            });
+            match custom {
+                Some(replacement) => replacement.chars().map(char_bound).sum::<usize>().max(char_bound(c)),
+                None => char_bound(c),
+            }
+        })
+        .sum();
+    // Smart case may split words before any character
+    let bound = if options.smart_case { bound + filename.chars().count() } else { bound };
+
+    // Truncation keeps the result within the limit, except for an extension
+    // longer than the limit; names returned as is are no longer than the input
+    let cap = options.max_length.map_or(MAX_FILENAME_BYTES, |max| max.min(MAX_FILENAME_BYTES));
+    // Cross-platform mode may append `_` to a device name like `con`
+    let device_suffix = usize::from(options.cross_platform);
+    (bound + device_suffix).min(cap.max(filename.len()) + device_suffix)
+}
+
+/// Slugify a batch of filenames, returning `(original, slug)` pairs with
+/// in-batch collisions resolved by [`slugify_unique`].
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_batch, SlugifyOptions};
+///
+/// let pairs = slugify_batch(&["My File.txt", "My_File.txt"], &SlugifyOptions::default());
+/// assert_eq!(pairs[0], ("My File.txt".to_string(), "my-file.txt".to_string()));
+/// assert_eq!(pairs[1], ("My_File.txt".to_string(), "my-file-2.txt".to_string()));
+/// ```
+#[must_use]
+pub fn slugify_batch(filenames: &[&str], options: &SlugifyOptions) -> Vec<(String, String)> {
+    let mut seen = HashSet::new();
+    filenames
+        .iter()
+        .map(|name| {
+            let slug = slugify_unique(name, options, &mut seen).into_owned();
+            ((*name).to_string(), slug)
+        })
+        .collect()
+}
+
+/// Errors from [`slugify_template`]. Positions are byte offsets into the template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TemplateError {
+    /// A slot names a variable that was not supplied.
+    UndefinedVariable(String),
+    /// A `{` with no matching `}`.
+    UnclosedSlot { position: usize },
+    /// A `}` that closes no slot. Write `}}` for a literal brace.
+    UnmatchedBrace { position: usize },
+    /// A slot name that is empty or has characters other than ASCII
+    /// letters, digits, and `_`.
+    InvalidSlotName { position: usize, name: String },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UndefinedVariable(name) => write!(f, "undefined template variable '{name}'"),
+            Self::UnclosedSlot { position } => write!(f, "unclosed '{{' at byte {position}"),
+            Self::UnmatchedBrace { position } => write!(f, "unmatched '}}' at byte {position}"),
+            Self::InvalidSlotName { position, name } => write!(f, "invalid slot name '{name}' at byte {position}"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Fill the `{name}` slots in `template` with the matching entries of
+/// `vars`, each slugified with [`slugify_string`]. Text outside the slots is
+/// kept as written, so the template supplies the separators and extension.
+/// `{{` and `}}` stand for literal braces.
+///
+/// # Errors
+///
+/// Returns [`TemplateError`] if a slot is malformed or names a variable
+/// missing from `vars`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use fileslug::{slugify_template, SlugifyOptions};
+///
+/// let vars = HashMap::from([("year", "2024"), ("title", "My Song")]);
+/// let name = slugify_template("{year}-{title}.mp3", &vars, &SlugifyOptions::default()).unwrap();
+/// assert_eq!(name, "2024-my-song.mp3");
+/// ```
+pub fn slugify_template<S: BuildHasher>(
+    template: &str,
+    vars: &HashMap<&str, &str, S>,
+    options: &SlugifyOptions,
+) -> Result<String, TemplateError> {
+    expand_template(template, |name| vars.get(name).map(|value| slugify_string(value, options)))
+}
+
+/// Like [`slugify_template`], but the values are inserted as they are. For
+/// values that are already slugs, or must not be slugified (a compound
+/// extension such as `tar.gz`).
+///
+/// # Errors
+///
+/// Returns [`TemplateError`] if a slot is malformed or names a variable
+/// missing from `vars`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use fileslug::fill_template;
+///
+/// let vars = HashMap::from([("slug", "my-song"), ("ext", "tar.gz")]);
+/// assert_eq!(fill_template("{slug}.{ext}", &vars).unwrap(), "my-song.tar.gz");
+/// ```
+pub fn fill_template<K, V, S>(template: &str, vars: &HashMap<K, V, S>) -> Result<String, TemplateError>
+where
+    K: Borrow<str> + Eq + Hash,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    expand_template(template, |name| vars.get(name).map(|value| value.as_ref().to_string()))
+}
+
+/// Substitute each slot of `template` with `lookup(name)`.
+fn expand_template(template: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String, TemplateError> {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(i) = rest.find(['{', '}']) {
+        let position = template.len() - rest.len() + i;
+        result.push_str(&rest[..i]);
+        let brace = &rest[i..=i];
+        let after = &rest[i + 1..];
+
+        if after.starts_with(brace) {
+            result.push_str(brace);
+            rest = &after[1..];
+            continue;
+        }
+        if brace == "}" {
+            return Err(TemplateError::UnmatchedBrace { position });
+        }
+
+        let end = after.find('}').ok_or(TemplateError::UnclosedSlot { position })?;
+        let name = &after[..end];
+        if name.is_empty() || !name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+            return Err(TemplateError::InvalidSlotName { position, name: name.to_string() });
+        }
+        let value = lookup(name).ok_or_else(|| TemplateError::UndefinedVariable(name.to_string()))?;
+        result.push_str(&value);
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Slugify each of `filenames` with [`slugify`], spreading the work across
+/// rayon's thread pool. The results are in input order.
+///
+/// Unlike [`slugify_batch`], collisions within the batch are not resolved.
+/// The pool size follows rayon's defaults: one thread per core, or
+/// `RAYON_NUM_THREADS` if set.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::{slugify_batch_rayon, SlugifyOptions};
+///
+/// let slugs = slugify_batch_rayon(&["My File.txt", "notes.md", "Café Menu.pdf"], &SlugifyOptions::default());
+/// assert_eq!(slugs, ["my-file.txt", "notes.md", "cafe-menu.pdf"]);
+/// ```
+#[cfg(feature = "parallel")]
+#[must_use]
+pub fn slugify_batch_rayon<'a>(filenames: &'a [&str], options: &SlugifyOptions) -> Vec<Cow<'a, str>> {
+    use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+    // Slugifying one name is cheap, so hand out work in runs rather than one name at a time
+    filenames.par_iter().with_min_len(256).map(|name| slugify(name, options)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::assert_slugify_idempotent;
+
+    #[test]
+    fn test_split_simple_extension() {
+        assert_eq!(split_extension("hello.txt"), ("hello", ".txt"));
+    }
+
+    #[test]
+    fn test_split_compound_extension() {
+        assert_eq!(split_extension("archive.tar.gz"), ("archive", ".tar.gz"));
+        assert_eq!(split_extension("backup.tar.bz2"), ("backup", ".tar.bz2"));
+        assert_eq!(split_extension("data.tar.xz"), ("data", ".tar.xz"));
+        assert_eq!(split_extension("logs.tar.zst"), ("logs", ".tar.zst"));
+    }
+
+    #[test]
+    fn test_split_dotfile() {
+        assert_eq!(split_extension(".gitignore"), ("", ".gitignore"));
+        assert_eq!(split_extension(".env"), ("", ".env"));
+    }
+
+    #[test]
+    fn test_split_no_extension() {
+        assert_eq!(split_extension("Makefile"), ("Makefile", ""));
+        assert_eq!(split_extension("README"), ("README", ""));
+    }
+
+    #[test]
+    fn test_split_multiple_dots() {
+        assert_eq!(split_extension("my.cool.file.txt"), ("my.cool.file", ".txt"));
+    }
+
+    #[test]
+    fn test_split_dotfile_with_extension() {
+        assert_eq!(split_extension(".bashrc"), ("", ".bashrc"));
+    }
+
+    #[test]
+    fn test_split_extension_options_default() {
+        let opts = SplitExtensionOptions::default();
+        assert_eq!(opts.compound_extensions, DEFAULT_COMPOUND_EXTENSIONS);
+        assert_eq!(split_extension_with_opts("archive.tar.gz", &opts), ("archive", ".tar.gz"));
+    }
+
+    #[test]
+    fn test_split_extension_custom_compound() {
+        let opts = SplitExtensionOptions { compound_extensions: vec![".nii.gz".to_string()] };
+        assert_eq!(split_extension_with_opts("brain.nii.gz", &opts), ("brain", ".nii.gz"));
+        assert_eq!(split_extension_with_opts("brain.NII.GZ", &opts), ("brain", ".NII.GZ"));
+        // Built-ins are replaced, not extended
+        assert_eq!(split_extension_with_opts("archive.tar.gz", &opts), ("archive.tar", ".gz"));
+    }
+
+    /// Every upper/lower combination of `s`'s ASCII letters.
+    fn case_variants(s: &str) -> Vec<String> {
+        let letters: Vec<usize> =
+            s.char_indices().filter(|(_, c)| c.is_ascii_alphabetic()).map(|(i, _)| i).collect();
+        (0..1u32 << letters.len())
+            .map(|mask| {
+                let mut bytes = s.as_bytes().to_vec();
+                for (bit, &i) in letters.iter().enumerate() {
+                    if mask & (1 << bit) != 0 {
+                        bytes[i] = bytes[i].to_ascii_uppercase();
+                    }
+                }
+                String::from_utf8(bytes).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_compound_extension_every_capitalization() {
+        for ext in DEFAULT_COMPOUND_EXTENSIONS {
+            let variants = case_variants(ext);
+            assert_eq!(variants.len(), 1 << ext.chars().filter(char::is_ascii_alphabetic).count());
+            for variant in variants {
+                let name = format!("Archive{variant}");
+                assert_eq!(split_extension(&name), ("Archive", variant.as_str()), "input: {name}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_new_compound_extensions() {
+        for (name, base, ext) in [
+            ("backup.tar.lz4", "backup", ".tar.lz4"),
+            ("backup.tar.lzma", "backup", ".tar.lzma"),
+            ("backup.tar.lz", "backup", ".tar.lz"),
+            ("backup.tar.sz", "backup", ".tar.sz"),
+            ("backup.tar.Z", "backup", ".tar.Z"),
+            ("backup.tar.z", "backup", ".tar.z"),
+            ("initrd.cpio.gz", "initrd", ".cpio.gz"),
+            ("initrd.cpio.bz2", "initrd", ".cpio.bz2"),
+            ("brain_t1.nii.gz", "brain_t1", ".nii.gz"),
+            ("sample.vcf.gz", "sample", ".vcf.gz"),
+            ("export.csv.gz", "export", ".csv.gz"),
+            ("dump.json.gz", "dump", ".json.gz"),
+        ] {
+            assert_eq!(split_extension(name), (base, ext), "input: {name}");
+        }
+    }
+
+    #[test]
+    fn test_split_lz_does_not_swallow_lz4() {
+        assert_eq!(split_extension("a.tar.lz4"), ("a", ".tar.lz4"));
+        assert_eq!(split_extension("a.lz4"), ("a", ".lz4"));
+    }
+
+    #[test]
+    fn test_slugify_new_compound_extension() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("Brain Scan.NII.GZ", &opts), "brain-scan.NII.GZ");
+        assert_eq!(slugify("Old Backup.tar.Z", &opts), "old-backup.tar.Z");
+    }
+
+    #[test]
+    fn test_split_compound_extension_title_case() {
+        assert_eq!(split_extension("Archive.Tar.Gz"), ("Archive", ".Tar.Gz"));
+        assert_eq!(split_extension("Archive.TAR.GZ"), ("Archive", ".TAR.GZ"));
+    }
+
+    #[test]
+    fn test_split_custom_compound_unicode_case() {
+        let opts = SplitExtensionOptions { compound_extensions: vec![".été.gz".to_string()] };
+        assert_eq!(split_extension_with_opts("data.ÉTÉ.GZ", &opts), ("data", ".ÉTÉ.GZ"));
+    }
+
+    #[test]
+    fn test_split_custom_compound_case_pair_length_differs() {
+        // 'ẞ' (3 bytes) lowercases to 'ß' (2 bytes)
+        let opts = SplitExtensionOptions { compound_extensions: vec![".straße.gz".to_string()] };
+        assert_eq!(split_extension_with_opts("data.STRAẞE.GZ", &opts), ("data", ".STRAẞE.GZ"));
+        assert_eq!(split_extension_with_opts("ẞ.straße.gz", &opts), ("ẞ", ".straße.gz"));
+    }
+
+    #[test]
+    fn test_split_compound_longer_than_name() {
+        assert_eq!(split_extension("a.gz"), ("a", ".gz"));
+        assert_eq!(split_extension("é.gz"), ("é", ".gz"));
+    }
+
+    #[test]
+    fn test_split_extension_empty_compound_ignored() {
+        let opts = SplitExtensionOptions { compound_extensions: vec![String::new()] };
+        assert_eq!(split_extension_with_opts("report.pdf", &opts), ("report", ".pdf"));
+    }
+
+    #[test]
+    fn test_slugify_custom_compound_extension() {
+        let mut split = SplitExtensionOptions::default();
+        split.compound_extensions.push(".warc.gz".to_string());
+        let opts = SlugifyOptions::default().with_extensions(ExtensionConfig { split_options: Some(split), ..Default::default() });
+        assert_eq!(slugify("Site Crawl.warc.gz", &opts), "site-crawl.warc.gz");
+        // Without the custom list, .warc is part of the base
+        assert_eq!(slugify("Site Crawl.warc.gz", &SlugifyOptions::default()), "site-crawl-warc.gz");
+    }
+
+    // --- slugify pipeline tests ---
+
+    #[test]
+    fn test_slugify_basic_kebab() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("My Cool File.txt", &opts), "my-cool-file.txt");
+    }
+
+    #[test]
+    fn test_slugify_snake() {
+        let opts = SlugifyOptions { style: Style::Snake, ..Default::default() };
+        assert_eq!(slugify("My Cool File.txt", &opts), "my_cool_file.txt");
+    }
+
+    #[test]
+    fn test_slugify_pascal() {
+        let opts = SlugifyOptions { style: Style::Pascal, ..Default::default() };
+        assert_eq!(slugify("my cool file.txt", &opts), "MyCoolFile.txt");
+    }
+
+    #[test]
+    fn test_slugify_unicode_transliterate() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("Café Résumé.txt", &opts), "cafe-resume.txt");
+    }
+
+    #[test]
+    fn test_slugify_keep_unicode() {
+        let opts = SlugifyOptions { keep_unicode: true, ..Default::default() };
+        assert_eq!(slugify("Café Résumé.txt", &opts), "café-résumé.txt");
+    }
+
+    #[test]
+    fn test_slugify_brackets_stripped_contents_kept() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("Report (Final) [2024].txt", &opts), "report-final-2024.txt");
+    }
+
+    #[test]
+    fn test_slugify_compound_extension() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("My Archive File.tar.gz", &opts), "my-archive-file.tar.gz");
+    }
+
+    #[test]
+    fn test_slugify_dotfile_untouched() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify(".gitignore", &opts), ".gitignore");
+    }
+
+    #[test]
+    fn test_slugify_already_clean() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("already-clean.txt", &opts), "already-clean.txt");
+    }
+
+    #[test]
+    fn test_slugify_special_chars() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("file@name#with$symbols.txt", &opts), "file-name-with-symbols.txt");
+    }
+
+    #[test]
+    fn test_slugify_collapses_separators() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("too   many   spaces.txt", &opts), "too-many-spaces.txt");
+    }
+
+    #[test]
+    fn test_slugify_trims_separators() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify(" leading and trailing .txt", &opts), "leading-and-trailing.txt");
+    }
+
+    #[test]
+    fn test_slugify_no_extension() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("My Makefile", &opts), "my-makefile");
+    }
+
+    #[test]
+    fn test_slugify_full_pipeline() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("Café Résumé (Final Copy) [2024].tar.gz", &opts), "cafe-resume-final-copy-2024.tar.gz");
+    }
+
+    #[test]
+    fn test_slugify_curly_braces() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("file {draft}.txt", &opts), "file-draft.txt");
+    }
+
+    #[test]
+    fn test_slugify_pascal_multiple_words() {
+        let opts = SlugifyOptions { style: Style::Pascal, ..Default::default() };
+        assert_eq!(slugify("Hello World Foo Bar.txt", &opts), "HelloWorldFooBar.txt");
+    }
+
+    #[test]
+    fn test_slugify_empty_string() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("", &opts), "");
+    }
+
+    #[test]
+    fn test_slugify_only_special_chars() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("@#$.txt", &opts), ".txt");
+    }
+
+    #[test]
+    fn test_slugify_numbers_only() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("12345.txt", &opts), "12345.txt");
+    }
+
+    #[test]
+    fn test_slugify_dotfile_with_extension() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify(".env.local", &opts), ".env.local");
+    }
+
+    #[test]
+    fn test_slugify_hidden_config_multipart() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify(".config.backup.old", &opts), ".config-backup.old");
+    }
+
+    #[test]
+    fn test_slugify_stem_keeps_extension_verbatim() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify_stem("My Document.pdf", &opts), "my-document.pdf");
+        assert_eq!(slugify_stem("My Document.PDF", &opts), "my-document.PDF");
+        assert_eq!(slugify_stem("Backup.TAR.GZ", &opts), "backup.TAR.GZ");
+        assert_eq!(slugify_stem(".gitignore", &opts), ".gitignore");
+    }
+
+    #[test]
+    fn test_slugify_stem_matches_slugify() {
+        let opts = SlugifyOptions::default();
+        for name in ["Café (Final).pdf", "app-1.2.3.dmg", "README", ".env.local"] {
+            assert_eq!(slugify_stem(name, &opts), slugify(name, &opts));
+        }
+    }
+
+    #[test]
+    fn test_slugify_ext_too_lowercases_extension() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify_ext_too("My Document.PDF", &opts), "my-document.pdf");
+        assert_eq!(slugify_ext_too("Backup.Tar.GZ", &opts), "backup.tar.gz");
+        assert_eq!(slugify_ext_too("README", &opts), "readme");
+    }
+
+    #[test]
+    fn test_split_dotfile_with_second_extension() {
+        assert_eq!(split_extension(".env.local"), (".env", ".local"));
+    }
+
+    #[test]
+    fn test_slugify_only_special_no_ext() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("@@@", &opts), "");
+    }
+
+    // --- Adversarial & unicode tests ---
+
+    #[test]
+    fn test_slugify_shell_injection() {
+        let opts = SlugifyOptions::default();
+        let result = slugify("$(echo pwned).txt", &opts);
+        assert!(!result.contains('$'));
+        assert!(!result.contains('('));
+        assert!(!result.contains(')'));
+        assert_eq!(result, "echo-pwned.txt");
+    }
+
+    #[test]
+    fn test_slugify_backticks() {
+        let opts = SlugifyOptions::default();
+        let result = slugify("`rm -rf /`.txt", &opts);
+        assert!(!result.contains('`'));
+        assert_eq!(result, "rm-rf.txt");
+    }
+
+    #[test]
+    fn test_slugify_pipe_redirect() {
+        let opts = SlugifyOptions::default();
+        let result = slugify("file|name>output.txt", &opts);
+        assert!(!result.contains('|'));
+        assert!(!result.contains('>'));
+        assert_eq!(result, "file-name-output.txt");
+    }
+
+    #[test]
+    fn test_slugify_newline_in_name() {
+        let opts = SlugifyOptions::default();
+        let result = slugify("file\nname.txt", &opts);
+        assert!(!result.contains('\n'));
+        assert_eq!(result, "file-name.txt");
+    }
+
+    #[test]
+    fn test_slugify_emoji() {
+        let opts = SlugifyOptions::default();
+        let result = slugify("🎉.txt", &opts);
+        // any_ascii transliterates emoji — result should be ASCII and not empty
+        assert!(result.ends_with(".txt"));
+        assert!(result.is_ascii());
+    }
+
+    #[test]
+    fn test_slugify_cjk() {
+        let opts = SlugifyOptions::default();
+        let result = slugify("你好世界.txt", &opts);
+        // any_ascii transliterates CJK to pinyin-like ASCII
+        assert!(result.ends_with(".txt"));
+        assert!(result.is_ascii());
+        assert!(result.len() > ".txt".len(), "CJK should transliterate to something");
+    }
+
+    #[test]
+    fn test_slugify_rtl_arabic() {
+        let opts = SlugifyOptions::default();
+        let result = slugify("مرحبا.txt", &opts);
+        assert!(result.ends_with(".txt"));
+        assert!(result.is_ascii());
+        assert!(result.len() > ".txt".len(), "Arabic should transliterate to something");
+    }
+
+    #[test]
+    fn test_slugify_combining_char() {
+        let opts = SlugifyOptions::default();
+        // Decomposed é (e + combining acute accent) vs precomposed é
+        let decomposed = slugify("caf\u{0065}\u{0301}.txt", &opts);
+        let precomposed = slugify("caf\u{00e9}.txt", &opts);
+        assert_eq!(decomposed, precomposed);
+    }
+
+    #[test]
+    fn test_slugify_zero_width_chars() {
+        let opts = SlugifyOptions::default();
+        // Zero-width space (U+200B) should be stripped
+        let result = slugify("hello\u{200B}world.txt", &opts);
+        assert!(!result.contains('\u{200B}'));
+        assert_eq!(result, "helloworld.txt");
+    }
+
+    #[test]
+    fn test_slugify_very_long_name() {
+        let opts = SlugifyOptions::default();
+        let long_name = "a".repeat(255) + ".txt";
+        let result = slugify(&long_name, &opts);
+        assert!(result.len() <= 255, "result is {} bytes", result.len());
+        assert!(result.ends_with(".txt"));
+        assert!(!result.is_empty());
+    }
+
+    #[test]
+    fn test_slugify_already_numbered() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("file-2.txt", &opts), "file-2.txt");
+    }
+
+    #[test]
+    fn test_slugify_compound_ext_mixed_case() {
+        let opts = SlugifyOptions::default();
+        let result = slugify("Archive.TAR.GZ", &opts);
+        assert_eq!(result, "archive.TAR.GZ");
+    }
+
+    // --- version dot preservation tests ---
+
+    #[test]
+    fn test_preserve_version_dots_simple() {
+        assert_eq!(preserve_version_dots("foo-0.8.34-bar"), "foo-0\x018\x0134-bar");
+    }
+
+    #[test]
+    fn test_preserve_version_dots_semver() {
+        assert_eq!(preserve_version_dots("app-1.2.3"), "app-1\x012\x013");
+    }
+
+    #[test]
+    fn test_preserve_version_dots_two_part() {
+        assert_eq!(preserve_version_dots("app-7.20"), "app-7\x0120");
+    }
+
+    #[test]
+    fn test_preserve_version_dots_no_version() {
+        assert_eq!(preserve_version_dots("hello-world"), "hello-world");
+    }
+
+    #[test]
+    fn test_preserve_version_dots_letters_not_matched() {
+        assert_eq!(preserve_version_dots("a.b.c"), "a.b.c");
+    }
+
+    #[test]
+    fn test_preserve_version_dots_not_followed_by_digit() {
+        assert_eq!(preserve_version_dots("7.txt"), "7.txt");
+    }
+
+    #[test]
+    fn test_preserve_version_dots_adjacent_to_letters() {
+        assert_eq!(preserve_version_dots("istatmenus7.20"), "istatmenus7\x0120");
+    }
+
+    #[test]
+    fn test_preserve_version_dots_multiple_versions() {
+        assert_eq!(preserve_version_dots("2.10-2.12.26"), "2\x0110-2\x0112\x0126");
+    }
+
+    // --- date dot preservation tests ---
+
+    #[test]
+    fn test_preserve_version_dots_greek_neighbors() {
+        assert_eq!(preserve_version_dots("αβ1.2.3γ"), "αβ1\x012\x013γ");
+        assert_eq!(preserve_version_dots("λ2.0"), "λ2\x010");
+    }
+
+    #[test]
+    fn test_preserve_version_dots_arabic_indic_digits() {
+        // Arabic-Indic digits are not ASCII digits and are left alone
+        assert_eq!(preserve_version_dots("v١.٢.٣"), "v١.٢.٣");
+        assert_eq!(preserve_version_dots("١1.2٢"), "١1\x012٢");
+    }
+
+    // --- Version tests ---
+
+    #[test]
+    fn test_version_parse_and_display_round_trip() {
+        for s in ["1.2.3-rc1", "7.20", "0.8.34", "0.0", "10.0.0-beta2", "4294967295.0"] {
+            let version: Version = s.parse().unwrap();
+            assert_eq!(version.to_string(), s);
+        }
+        let version: Version = "7.20".parse().unwrap();
+        assert_eq!(version, Version { major: 7, minor: 20, patch: None, pre: None });
+    }
+
+    #[test]
+    fn test_version_parse_rejects() {
+        for s in [
+            "", "1", "1.", ".1", "1..2", "1.2.3.4", "v1.2", "1.2 ", "01.2", "1.02", "1.2-", "1.2-1", "1.2-rc.1", "1.2-rc-1",
+            "1.2.x", "١.٢",
+        ] {
+            assert_eq!(s.parse::<Version>(), Err(ParseVersionError::Malformed), "{s:?}");
+        }
+        assert_eq!("4294967296.0".parse::<Version>(), Err(ParseVersionError::NumberTooLarge));
+    }
+
+    #[test]
+    fn test_extract_versions() {
+        let spans = |s: &str| -> Vec<(usize, usize, String)> {
+            extract_versions(s).into_iter().map(|(start, end, version)| (start, end, version.to_string())).collect()
+        };
+        assert_eq!(spans("no version here"), []);
+        assert_eq!(spans("foo-0.8.34_bar"), [(4, 10, "0.8.34".to_string())]);
+        assert_eq!(spans("foo-0.8.34-bar"), [(4, 14, "0.8.34-bar".to_string())]);
+        // a hyphen before a digit is a range, not a pre-release tag
+        assert_eq!(spans("2.10-2.12.26"), [(0, 4, "2.10".to_string()), (5, 12, "2.12.26".to_string())]);
+        assert_eq!(spans("lib-1.2.3-rc1.tar.gz"), [(4, 13, "1.2.3-rc1".to_string())]);
+        // runs that are not valid versions are skipped whole
+        assert_eq!(spans("1.2.3.4 and 2024.03.15 then 3.1"), [(28, 31, "3.1".to_string())]);
+        assert_eq!(spans("99999999999.1"), []);
+        assert_eq!(spans("Café 2.0 – 3.0"), [(6, 9, "2.0".to_string()), (14, 17, "3.0".to_string())]);
+    }
+
+    #[test]
+    fn test_preserve_version_dots_fullwidth_digits() {
+        assert_eq!(preserve_version_dots("版本１．２"), "版本１．２");
+        assert_eq!(preserve_version_dots("版本1.2．３"), "版本1\x012．３");
+        assert_eq!(preserve_version_dots("１3.4"), "１3\x014");
+    }
+
+    #[test]
+    fn test_preserve_version_dots_trailing_dot_before_multibyte() {
+        assert_eq!(preserve_version_dots("7.é"), "7.é");
+        assert_eq!(preserve_version_dots("1.2.é"), "1\x012.é");
+    }
+
+    #[test]
+    fn test_slugify_version_next_to_greek() {
+        let opts = SlugifyOptions { keep_unicode: true, ..Default::default() };
+        assert_eq!(slugify("λ1.2.3 beta.txt", &opts), "λ1.2.3-beta.txt");
+    }
+
+    #[test]
+    fn test_slugify_ext_too_transliterates_and_strips() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify_ext_too("notes.Ré Sumé", &opts), "notes.resume");
+        assert_eq!(slugify_ext_too("photo.JP_EG", &opts), "photo.jpeg");
+        assert_eq!(slugify_ext_too("data.(!)", &opts), "data");
+    }
+
+    #[test]
+    fn test_slugify_ext_too_ignores_style() {
+        let opts = SlugifyOptions { style: Style::Pascal, ..Default::default() };
+        assert_eq!(slugify_ext_too("my report.PDF", &opts), "MyReport.pdf");
+    }
+
+    #[test]
+    fn test_slugify_ext_too_dotfile() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify_ext_too(".Env.LOCAL", &opts), ".env.local");
+    }
+
+    #[test]
+    fn test_truncate_base_never_ends_with_separator() {
+        assert_eq!(truncate_base("ab--cd", "", 5), "ab");
+        assert_eq!(truncate_base("_abcdef", "", 1), "");
+        assert_eq!(truncate_base("abc-def", "", 4), "abc");
+    }
+
+    #[test]
+    fn test_truncate_base_ext_longer_than_budget() {
+        assert_eq!(truncate_base("name", ".verylongext", 4), "");
+    }
+
+    #[test]
+    fn test_truncate_slug_trims_trailing_separator() {
+        assert_eq!(truncate_slug("abc-", '-', 10), "abc");
+        assert_eq!(truncate_slug("abc-def", '_', 5), "abc-d");
+        assert_eq!(truncate_slug("", '-', 0), "");
+    }
+
+    /// Check the documented guarantees over pseudo-random inputs built from
+    /// separators and 1- to 4-byte characters.
+    #[test]
+    fn test_truncate_invariants_fuzz() {
+        const ALPHABET: [&str; 8] = ["a", "Z", "-", "_", "é", "€", "𝄞", "7"];
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..2_000 {
+            let len = usize::try_from(next() % 24).unwrap();
+            let input: String = (0..len).map(|_| ALPHABET[usize::try_from(next() % 8).unwrap()]).collect();
+            let ext = [".txt", "", ".tar.gz"][usize::try_from(next() % 3).unwrap()];
+            for max_bytes in 0..=input.len() + ext.len() + 1 {
+                let base = truncate_base(&input, ext, max_bytes);
+                assert!(input.starts_with(&base), "{input:?} -> {base:?}");
+                if base != input {
+                    assert!(base.len() + ext.len() <= max_bytes || base.is_empty(), "{input:?} {max_bytes}");
+                    assert!(!base.ends_with(['-', '_']), "{input:?} {max_bytes} -> {base:?}");
+                }
+
+                let slug = truncate_slug(&input, '-', max_bytes);
+                assert!(input.starts_with(&slug), "{input:?} -> {slug:?}");
+                assert!(slug.len() <= max_bytes, "{input:?} {max_bytes} -> {slug:?}");
+                assert!(!slug.ends_with('-'), "{input:?} {max_bytes} -> {slug:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_strip_direction_overrides_rtlo() {
+        assert_eq!(strip_direction_overrides("file\u{202E}gpj.pdf"), "filegpj.pdf");
+    }
+
+    #[test]
+    fn test_strip_direction_overrides_marks_and_embeddings() {
+        // RLM, LRE, PDF, RLE, LRO
+        assert_eq!(strip_direction_overrides("a\u{200F}b"), "ab");
+        assert_eq!(strip_direction_overrides("\u{202A}text\u{202C}"), "text");
+        assert_eq!(strip_direction_overrides("\u{202B}x\u{202D}y"), "xy");
+        // Isolates, LRM, ALM
+        assert_eq!(strip_direction_overrides("\u{2066}a\u{2067}b\u{2068}c\u{2069}"), "abc");
+        assert_eq!(strip_direction_overrides("\u{200E}a\u{061C}b"), "ab");
+    }
+
+    #[test]
+    fn test_strip_direction_overrides_borrows_clean_input() {
+        assert!(matches!(strip_direction_overrides("عربي.txt"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_normalize_path_separators() {
+        assert_eq!(normalize_path_separators(r"path\to\file.txt"), "path/to/file.txt");
+        assert_eq!(normalize_path_separators(r"C:\Users\\Me"), "C:/Users//Me");
+        assert!(matches!(normalize_path_separators("path/to/file.txt"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_slugify_backslash_paths() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify(r"path\to\file.txt", &opts), "path-to-file.txt");
+        assert_eq!(slugify(r"C:\Users\Me\My File.txt", &opts), "c-users-me-my-file.txt");
+        assert_eq!(slugify(r".config\app.json", &opts), ".config-app.json");
+        // A separator after the last dot means there is no extension
+        assert_eq!(slugify(r"notes.d\today", &opts), "notes-d-today");
+        assert_eq!(slugify("notes.d/today", &opts), "notes-d-today");
+        assert_eq!(slugify(r"v1.2\readme", &opts), "v1.2-readme");
+        let whole = SlugifyOptions::default()
+            .with_extensions(ExtensionConfig { dotfile_policy: DotfilePolicy::KeepWhole, ..Default::default() });
+        assert_eq!(slugify(r".env\local.d", &whole), ".env-local-d");
+        let steps = explain_slug(r"a\b.txt", &opts);
+        assert_eq!(steps[0].stage, "normalize path separators");
+        assert_eq!(steps[0].value, "a/b.txt");
+    }
+
+    #[test]
+    fn test_slugify_rtlo_spoofed_extension() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("file\u{202E}gpj.pdf", &opts), "filegpj.pdf");
+        assert_eq!(slugify("invoice\u{202E}fdp.exe", &opts), "invoicefdp.exe");
+    }
+
+    #[test]
+    fn test_slugify_rtlo_keep_unicode() {
+        let opts = SlugifyOptions { keep_unicode: true, ..Default::default() };
+        assert_eq!(slugify("file\u{202E}gpj.pdf", &opts), "filegpj.pdf");
+        assert_eq!(slugify_string("a\u{200F}b c", &opts), "ab-c");
+    }
+
+    #[test]
+    fn test_explain_records_direction_stripping() {
+        let steps = explain_slug("file\u{202E}gpj.pdf", &SlugifyOptions::default());
+        assert_eq!(steps[0].stage, "strip direction overrides");
+        assert_eq!(steps[0].value, "filegpj.pdf");
+        let steps = explain_slug("file.pdf", &SlugifyOptions::default());
+        assert_eq!(steps[0].stage, "split extension");
+    }
+
+    #[test]
+    fn test_strip_control_chars_ranges() {
+        assert_eq!(strip_control_chars("a\0b\x1fc\x7fd\u{80}e\u{9f}f"), "abcdef");
+        // U+00A0 (no-break space) is just past C1 and is kept
+        assert_eq!(strip_control_chars("a\u{a0}b"), "a\u{a0}b");
+        assert!(matches!(strip_control_chars("clean name.txt"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_slugify_null_byte() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("report\0final.pdf", &opts), "reportfinal.pdf");
+        assert_eq!(slugify_string("a\0b", &opts), "ab");
+    }
+
+    #[test]
+    fn test_slugify_version_dot_placeholder_in_input() {
+        // A literal U+0001 must not come back out as a dot
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("my\x01file.txt", &opts), "myfile.txt");
+        assert_eq!(slugify_string("a\x01b", &opts), "ab");
+    }
+
+    #[test]
+    fn test_strip_control_chars_tab_and_esc() {
+        assert_eq!(strip_control_chars("a\tb"), "ab");
+        assert_eq!(strip_control_chars("\x1bc"), "c");
+    }
+
+    #[test]
+    fn test_slugify_tab() {
+        // Whitespace controls separate words rather than joining them
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("col\tumn.csv", &opts), "col-umn.csv");
+        assert_eq!(slugify("My File\t.txt", &opts), "my-file.txt");
+        assert_eq!(slugify_string("a\r\nb", &opts), "a-b");
+    }
+
+    #[test]
+    fn test_slugify_escape_sequence() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify("\x1b[31mred\x1b[0m.log", &opts), "31mred-0m.log");
+    }
+
+    #[test]
+    fn test_explain_records_control_stripping() {
+        let steps = explain_slug("a\0b\tc.txt", &SlugifyOptions::default());
+        assert_eq!(steps[0].stage, "strip control characters");
+        assert_eq!(steps[0].value, "ab c.txt");
+    }
+
+    #[test]
+    fn test_normalize_slug_collapses_separators() {
+        assert_eq!(normalize_slug("my--file.txt", &Style::Kebab), "my-file.txt");
+        assert_eq!(normalize_slug("a___b", &Style::Snake), "a_b");
+    }
+
+    #[test]
+    fn test_normalize_slug_strips_edge_separators() {
+        assert_eq!(normalize_slug("-my-file-.txt", &Style::Kebab), "my-file.txt");
+        assert_eq!(normalize_slug("_name_", &Style::Snake), "name");
+        // A pure dotfile is all extension and is left alone
+        assert_eq!(normalize_slug(".env-", &Style::Kebab), ".env-");
+    }
+
+    #[test]
+    fn test_normalize_slug_lowercases() {
+        assert_eq!(normalize_slug("My-File.PDF", &Style::Kebab), "my-file.PDF");
+        assert_eq!(normalize_slug("Café_Au_Lait", &Style::Snake), "café_au_lait");
+        assert_eq!(normalize_slug("MyFile.txt", &Style::Pascal), "MyFile.txt");
+    }
+
+    #[test]
+    fn test_normalize_slug_no_transliteration_or_splitting() {
+        assert_eq!(normalize_slug("café-münchen.txt", &Style::Kebab), "café-münchen.txt");
+        // Other separators and spaces are not word boundaries here
+        assert_eq!(normalize_slug("my file_name", &Style::Kebab), "my file_name");
+        assert_eq!(normalize_slug("app-1.2.3.dmg", &Style::Kebab), "app-1.2.3.dmg");
+    }
+
+    #[test]
+    fn test_normalize_slug_borrows_when_clean() {
+        assert!(matches!(normalize_slug("my-file.txt", &Style::Kebab), Cow::Borrowed(_)));
+        assert!(is_normalized_slug("my-file.txt", &Style::Kebab));
+        assert!(!is_normalized_slug("my--file.txt", &Style::Kebab));
+        assert!(is_normalized_slug("My-File", &Style::Pascal));
+    }
+
+    #[test]
+    fn test_slugify_output_is_normalized() {
+        for style in [Style::Kebab, Style::Snake, Style::Pascal] {
+            let opts = SlugifyOptions { style, ..Default::default() };
+            for name in ["My  Résumé (Final).pdf", "--weird__name--.TXT", ".env.local", "app-1.2.3.dmg"] {
+                let slug = slugify(name, &opts);
+                assert!(is_normalized_slug(&slug, &style), "{name:?} -> {slug:?} ({style:?})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_version_numbers_off() {
+        let opts = SlugifyOptions { preserve_version_numbers: false, ..Default::default() };
+        assert_eq!(slugify("app-1.2.3.dmg", &opts), "app-1-2-3.dmg");
+        assert_eq!(slugify("app-1.2.3.tar.gz", &opts), "app-1-2-3.tar.gz");
+        assert_eq!(slugify_string("Release 2.0", &opts), "release-2-0");
+    }
+
+    #[test]
+    fn test_version_numbers_on_by_default() {
+        assert!(SlugifyOptions::default().preserve_version_numbers);
+        assert_eq!(slugify("app-1.2.3.tar.gz", &SlugifyOptions::default()), "app-1.2.3.tar.gz");
+    }
+
+    #[test]
+    fn test_version_numbers_off_keeps_dates() {
+        let opts = SlugifyOptions { preserve_version_numbers: false, preserve_date: true, ..Default::default() };
+        assert_eq!(slugify("Report 2024.03.15 v1.2.pdf", &opts), "report-2024.03.15-v1-2.pdf");
+    }
+
+    #[test]
+    fn test_version_numbers_off_trace() {
+        let opts = SlugifyOptions { preserve_version_numbers: false, ..Default::default() };
+        let steps = explain_slug("app-1.2.dmg", &opts);
+        assert!(steps.iter().all(|s| !s.stage.contains("version dots")));
     }
 
-    let (base, ext) = split_extension(filename);
+    #[test]
+    fn test_preset_identifier_drops_version_dots() {
+        let opts = SlugifyOptions::preset_identifier();
+        assert_eq!(slugify_string("module 1.2", &opts), "module_1_2");
+    }
 
-    // Dotfiles with no base: return as-is
-    if base.is_empty() {
-        return Cow::Borrowed(filename);
+    #[test]
+    fn test_merge_empty_override_is_identity() {
+        for opts in [SlugifyOptions::default(), SlugifyOptions::preset_url(), SlugifyOptions::preset_cross_platform()] {
+            assert_eq!(opts.merge(&SlugifyOptionsOverride::default()), opts);
+        }
     }
 
-    let is_dotfile = base.starts_with('.');
+    #[test]
+    fn test_merge_replaces_set_fields() {
+        let full = SlugifyOptionsOverride {
+            style: Some(Style::Pascal),
+            keep_unicode: Some(true),
+            extensions: Some(ExtensionConfig {
+                split_options: Some(SplitExtensionOptions::default()),
+                dotfile_policy: DotfilePolicy::KeepWhole,
+                normalize: true,
+            }),
+            max_length: Some(Some(12)),
+            stop_words: Some(vec!["the".to_string()]),
+            min_word_length: Some(2),
+            #[cfg(feature = "locale")]
+            locale: Some(Some("de".to_string())),
+            custom_transliteration: Some(Some(BTreeMap::from([('&', "and".to_string())]))),
+            #[cfg(feature = "unicode-script")]
+            script_policies: Some(Some(BTreeMap::new())),
+            preserve_date: Some(true),
+            preserve_version_numbers: Some(false),
+            cross_platform: Some(true),
+            sentence_case: Some(true),
+            normalise_whitespace: Some(false),
+            warn_on_unchanged: Some(true),
+            strip_punctuation_only: Some(true),
+            preserve_leading_separator: Some(true),
+            preserve_trailing_separator: Some(true),
+            smart_case: Some(true),
+            strip_copy_annotations: Some(true),
+        };
 
-    let slugified = slugify_core(base, options);
+        let merged = SlugifyOptions::default().merge(&full);
+        assert_eq!(merged.style, Style::Pascal);
+        assert!(merged.keep_unicode);
+        assert_eq!(merged.extensions.split_options, Some(SplitExtensionOptions::default()));
+        assert_eq!(merged.extensions.dotfile_policy, DotfilePolicy::KeepWhole);
+        assert!(merged.extensions.normalize);
+        assert_eq!(merged.max_length, Some(12));
+        assert_eq!(merged.stop_words, ["the"]);
+        assert_eq!(merged.min_word_length, 2);
+        assert_eq!(merged.custom_transliteration.as_ref().map(BTreeMap::len), Some(1));
+        assert!(merged.preserve_date);
+        assert!(!merged.preserve_version_numbers);
+        assert!(merged.cross_platform);
+        assert!(merged.sentence_case);
+        assert!(!merged.normalise_whitespace);
+        assert!(merged.warn_on_unchanged);
+        assert!(merged.strip_punctuation_only);
+        assert!(merged.preserve_leading_separator);
+        assert!(merged.preserve_trailing_separator);
+        assert!(merged.smart_case);
+        assert!(merged.strip_copy_annotations);
+        #[cfg(feature = "locale")]
+        assert_eq!(merged.locale.as_deref(), Some("de"));
+        #[cfg(feature = "unicode-script")]
+        assert!(merged.script_policies.is_some());
+    }
 
-    if slugified.is_empty() {
-        return Cow::Owned(ext.to_string());
+    #[test]
+    fn test_is_noop() {
+        let lenient = SlugifyOptions {
+            keep_unicode: true,
+            strip_punctuation_only: true,
+            preserve_leading_separator: true,
+            preserve_trailing_separator: true,
+            ..Default::default()
+        };
+        let all = [SlugifyOptions::default(), lenient, SlugifyOptions::preset_url(), SlugifyOptions::preset_identifier()];
+        let styles = [Style::Kebab, Style::Snake, Style::Pascal];
+        for opts in all.iter().flat_map(|o| styles.map(|style| SlugifyOptions { style, ..o.clone() })) {
+            assert!(!opts.is_noop());
+            assert!(!opts.is_noop_for("My File.txt"), "{opts:?}");
+        }
     }
 
-    // Restore leading dot for dotfiles (e.g. .env.local → .env.local)
-    let slugified = if is_dotfile {
-        format!(".{slugified}")
-    } else {
-        slugified
-    };
+    #[test]
+    fn test_is_noop_for() {
+        let opts = SlugifyOptions::default();
+        assert!(opts.is_noop_for("my-file.txt"));
+        assert!(opts.is_noop_for(".gitignore"));
+        assert!(opts.is_noop_for("photo-2024-01.JPG"));
+        assert!(opts.is_noop_for("app-1.2.3.tar.gz"));
+        assert!(!opts.is_noop_for("MyFile.txt"));
+        assert!(!opts.is_noop_for("my_file.txt"));
+        assert!(!opts.is_noop_for("café-menu.txt"));
 
-    // Truncate if filename would exceed filesystem limit
-    let slugified = truncate_base(&slugified, ext, MAX_FILENAME_BYTES);
+        let unicode = SlugifyOptions { keep_unicode: true, ..Default::default() };
+        assert!(unicode.is_noop_for("café-menu.txt"));
+        assert!(!unicode.is_noop_for("Café Menu.txt"));
 
-    Cow::Owned(format!("{slugified}{ext}"))
-}
+        let no_versions = SlugifyOptions { preserve_version_numbers: false, ..Default::default() };
+        assert!(!no_versions.is_noop_for("app-1.2.3.zip"));
 
-/// Slugify an arbitrary string (not a filename).
-///
-/// Unlike [`slugify`], this treats the entire input as plain text — no
-/// extension splitting, no dotfile preservation. Use this for generating
-/// URL slugs, identifiers, or other non-filename use cases.
-///
-/// # Examples
-///
-/// ```
-/// use fileslug::{slugify_string, SlugifyOptions};
-///
-/// let opts = SlugifyOptions::default();
-/// assert_eq!(slugify_string("My Blog Post Title!", &opts), "my-blog-post-title");
-/// assert_eq!(slugify_string("Café Résumé", &opts), "cafe-resume");
-/// ```
-#[must_use]
-pub fn slugify_string(input: &str, options: &SlugifyOptions) -> String {
-    if input.is_empty() {
-        return String::new();
+        let short = SlugifyOptions { max_length: Some(8), ..Default::default() };
+        assert!(!short.is_noop_for("a-long-name.txt"));
     }
 
-    let slugified = slugify_core(input, options);
+    #[test]
+    fn test_truncation_drops_dangling_version_dot() {
+        let opts = SlugifyOptions { max_length: Some(16), ..Default::default() };
+        assert_eq!(slugify("iStatMenus7.20.zip", &opts), "istatmenus7.zip");
+        assert_eq!(truncate_base("app7.20", ".zip", 9), "app7");
+        assert_slugify_idempotent("iStatMenus7.20.zip", &opts);
+    }
 
-    if slugified.is_empty() {
-        return String::new();
+    #[test]
+    fn test_pascal_reads_capitals_as_word_starts() {
+        let opts = SlugifyOptions { style: Style::Pascal, ..Default::default() };
+        assert_eq!(slugify("MyFile.txt", &opts), "MyFile.txt");
+        assert_eq!(slugify("Report (2) (copy).pdf", &opts), "Report2Copy.pdf");
+        assert_eq!(slugify("MacBook Pro", &opts), "MacBookPro");
+        assert_eq!(slugify("IMG_20240301.jpg", &opts), "Img20240301.jpg");
+        for input in ["My Great Document.txt", "report v1.2 final", "Ünïcödé Fïlé.txt"] {
+            assert_slugify_idempotent(input, &opts);
+        }
+        // Kebab and snake case still treat a capital as part of its word
+        assert_eq!(slugify("MyFile.txt", &SlugifyOptions::default()), "myfile.txt");
     }
 
-    truncate_base(&slugified, "", MAX_SLUG_BYTES)
-}
+    #[test]
+    fn test_split_case_transitions() {
+        assert_eq!(split_case_transitions("MyFile2Go"), ["My", "File2", "Go"]);
+        assert_eq!(split_case_transitions("IMGFile"), ["IMGFile"]);
+        assert_eq!(split_case_transitions("lower"), ["lower"]);
+        assert_eq!(split_case_transitions("ÜberÄrger"), ["Über", "Ärger"]);
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_warn_on_unchanged_collects_diagnostics() {
+        let _ = take_diagnostics();
+        let opts = SlugifyOptions { warn_on_unchanged: true, ..Default::default() };
+        for name in [".gitignore", "My File.txt", "my-file.txt", "app-1.2.3.tar.gz"] {
+            let _ = slugify(name, &opts);
+        }
+        assert_eq!(
+            take_diagnostics(),
+            [
+                SlugDiagnostic::Unchanged { input: ".gitignore".to_string() },
+                SlugDiagnostic::Unchanged { input: "my-file.txt".to_string() },
+                SlugDiagnostic::Unchanged { input: "app-1.2.3.tar.gz".to_string() },
+            ]
+        );
+        assert!(take_diagnostics().is_empty());
+    }
 
     #[test]
-    fn test_split_simple_extension() {
-        assert_eq!(split_extension("hello.txt"), ("hello", ".txt"));
+    fn test_warn_on_unchanged_off_records_nothing() {
+        let _ = take_diagnostics();
+        let _ = slugify("my-file.txt", &SlugifyOptions::default());
+        let opts = SlugifyOptions { warn_on_unchanged: true, ..Default::default() };
+        assert!(opts.is_noop_for("my-file.txt"));
+        assert!(take_diagnostics().is_empty());
     }
 
     #[test]
-    fn test_split_compound_extension() {
-        assert_eq!(split_extension("archive.tar.gz"), ("archive", ".tar.gz"));
-        assert_eq!(split_extension("backup.tar.bz2"), ("backup", ".tar.bz2"));
-        assert_eq!(split_extension("data.tar.xz"), ("data", ".tar.xz"));
-        assert_eq!(split_extension("logs.tar.zst"), ("logs", ".tar.zst"));
+    fn test_diagnostics_are_per_thread() {
+        let _ = take_diagnostics();
+        let opts = SlugifyOptions { warn_on_unchanged: true, ..Default::default() };
+        let other = std::thread::spawn(move || {
+            let _ = slugify("clean.txt", &opts);
+            take_diagnostics().len()
+        });
+        assert_eq!(other.join().unwrap(), 1);
+        assert!(take_diagnostics().is_empty());
     }
 
     #[test]
-    fn test_split_dotfile() {
-        assert_eq!(split_extension(".gitignore"), ("", ".gitignore"));
-        assert_eq!(split_extension(".env"), ("", ".env"));
+    fn test_merge_keeps_unset_fields() {
+        let base = SlugifyOptions::preset_url();
+        let merged = base.merge(&SlugifyOptionsOverride { style: Some(Style::Snake), ..Default::default() });
+        assert_eq!(merged.style, Style::Snake);
+        assert_eq!(merged.max_length, base.max_length);
+        assert_eq!(merged.stop_words, base.stop_words);
     }
 
     #[test]
-    fn test_split_no_extension() {
-        assert_eq!(split_extension("Makefile"), ("Makefile", ""));
-        assert_eq!(split_extension("README"), ("README", ""));
+    fn test_merge_some_none_clears_optional_fields() {
+        let base = SlugifyOptions { max_length: Some(10), ..SlugifyOptions::with_transliteration_override(&[('&', "and")]) };
+        let merged = base.merge(&SlugifyOptionsOverride {
+            max_length: Some(None),
+            custom_transliteration: Some(None),
+            ..Default::default()
+        });
+        assert_eq!(merged.max_length, None);
+        assert_eq!(merged.custom_transliteration, None);
     }
 
     #[test]
-    fn test_split_multiple_dots() {
-        assert_eq!(split_extension("my.cool.file.txt"), ("my.cool.file", ".txt"));
+    fn test_merge_layers_in_order() {
+        let config = SlugifyOptionsOverride { style: Some(Style::Snake), max_length: Some(Some(40)), ..Default::default() };
+        let env = SlugifyOptionsOverride { style: Some(Style::Pascal), keep_unicode: Some(true), ..Default::default() };
+        let flags = SlugifyOptionsOverride { keep_unicode: Some(false), ..Default::default() };
+
+        let merged = SlugifyOptions::default().merge(&config).merge(&env).merge(&flags);
+        assert_eq!(merged.style, Style::Pascal);
+        assert!(!merged.keep_unicode);
+        assert_eq!(merged.max_length, Some(40));
     }
 
+    #[cfg(feature = "parallel")]
     #[test]
-    fn test_split_dotfile_with_extension() {
-        assert_eq!(split_extension(".bashrc"), ("", ".bashrc"));
+    fn test_slugify_batch_rayon_matches_sequential() {
+        let names: Vec<String> = (0..2_000).map(|i| format!("Report Nº{i} – Final (v1.{i}).tar.gz")).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let opts = SlugifyOptions::preset_url();
+
+        let parallel = slugify_batch_rayon(&names, &opts);
+        let sequential: Vec<_> = names.iter().map(|name| slugify(name, &opts)).collect();
+        assert_eq!(parallel, sequential);
+        assert!(slugify_batch_rayon(&[], &opts).is_empty());
     }
 
-    // --- slugify pipeline tests ---
+    #[test]
+    fn test_options_as_cache_key() {
+        use std::collections::HashMap;
+
+        let mut cache = HashMap::new();
+        cache.insert(SlugifyOptions::with_transliteration_override(&[('&', "and"), ('@', "at")]), "first");
+        cache.insert(SlugifyOptions::preset_url(), "url");
+
+        // Insertion order of map entries doesn't change the key
+        let same = SlugifyOptions::with_transliteration_override(&[('@', "at"), ('&', "and")]);
+        assert_eq!(cache.get(&same), Some(&"first"));
+        assert_eq!(cache.get(&SlugifyOptions::preset_url()), Some(&"url"));
+        assert_eq!(cache.get(&SlugifyOptions::default()), None);
+    }
 
     #[test]
-    fn test_slugify_basic_kebab() {
+    fn test_slugify_template() {
+        let vars = HashMap::from([("year", "2024"), ("title", "My Song"), ("artist", "Björk & Friends")]);
         let opts = SlugifyOptions::default();
-        assert_eq!(slugify("My Cool File.txt", &opts), "my-cool-file.txt");
+        assert_eq!(slugify_template("{year}-{title}.mp3", &vars, &opts).unwrap(), "2024-my-song.mp3");
+        assert_eq!(
+            slugify_template("{year}_{artist}/{title}", &vars, &opts).unwrap(),
+            "2024_bjork-friends/my-song"
+        );
+        assert_eq!(slugify_template("no slots.txt", &vars, &opts).unwrap(), "no slots.txt");
+
+        let snake = SlugifyOptions { style: Style::Snake, ..Default::default() };
+        assert_eq!(slugify_template("{title}.txt", &vars, &snake).unwrap(), "my_song.txt");
     }
 
     #[test]
-    fn test_slugify_snake() {
-        let opts = SlugifyOptions { style: Style::Snake, ..Default::default() };
-        assert_eq!(slugify("My Cool File.txt", &opts), "my_cool_file.txt");
+    fn test_slugify_template_escaped_braces() {
+        let vars = HashMap::from([("title", "My Song")]);
+        let result = slugify_template("{{{title}}}.txt", &vars, &SlugifyOptions::default());
+        assert_eq!(result.unwrap(), "{my-song}.txt");
     }
 
     #[test]
-    fn test_slugify_pascal() {
-        let opts = SlugifyOptions { style: Style::Pascal, ..Default::default() };
-        assert_eq!(slugify("my cool file.txt", &opts), "MyCoolFile.txt");
+    fn test_fill_template_keeps_values() {
+        let vars = HashMap::from([("slug".to_string(), "My Song".to_string()), ("ext".to_string(), "tar.gz".to_string())]);
+        assert_eq!(fill_template("{slug}.{ext}", &vars).unwrap(), "My Song.tar.gz");
+        assert_eq!(fill_template("{slug}.{size}", &vars).unwrap_err(), TemplateError::UndefinedVariable("size".to_string()));
     }
 
     #[test]
-    fn test_slugify_unicode_transliterate() {
+    fn test_slugify_template_errors() {
+        let vars = HashMap::from([("title", "My Song")]);
         let opts = SlugifyOptions::default();
-        assert_eq!(slugify("Café Résumé.txt", &opts), "cafe-resume.txt");
+        let err = |template| slugify_template(template, &vars, &opts).unwrap_err();
+
+        assert_eq!(err("{year}-{title}"), TemplateError::UndefinedVariable("year".to_string()));
+        assert_eq!(err("{title}-{year"), TemplateError::UnclosedSlot { position: 8 });
+        assert_eq!(err("title}"), TemplateError::UnmatchedBrace { position: 5 });
+        assert_eq!(err("a{}"), TemplateError::InvalidSlotName { position: 1, name: String::new() });
+        assert_eq!(err("{my title}"), TemplateError::InvalidSlotName { position: 0, name: "my title".to_string() });
+        assert_eq!(err("{a{b}"), TemplateError::InvalidSlotName { position: 0, name: "a{b".to_string() });
+        assert_eq!(err("{year}").to_string(), "undefined template variable 'year'");
     }
 
     #[test]
-    fn test_slugify_keep_unicode() {
-        let opts = SlugifyOptions { keep_unicode: true, ..Default::default() };
-        assert_eq!(slugify("Café Résumé.txt", &opts), "café-résumé.txt");
+    fn test_slugify_path_components() {
+        let opts = SlugifyOptions::default();
+        let slug = |path: &str| slugify_path_components(Path::new(path), &opts);
+        assert_eq!(slug("/My Documents/Sub Folder/My File.txt"), Path::new("/my-documents/sub-folder/my-file.txt"));
+        // Directories are plain text, so their dots are separators; the file keeps its extension
+        assert_eq!(slug("Backups.2024/Old Site.tar.gz"), Path::new("backups-2024/old-site.tar.gz"));
+        assert_eq!(slug("./.Config Files/../Read Me"), Path::new("./.config-files/../read-me"));
+        assert_eq!(slug("/!!!/Notes.md"), Path::new("/!!!/notes.md"));
+        assert_eq!(slug("/"), Path::new("/"));
+        assert_eq!(slug(""), Path::new(""));
     }
 
     #[test]
-    fn test_slugify_brackets_stripped_contents_kept() {
+    fn test_slugify_path_display() {
         let opts = SlugifyOptions::default();
-        assert_eq!(slugify("Report (Final) [2024].txt", &opts), "report-final-2024.txt");
+        let show = |path: &str| slugify_path_display(Path::new(path), &opts).replace(MAIN_SEPARATOR, "/");
+        assert_eq!(show("/My Projects/Sub Work/file.txt"), "/my-projects/sub-work/file.txt");
+        assert_eq!(show("../Old Notes/Draft.md"), "../old-notes/draft.md");
+        assert_eq!(show("My File.txt"), "my-file.txt");
+        assert_eq!(show("/"), "/");
+        assert_eq!(show(""), "");
     }
 
+    #[cfg(windows)]
     #[test]
-    fn test_slugify_compound_extension() {
+    fn test_slugify_path_display_windows() {
         let opts = SlugifyOptions::default();
-        assert_eq!(slugify("My Archive File.tar.gz", &opts), "my-archive-file.tar.gz");
+        assert_eq!(slugify_path_display(Path::new("C:/My Docs/A.txt"), &opts), "C:\\my-docs\\a.txt");
+        assert_eq!(slugify_path_display(Path::new("C:My Docs\\A.txt"), &opts), "C:my-docs\\a.txt");
     }
 
     #[test]
-    fn test_slugify_dotfile_untouched() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify(".gitignore", &opts), ".gitignore");
+    fn test_paths_diff_display() {
+        let show = |a: &str, b: &str| paths_diff_display(Path::new(a), Path::new(b)).replace(MAIN_SEPARATOR, "/");
+        assert_eq!(show("/a/b.txt", "/a/b.txt"), "/a/b.txt");
+        assert_eq!(show("Sub Work/My\tFile.txt", "sub-work/my-file.txt"), "[-Sub Work-]{+sub-work+}/[-My\\tFile.txt-]{+my-file.txt+}");
+        assert_eq!(show("a/b/c", "a/b"), "a/b/[-c-]");
+        assert_eq!(show("a", "a/b"), "a/{+b+}");
     }
 
     #[test]
-    fn test_slugify_already_clean() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("already-clean.txt", &opts), "already-clean.txt");
+    fn test_slugify_path_components_snake() {
+        let opts = SlugifyOptions { style: Style::Snake, ..Default::default() };
+        let path = slugify_path_components(Path::new("Café Menu/Plats Du Jour.PDF"), &opts);
+        assert_eq!(path, Path::new("cafe_menu/plats_du_jour.PDF"));
     }
 
     #[test]
-    fn test_slugify_special_chars() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("file@name#with$symbols.txt", &opts), "file-name-with-symbols.txt");
+    fn test_normalize_extension_all_aliases() {
+        let opts = SlugifyOptions::default().with_extensions(ExtensionConfig { normalize: true, ..Default::default() });
+        for (alias, canonical) in DEFAULT_EXTENSION_ALIASES {
+            assert_eq!(slugify(&format!("My File.{alias}"), &opts), format!("my-file.{canonical}"), "{alias}");
+            let upper = format!("My File.{}", alias.to_ascii_uppercase());
+            assert_eq!(slugify(&upper, &opts), format!("my-file.{}", canonical.to_ascii_uppercase()), "{alias}");
+            assert_eq!(slugify(&format!("my-file.{canonical}"), &opts), format!("my-file.{canonical}"), "{canonical}");
+        }
     }
 
     #[test]
-    fn test_slugify_collapses_separators() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("too   many   spaces.txt", &opts), "too-many-spaces.txt");
+    fn test_normalize_extension() {
+        let opts = SlugifyOptions::default().with_extensions(ExtensionConfig { normalize: true, ..Default::default() });
+        assert_eq!(slugify("Photo.Jpeg", &opts), "photo.Jpg");
+        assert_eq!(slugify("Photo.jPEG", &opts), "photo.jpg");
+        assert_eq!(slugify("song.mp3", &opts), "song.mp3");
+        assert_eq!(slugify(".jpeg", &opts), ".jpeg");
+        assert_eq!(slugify("archive.tar.gz", &opts), "archive.tar.gz");
+        assert_eq!(slugify("Photo.JPEG", &SlugifyOptions::default()), "photo.JPEG");
+        assert_eq!(slugify_ext_too("Photo.JPEG", &opts), "photo.jpg");
+        assert_eq!(slugify_stem("Photo.JPEG", &opts), "photo.JPEG");
     }
 
     #[test]
-    fn test_slugify_trims_separators() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify(" leading and trailing .txt", &opts), "leading-and-trailing.txt");
+    fn test_slug_pipeline_matches_slugify() {
+        let names = [
+            "My File.txt",
+            "Café Résumé (Final).PDF",
+            "Release Notes v2.10-2.12.26.md",
+            "The Quick Brown Fox.tar.gz",
+            "MyCoolFile.rs",
+            "report 2024.03.15 [draft].docx",
+            "a b c d e f g h i j k l m n o p q r s t u v w x y z.txt",
+            "Makefile",
+            "Report (2) (copy).pdf",
+        ];
+        let option_sets = [
+            SlugifyOptions::default(),
+            SlugifyOptions { style: Style::Snake, sentence_case: true, preserve_date: true, ..Default::default() },
+            SlugifyOptions { style: Style::Pascal, ..Default::default() },
+            SlugifyOptions { keep_unicode: true, max_length: Some(20), ..Default::default() },
+            SlugifyOptions { stop_words: vec!["the".to_string()], min_word_length: 2, ..Default::default() },
+            SlugifyOptions { preserve_version_numbers: false, ..Default::default() },
+            SlugifyOptions { strip_copy_annotations: true, ..Default::default() },
+        ];
+        for opts in &option_sets {
+            let pipeline = SlugPipeline::new(opts);
+            for name in names {
+                let (base, ext) = pipeline.split(name);
+                let words = pipeline.collect_words(&pipeline.transliterate(base));
+                let chained = pipeline.reassemble(&pipeline.join(&words), ext);
+                assert_eq!(chained, slugify(name, opts), "{name:?} with {opts:?}");
+            }
+        }
     }
 
     #[test]
-    fn test_slugify_no_extension() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("My Makefile", &opts), "my-makefile");
+    fn test_parse_compound_extensions() {
+        let exts = parse_compound_extensions("# neuroimaging\n.nii.gz\n\n  .WARC.gz  \n").unwrap();
+        assert_eq!(exts, [".nii.gz", ".WARC.gz"]);
+
+        for bad in ["tar.gz", ".gz", ".tar.gz.bak", ".tar..gz", ".tar.g z", ".tär.gz"] {
+            let err = parse_compound_extensions(&format!(".nii.gz\n{bad}\n")).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{bad:?}");
+            assert!(err.to_string().starts_with("line 2: "), "{err}");
+        }
+        let missing = load_compound_extensions_file(Path::new("/nonexistent/compound-extensions.txt"));
+        assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
     }
 
     #[test]
-    fn test_slugify_full_pipeline() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("Café Résumé (Final Copy) [2024].tar.gz", &opts), "cafe-resume-final-copy-2024.tar.gz");
+    fn test_with_extra_compound_extensions() {
+        let exts = parse_compound_extensions(".nii.gz\n.ipynb.bak\n").unwrap();
+        let opts = SlugifyOptions::default().with_extra_compound_extensions(&exts);
+        assert_eq!(opts.extension_config().split_extension("brain.nii.gz"), ("brain", ".nii.gz"));
+        assert_eq!(opts.extension_config().split_extension("Analysis.ipynb.bak"), ("Analysis", ".ipynb.bak"));
+        assert_eq!(slugify("My Analysis.ipynb.bak", &opts), "my-analysis.ipynb.bak");
+        assert_eq!(slugify("My Analysis.ipynb.bak", &SlugifyOptions::default()), "my-analysis-ipynb.bak");
+
+        let compound = &opts.extensions.split_options.as_ref().unwrap().compound_extensions;
+        assert_eq!(compound.len(), DEFAULT_COMPOUND_EXTENSIONS.len() + 1, ".nii.gz is built in");
+        assert_eq!(compound.last().map(String::as_str), Some(".ipynb.bak"));
     }
 
     #[test]
-    fn test_slugify_curly_braces() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("file {draft}.txt", &opts), "file-draft.txt");
+    fn test_with_extensions() {
+        let extensions = ExtensionConfig { dotfile_policy: DotfilePolicy::SplitAtFirst, normalize: true, ..Default::default() };
+        let opts = SlugifyOptions::preset_url().with_extensions(extensions.clone());
+        assert_eq!(opts.extension_config(), &extensions);
+        assert_eq!(opts.max_length, Some(100));
+        assert_eq!(SlugifyOptions::default().extension_config(), &ExtensionConfig::default());
     }
 
     #[test]
-    fn test_slugify_pascal_multiple_words() {
-        let opts = SlugifyOptions { style: Style::Pascal, ..Default::default() };
-        assert_eq!(slugify("Hello World Foo Bar.txt", &opts), "HelloWorldFooBar.txt");
+    fn test_dotfile_extension_policy() {
+        let with = |policy| ExtensionConfig { dotfile_policy: policy, ..Default::default() };
+        let last = with(DotfilePolicy::SplitAtLast);
+        let whole = with(DotfilePolicy::KeepWhole);
+        let first = with(DotfilePolicy::SplitAtFirst);
+        let slugify = |name, extensions: &ExtensionConfig| {
+            slugify(name, &SlugifyOptions::default().with_extensions(extensions.clone())).into_owned()
+        };
+
+        assert_eq!(last.split_extension(".env.local"), (".env", ".local"));
+        assert_eq!(whole.split_extension(".env.local"), ("", ".env.local"));
+        assert_eq!(first.split_extension(".env.local"), (".env", ".local"));
+        for opts in [&last, &whole, &first] {
+            assert_eq!(opts.split_extension(".gitignore"), ("", ".gitignore"));
+            assert_eq!(opts.split_extension(".bashrc"), ("", ".bashrc"));
+            assert_eq!(slugify(".env.local", opts), ".env.local");
+            assert_eq!(slugify(".gitignore", opts), ".gitignore");
+            assert_eq!(slugify(".bashrc", opts), ".bashrc");
+            assert_eq!(opts.split_extension("notes.old.txt"), ("notes.old", ".txt"));
+        }
+
+        assert_eq!(last.split_extension(".eslintrc.old.json"), (".eslintrc.old", ".json"));
+        assert_eq!(first.split_extension(".eslintrc.old.json"), (".eslintrc", ".old.json"));
+        assert_eq!(slugify(".My Config.JSON", &last), ".my-config.JSON");
+        assert_eq!(slugify(".My Config.JSON", &whole), ".My Config.JSON");
+        assert_eq!(slugify(".My_Config.Old.json", &first), ".my-config.Old.json");
+        assert_eq!(first.split_extension(".env.t\u{FFFD}t"), (".env.t\u{FFFD}t", ""));
+        assert_eq!(first.split_extension("..env.local"), ("..env", ".local"));
     }
 
     #[test]
-    fn test_slugify_empty_string() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("", &opts), "");
+    fn test_detect_case_convention() {
+        use CaseConvention::{CamelCase, KebabCase, PascalCase, SnakeCase, Unknown};
+
+        for (input, expected) in [
+            ("myFileName", CamelCase),
+            ("parseHTTPResponse", CamelCase),
+            ("version2Final", CamelCase),
+            ("MyFileName", PascalCase),
+            ("XMLHttpRequest", PascalCase),
+            ("ÉcoleNormale", PascalCase),
+            ("my_file_name", SnakeCase),
+            ("MY_CONSTANT", SnakeCase),
+            ("_private_module", SnakeCase),
+            ("my-file-name", KebabCase),
+            ("", Unknown),
+            ("myfile", Unknown),
+            ("Myfile", Unknown),
+            ("MYFILE", Unknown),
+            ("my_file-name", Unknown),
+            ("my File", Unknown),
+            ("myFile.v2", Unknown),
+            ("_private", Unknown),
+            ("--", Unknown),
+        ] {
+            assert_eq!(detect_case_convention(input), expected, "{input:?}");
+        }
     }
 
     #[test]
-    fn test_slugify_only_special_chars() {
+    fn test_strip_copy_annotations() {
         let opts = SlugifyOptions::default();
-        assert_eq!(slugify("@#$.txt", &opts), ".txt");
+        assert_eq!(strip_copy_annotations("Report (copy).pdf", &opts), "Report.pdf");
+        assert_eq!(strip_copy_annotations("Report - Copy.pdf", &opts), "Report.pdf");
+        assert_eq!(strip_copy_annotations("Report- copy.pdf", &opts), "Report.pdf");
+        assert_eq!(strip_copy_annotations("Report COPY.pdf", &opts), "Report.pdf");
+        assert_eq!(strip_copy_annotations("Report (99).pdf", &opts), "Report.pdf");
+        assert_eq!(strip_copy_annotations("Report (2) (copy).pdf", &opts), "Report.pdf");
+        assert_eq!(strip_copy_annotations("Notes - Copy (3)", &opts), "Notes");
+        assert_eq!(strip_copy_annotations("notes copy", &opts), "notes");
+        assert_eq!(strip_copy_annotations("notes Copy 12", &opts), "notes");
+        assert!(matches!(strip_copy_annotations("Report.pdf", &opts), Cow::Borrowed(_)));
+        for kept in [
+            "Report (100).pdf",
+            "Report (01).pdf",
+            "Report 1.pdf",
+            "Report 2024.pdf",
+            "Chapter 3.pdf",
+            "Season 2.mkv",
+            "Report copy 1.pdf",
+            "Report(1).pdf",
+            "copy.txt",
+            " (1).txt",
+            "copy 2.txt",
+            "Copy of Report.pdf",
+        ] {
+            assert_eq!(strip_copy_annotations(kept, &opts), kept);
+        }
     }
 
     #[test]
-    fn test_slugify_numbers_only() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("12345.txt", &opts), "12345.txt");
+    fn test_strip_copy_annotations_uses_extension_config() {
+        let mut split = SplitExtensionOptions::default();
+        split.compound_extensions.push(".warc.gz".to_string());
+        let opts = SlugifyOptions::default().with_extensions(ExtensionConfig { split_options: Some(split), ..Default::default() });
+        assert_eq!(strip_copy_annotations("Crawl (1).warc.gz", &opts), "Crawl.warc.gz");
+        assert_eq!(strip_copy_annotations("Crawl (1).warc.gz", &SlugifyOptions::default()), "Crawl (1).warc.gz");
     }
 
     #[test]
-    fn test_slugify_dotfile_with_extension() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify(".env.local", &opts), ".env.local");
+    fn test_slugify_strip_copy_annotations() {
+        let opts = SlugifyOptions { strip_copy_annotations: true, ..Default::default() };
+        assert_eq!(slugify("Copy of Copy of Budget_2024 (1).xlsx", &opts), "copy-of-copy-of-budget-2024.xlsx");
+        assert_eq!(slugify("Copy of Copy of Budget_2024 (1).xlsx", &SlugifyOptions::default()), "copy-of-copy-of-budget-2024-1.xlsx");
+        assert_eq!(slugify("IMG_0042 copy 2.JPG", &opts), "img-0042.JPG");
+        assert_eq!(slugify("archive (1).tar.gz", &opts), "archive.tar.gz");
+
+        let steps = explain_slug("Report (copy).pdf", &opts);
+        assert_eq!(steps[1].stage, "strip copy annotations");
+        assert_eq!(steps[1].value, "Report");
     }
 
     #[test]
-    fn test_slugify_hidden_config_multipart() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify(".config.backup.old", &opts), ".config-backup.old");
+    fn test_smart_case() {
+        let smart = SlugifyOptions { smart_case: true, ..Default::default() };
+        assert_eq!(slugify("myFileName.txt", &smart), "my-file-name.txt");
+        assert_eq!(slugify("myFileName.txt", &SlugifyOptions::default()), "myfilename.txt");
+        assert_eq!(slugify("MyFileName.txt", &smart), "my-file-name.txt");
+        assert_eq!(slugify("XMLHttpRequest.js", &smart), "xmlhttp-request.js");
+        assert_eq!(slugify("my_file_name.txt", &smart), "my-file-name.txt");
+        assert_eq!(slugify("my-file-name.txt", &smart), "my-file-name.txt");
+        // Only whole-name conventions are split
+        assert_eq!(slugify("My camelCase Notes.txt", &smart), "my-camelcase-notes.txt");
+        assert_eq!(slugify("caféMenu.txt", &smart), "cafe-menu.txt");
+
+        let snake = SlugifyOptions { style: Style::Snake, ..smart.clone() };
+        assert_eq!(slugify("parseHttpResponse.rs", &snake), "parse_http_response.rs");
+        assert_eq!(slugify_string("userAccountId", &snake), "user_account_id");
+        let pascal = SlugifyOptions { style: Style::Pascal, ..smart };
+        assert_eq!(slugify("myFileName.txt", &pascal), "MyFileName.txt");
     }
 
     #[test]
-    fn test_split_dotfile_with_second_extension() {
-        assert_eq!(split_extension(".env.local"), (".env", ".local"));
+    fn test_preserve_boundary_separators() {
+        let leading = SlugifyOptions { preserve_leading_separator: true, ..Default::default() };
+        let trailing = SlugifyOptions { preserve_trailing_separator: true, ..Default::default() };
+        let both = SlugifyOptions { preserve_leading_separator: true, ..trailing.clone() };
+
+        assert_eq!(slugify("_private_module.rs", &leading), "_private-module.rs");
+        assert_eq!(slugify("_private_module.rs", &SlugifyOptions::default()), "private-module.rs");
+        assert_eq!(slugify("_internal_tool.sh", &leading), "_internal-tool.sh");
+        assert_eq!(slugify("__init__.py", &leading), "_init.py");
+        assert_eq!(slugify("__init__.py", &trailing), "init_.py");
+        assert_eq!(slugify("__init__.py", &both), "_init_.py");
+        assert_eq!(slugify("-Draft Notes-.md", &both), "-draft-notes-.md");
+        // Only `_` and `-` count, and only at the very start or end
+        assert_eq!(slugify(" _Spaced.txt", &leading), "spaced.txt");
+        assert_eq!(slugify("(_Bracketed).txt", &leading), "bracketed.txt");
+        assert_eq!(slugify("Plain Name.txt", &both), "plain-name.txt");
+        // Nothing to keep a separator around
+        assert_eq!(slugify("___.txt", &both), ".txt");
+        assert_eq!(slugify_string("_My Title_", &both), "_my-title_");
+        let snake = SlugifyOptions { style: Style::Snake, ..leading.clone() };
+        assert_eq!(slugify("-Private Module.rs", &snake), "-private_module.rs");
     }
 
     #[test]
-    fn test_slugify_only_special_no_ext() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("@@@", &opts), "");
+    fn test_strip_punctuation_only() {
+        let opts = SlugifyOptions { strip_punctuation_only: true, ..Default::default() };
+        assert_eq!(slugify("my_already_slugified.txt", &opts), "my_already_slugified.txt");
+        assert_eq!(slugify("My-File_v2 (Draft!).txt", &opts), "my-file_v2-draft.txt");
+        assert_eq!(slugify("__init__.py", &opts), "init.py");
+        assert_eq!(slugify("a - b.txt", &opts), "a-b.txt");
+        assert_eq!(slugify("my_already_slugified.txt", &SlugifyOptions::default()), "my-already-slugified.txt");
+
+        let snake = SlugifyOptions { style: Style::Snake, ..opts };
+        assert_eq!(slugify("my-file name.txt", &snake), "my-file_name.txt");
     }
 
-    // --- Adversarial & unicode tests ---
+    #[test]
+    fn test_normalise_whitespace() {
+        assert_eq!(normalise_whitespace("a\u{2009}b \u{A0} c\u{3000}d"), "a b c d");
+        assert!(matches!(normalise_whitespace("plain  ascii"), Cow::Borrowed(_)));
+    }
 
     #[test]
-    fn test_slugify_shell_injection() {
+    fn test_slugify_unicode_whitespace() {
         let opts = SlugifyOptions::default();
-        let result = slugify("$(echo pwned).txt", &opts);
-        assert!(!result.contains('$'));
-        assert!(!result.contains('('));
-        assert!(!result.contains(')'));
-        assert_eq!(result, "echo-pwned.txt");
+        let off = SlugifyOptions { normalise_whitespace: false, ..Default::default() };
+        for name in ["My\u{A0}File.txt", "My\u{2009}File.txt", "My\u{3000}File.txt", "My\u{202F}\u{A0}File.txt"] {
+            assert_eq!(slugify(name, &opts), "my-file.txt", "{name:?}");
+            assert_eq!(slugify(name, &off), "my-file.txt", "{name:?}");
+        }
+        let unicode = SlugifyOptions { keep_unicode: true, ..Default::default() };
+        assert_eq!(slugify_string("Café\u{3000}Crème", &unicode), "café-crème");
     }
 
     #[test]
-    fn test_slugify_backticks() {
-        let opts = SlugifyOptions::default();
-        let result = slugify("`rm -rf /`.txt", &opts);
-        assert!(!result.contains('`'));
-        assert_eq!(result, "rm-rf.txt");
+    fn test_normalise_whitespace_feeds_custom_transliteration() {
+        let map = BTreeMap::from([(' ', " and ".to_string())]);
+        let opts = SlugifyOptions { custom_transliteration: Some(map), ..Default::default() };
+        assert_eq!(slugify_string("salt\u{A0}\u{2009}pepper", &opts), "salt-and-pepper");
+
+        let off = SlugifyOptions { normalise_whitespace: false, ..opts };
+        assert_eq!(slugify_string("salt\u{A0}pepper", &off), "salt-pepper");
     }
 
     #[test]
-    fn test_slugify_pipe_redirect() {
-        let opts = SlugifyOptions::default();
-        let result = slugify("file|name>output.txt", &opts);
-        assert!(!result.contains('|'));
-        assert!(!result.contains('>'));
-        assert_eq!(result, "file-name-output.txt");
+    fn test_explain_records_whitespace_normalisation() {
+        let steps = explain_slug("My\u{3000}File.txt", &SlugifyOptions::default());
+        assert_eq!(steps[1].stage, "normalise whitespace");
+        assert_eq!(steps[1].value, "My File");
+        let steps = explain_slug("My File.txt", &SlugifyOptions::default());
+        assert_eq!(steps[1].stage, "transliterate");
     }
 
     #[test]
-    fn test_slugify_newline_in_name() {
+    fn test_sentence_case() {
         let opts = SlugifyOptions::default();
-        let result = slugify("file\nname.txt", &opts);
-        assert!(!result.contains('\n'));
-        assert_eq!(result, "file-name.txt");
+        assert_eq!(slugify_string("MY GREAT BLOG POST", &opts), "my-great-blog-post");
+
+        let sentence = SlugifyOptions { sentence_case: true, ..Default::default() };
+        assert_eq!(slugify_string("MY GREAT BLOG POST", &sentence), "My-great-blog-post");
+        assert_eq!(slugify("MY GREAT BLOG POST.TXT", &sentence), "My-great-blog-post.TXT");
+        assert_eq!(slugify_string("éclair recipe", &sentence), "Eclair-recipe");
+
+        let snake = SlugifyOptions { style: Style::Snake, ..sentence.clone() };
+        assert_eq!(slugify_string("MY GREAT BLOG POST", &snake), "My_great_blog_post");
+
+        let pascal = SlugifyOptions { style: Style::Pascal, ..sentence };
+        assert_eq!(slugify_string("MY GREAT BLOG POST", &pascal), "MyGreatBlogPost");
     }
 
     #[test]
-    fn test_slugify_emoji() {
+    fn test_slugify_sentence() {
         let opts = SlugifyOptions::default();
-        let result = slugify("🎉.txt", &opts);
-        // any_ascii transliterates emoji — result should be ASCII and not empty
-        assert!(result.ends_with(".txt"));
-        assert!(result.is_ascii());
+        assert_eq!(slugify_sentence("MY GREAT BLOG POST", &opts), "My-great-blog-post");
+        assert!(matches!(slugify_sentence("Already-a-sentence", &opts), Cow::Borrowed(_)));
+        assert_eq!(slugify_sentence("", &opts), "");
+        assert_eq!(slugify_sentence("!!!", &opts), "");
+        // Idempotent, so a sentence-case slug passes its own check
+        let slug = slugify_sentence("v1.2.3 release notes", &opts);
+        assert_eq!(slugify_sentence(&slug, &opts), slug);
     }
 
     #[test]
-    fn test_slugify_cjk() {
-        let opts = SlugifyOptions::default();
-        let result = slugify("你好世界.txt", &opts);
-        // any_ascii transliterates CJK to pinyin-like ASCII
-        assert!(result.ends_with(".txt"));
-        assert!(result.is_ascii());
-        assert!(result.len() > ".txt".len(), "CJK should transliterate to something");
+    fn test_split_extension_lossy() {
+        assert_eq!(split_extension_lossy("re\u{FFFD}sum\u{FFFD}.pdf"), ("re\u{FFFD}sum\u{FFFD}", ".pdf"));
+        assert_eq!(split_extension_lossy("file.t\u{FFFD}t"), ("file.t\u{FFFD}t", ""));
+        assert_eq!(split_extension_lossy("archive.tar.\u{FFFD}"), ("archive.tar.\u{FFFD}", ""));
+        assert_eq!(split_extension_lossy(".\u{FFFD}rc"), (".\u{FFFD}rc", ""));
+        // Without replacement characters it matches split_extension
+        for name in ["report.pdf", "archive.tar.gz", ".gitignore", "Makefile"] {
+            assert_eq!(split_extension_lossy(name), split_extension(name), "{name}");
+        }
     }
 
     #[test]
-    fn test_slugify_rtl_arabic() {
+    fn test_slugify_replacement_chars_in_extension() {
         let opts = SlugifyOptions::default();
-        let result = slugify("مرحبا.txt", &opts);
-        assert!(result.ends_with(".txt"));
-        assert!(result.is_ascii());
-        assert!(result.len() > ".txt".len(), "Arabic should transliterate to something");
+        assert_eq!(slugify("re\u{FFFD}sum\u{FFFD}.pdf", &opts), "re-sum.pdf");
+        assert_eq!(slugify("file.t\u{FFFD}t", &opts), "file-t-t");
+        let custom = SlugifyOptions::default()
+            .with_extensions(ExtensionConfig { split_options: Some(SplitExtensionOptions::default()), ..Default::default() });
+        assert_eq!(slugify("file.t\u{FFFD}t", &custom), "file-t-t");
     }
 
+    #[cfg(unix)]
     #[test]
-    fn test_slugify_combining_char() {
-        let opts = SlugifyOptions::default();
-        // Decomposed é (e + combining acute accent) vs precomposed é
-        let decomposed = slugify("caf\u{0065}\u{0301}.txt", &opts);
-        let precomposed = slugify("caf\u{00e9}.txt", &opts);
-        assert_eq!(decomposed, precomposed);
+    fn test_split_extension_os_and_slugify_os_invalid_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"caf\xe9 menu.txt");
+        let (base, ext) = split_extension_os(name);
+        assert_eq!(base, OsStr::new("caf\u{FFFD} menu"));
+        assert_eq!(ext, OsStr::new(".txt"));
+        assert_eq!(slugify_os(name, &SlugifyOptions::default()), "caf-menu.txt");
+
+        let name = OsStr::from_bytes(b"notes.m\xff");
+        assert_eq!(split_extension_os(name).1, OsString::new());
+        assert_eq!(slugify_os(name, &SlugifyOptions::default()), "notes-m");
     }
 
     #[test]
-    fn test_slugify_zero_width_chars() {
-        let opts = SlugifyOptions::default();
-        // Zero-width space (U+200B) should be stripped
-        let result = slugify("hello\u{200B}world.txt", &opts);
-        assert!(!result.contains('\u{200B}'));
-        assert_eq!(result, "helloworld.txt");
+    fn test_validate_defaults_and_presets() {
+        for opts in [
+            SlugifyOptions::default(),
+            SlugifyOptions::preset_url(),
+            SlugifyOptions::preset_identifier(),
+            SlugifyOptions::preset_filesystem(),
+            SlugifyOptions::preset_tag(),
+        ] {
+            assert_eq!(opts.validate(), Ok(()));
+        }
     }
 
     #[test]
-    fn test_slugify_very_long_name() {
-        let opts = SlugifyOptions::default();
-        let long_name = "a".repeat(255) + ".txt";
-        let result = slugify(&long_name, &opts);
-        assert!(result.len() <= 255, "result is {} bytes", result.len());
-        assert!(result.ends_with(".txt"));
-        assert!(!result.is_empty());
+    fn test_validate_zero_max_length() {
+        let opts = SlugifyOptions { max_length: Some(0), ..Default::default() };
+        assert_eq!(opts.validate().unwrap_err().problems, ["max_length must be at least 1"]);
     }
 
     #[test]
-    fn test_slugify_already_numbered() {
-        let opts = SlugifyOptions::default();
-        assert_eq!(slugify("file-2.txt", &opts), "file-2.txt");
+    fn test_validate_bad_stop_words() {
+        let opts = SlugifyOptions { stop_words: vec![String::new(), "of the".to_string(), "a".to_string()], ..Default::default() };
+        assert_eq!(opts.validate().unwrap_err().problems.len(), 2);
     }
 
     #[test]
-    fn test_slugify_compound_ext_mixed_case() {
-        let opts = SlugifyOptions::default();
-        let result = slugify("Archive.TAR.GZ", &opts);
-        assert_eq!(result, "archive.TAR.GZ");
+    fn test_validate_compound_extension_without_dot() {
+        let split = SplitExtensionOptions { compound_extensions: vec!["tar.gz".to_string(), ".".to_string(), String::new()] };
+        let opts = SlugifyOptions::default().with_extensions(ExtensionConfig { split_options: Some(split), ..Default::default() });
+        let err = opts.validate().unwrap_err();
+        assert_eq!(err.problems, ["compound extension \"tar.gz\" must start with a dot", "compound extension \".\" must start with a dot"]);
     }
 
-    // --- version dot preservation tests ---
+    #[test]
+    fn test_validate_control_char_transliteration() {
+        let opts = SlugifyOptions::with_transliteration_override(&[('a', "\x01"), ('b', "ok")]);
+        assert_eq!(opts.validate().unwrap_err().problems, ["transliteration of 'a' contains control characters"]);
+    }
 
+    #[cfg(feature = "locale")]
     #[test]
-    fn test_preserve_version_dots_simple() {
-        assert_eq!(preserve_version_dots("foo-0.8.34-bar"), "foo-0\x018\x0134-bar");
+    fn test_validate_empty_locale() {
+        let opts = SlugifyOptions { locale: Some(String::new()), ..Default::default() };
+        assert_eq!(opts.validate().unwrap_err().problems, ["locale must not be empty"]);
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let opts = SlugifyOptions { max_length: Some(0), stop_words: vec![String::new()], ..Default::default() };
+        let err = opts.validate().unwrap_err();
+        assert_eq!(err.problems.len(), 2);
+        assert!(err.to_string().starts_with("invalid slugify options: max_length"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "max_length must be at least 1")]
+    fn test_slugify_debug_asserts_valid_options() {
+        let opts = SlugifyOptions { max_length: Some(0), ..Default::default() };
+        let _ = slugify("file.txt", &opts);
+    }
+
+    #[test]
+    fn test_preserve_date_dots_iso() {
+        assert_eq!(preserve_date_dots("Report 2024.03.15"), "Report 2024\x0103\x0115");
     }
 
     #[test]
-    fn test_preserve_version_dots_semver() {
-        assert_eq!(preserve_version_dots("app-1.2.3"), "app-1\x012\x013");
+    fn test_preserve_date_dots_requires_exact_widths() {
+        assert_eq!(preserve_date_dots("2024.3.15"), "2024.3.15");
+        assert_eq!(preserve_date_dots("12024.03.15"), "12024.03.15");
+        assert_eq!(preserve_date_dots("2024.03.155"), "2024.03.155");
     }
 
     #[test]
-    fn test_preserve_version_dots_two_part() {
-        assert_eq!(preserve_version_dots("app-7.20"), "app-7\x0120");
+    fn test_preserve_date_dots_multibyte_neighbors() {
+        assert_eq!(preserve_date_dots("é2024.03.15é"), "é2024\x0103\x0115é");
     }
 
     #[test]
-    fn test_preserve_version_dots_no_version() {
-        assert_eq!(preserve_version_dots("hello-world"), "hello-world");
+    fn test_slugify_preserve_date() {
+        let without = SlugifyOptions { preserve_version_numbers: false, ..Default::default() };
+        let opts = SlugifyOptions { preserve_date: true, ..without.clone() };
+        assert_eq!(slugify("Report 2024.03.15.pdf", &without), "report-2024-03-15.pdf");
+        assert_eq!(slugify("Report 2024.03.15.pdf", &opts), "report-2024.03.15.pdf");
+        assert_eq!(slugify("Report_2024-03-15.pdf", &opts), "report-2024-03-15.pdf");
     }
 
     #[test]
-    fn test_preserve_version_dots_letters_not_matched() {
-        assert_eq!(preserve_version_dots("a.b.c"), "a.b.c");
+    fn test_slugify_preserve_date_pascal_keeps_numbers_apart() {
+        let opts = SlugifyOptions {
+            style: Style::Pascal,
+            preserve_date: true,
+            preserve_version_numbers: false,
+            ..Default::default()
+        };
+        assert_eq!(slugify("1192.21.34-2.0", &opts), "1192.21.34-2.0");
+        assert_eq!(slugify("5-8955.45.07.2", &opts), "5-8955.45.07.2");
+        assert_eq!(slugify("Report 2024.03.15 Final.pdf", &opts), "Report2024.03.15Final.pdf");
     }
 
     #[test]
-    fn test_preserve_version_dots_not_followed_by_digit() {
-        assert_eq!(preserve_version_dots("7.txt"), "7.txt");
+    fn test_preserve_version_range() {
+        assert_eq!(preserve_version_range("Schedule 2.10-2.12.26"), "Schedule 2.10\x022.12.26");
+        assert_eq!(preserve_version_range("1.0-2.0-3.0"), "1.0\x022.0\x023.0");
+        assert_eq!(preserve_version_range("v1.2-v1.4"), "v1.2-v1.4");
+        // Both sides must be versions
+        assert_eq!(preserve_version_range("1.2-3"), "1.2-3");
+        assert_eq!(preserve_version_range("1-2.3"), "1-2.3");
+        assert_eq!(preserve_version_range("app-1.2.3-beta"), "app-1.2.3-beta");
+        assert_eq!(preserve_version_range("1.2.-3.4"), "1.2.-3.4");
+        assert_eq!(preserve_version_range("é1.2-3.4é"), "é1.2\x023.4é");
     }
 
     #[test]
-    fn test_preserve_version_dots_adjacent_to_letters() {
-        assert_eq!(preserve_version_dots("istatmenus7.20"), "istatmenus7\x0120");
+    fn test_slugify_version_range() {
+        let name = "Launch Schedule 2.10-2.12.26.png";
+        for (style, expected) in [
+            (Style::Kebab, "launch-schedule-2.10-2.12.26.png"),
+            (Style::Snake, "launch_schedule_2.10-2.12.26.png"),
+            (Style::Pascal, "LaunchSchedule2.10-2.12.26.png"),
+        ] {
+            let opts = SlugifyOptions { style, ..Default::default() };
+            assert_eq!(slugify(name, &opts), expected, "{style:?}");
+        }
+        let opts = SlugifyOptions::default();
+        assert_eq!(slugify_string("Supported: 1.0 - 2.0", &opts), "supported-1.0-2.0");
+        assert_eq!(slugify_string("Range 1.0-2.0-3.0", &opts), "range-1.0-2.0-3.0");
+        assert_eq!(slugify_string("build 1.2-3", &opts), "build-1.2-3");
+
+        let no_versions = SlugifyOptions { preserve_version_numbers: false, style: Style::Snake, ..Default::default() };
+        assert_eq!(slugify(name, &no_versions), "launch_schedule_2_10_2_12_26.png");
     }
 
     #[test]
-    fn test_preserve_version_dots_multiple_versions() {
-        assert_eq!(preserve_version_dots("2.10-2.12.26"), "2\x0110-2\x0112\x0126");
+    fn test_restore_version_range_dash() {
+        assert_eq!(restore_version_dots("2\x0110\x022\x0112"), "2.10-2.12");
     }
 
     #[test]
@@ -837,4 +5408,688 @@ mod tests {
         assert_eq!(slugify_string(".gitignore", &opts), "gitignore");
         assert_eq!(slugify_string(".env.local", &opts), "env-local");
     }
+
+    // --- slugify_unique / slugify_batch tests ---
+
+    #[test]
+    fn test_slugify_unique_no_collision() {
+        let opts = SlugifyOptions::default();
+        let mut seen = HashSet::new();
+        assert_eq!(slugify_unique("My File.txt", &opts, &mut seen), "my-file.txt");
+        assert!(seen.contains("my-file.txt"));
+    }
+
+    #[test]
+    fn test_slugify_unique_appends_suffix() {
+        let opts = SlugifyOptions::default();
+        let mut seen = HashSet::new();
+        assert_eq!(slugify_unique("My File.txt", &opts, &mut seen), "my-file.txt");
+        assert_eq!(slugify_unique("My_File.txt", &opts, &mut seen), "my-file-2.txt");
+        assert_eq!(slugify_unique("my  file.txt", &opts, &mut seen), "my-file-3.txt");
+    }
+
+    #[test]
+    fn test_slugify_unique_skips_taken_suffix() {
+        let opts = SlugifyOptions::default();
+        let mut seen: HashSet<String> = ["my-file.txt", "my-file-2.txt"].iter().map(ToString::to_string).collect();
+        assert_eq!(slugify_unique("My File.txt", &opts, &mut seen), "my-file-3.txt");
+    }
+
+    #[test]
+    fn test_slugify_unique_compound_extension() {
+        let opts = SlugifyOptions::default();
+        let mut seen = HashSet::new();
+        let _ = slugify_unique("Backup.tar.gz", &opts, &mut seen);
+        assert_eq!(slugify_unique("backup.tar.gz", &opts, &mut seen), "backup-2.tar.gz");
+    }
+
+    #[test]
+    fn test_slugify_unique_dotfile() {
+        let opts = SlugifyOptions::default();
+        let mut seen = HashSet::new();
+        let _ = slugify_unique(".env", &opts, &mut seen);
+        assert_eq!(slugify_unique(".env", &opts, &mut seen), ".env-2");
+    }
+
+    #[test]
+    fn test_slugify_unique_empty_not_recorded() {
+        let opts = SlugifyOptions::default();
+        let mut seen = HashSet::new();
+        assert_eq!(slugify_unique("@@@", &opts, &mut seen), "");
+        assert!(seen.is_empty());
+    }
+
+    #[test]
+    fn test_split_at_last_separator() {
+        let split = |slug, style| split_at_last_separator(slug, &style);
+        assert_eq!(split("my-long-document-name", Style::Kebab), Some(("my-long-document", "name")));
+        assert_eq!(split("my_long_name", Style::Snake), Some(("my_long", "name")));
+        assert_eq!(split("MyLongName", Style::Pascal), Some(("MyLong", "Name")));
+        assert_eq!(split("my-file.txt", Style::Kebab), Some(("my", "file.txt")));
+        // The other style's separator is part of a word
+        assert_eq!(split("my_long-name", Style::Snake), Some(("my", "long-name")));
+        // Single words, including ones with separators only at the edges
+        assert_eq!(split("document", Style::Kebab), None);
+        assert_eq!(split("-document-", Style::Kebab), None);
+        assert_eq!(split("Document", Style::Pascal), None);
+        assert_eq!(split("", Style::Snake), None);
+    }
+
+    #[test]
+    fn test_split_at_last_separator_versions() {
+        let split = |slug, style| split_at_last_separator(slug, &style);
+        assert_eq!(split("monarch-0.8.34", Style::Kebab), Some(("monarch", "0.8.34")));
+        assert_eq!(split("launch-schedule-2.10-2.12.26", Style::Kebab), Some(("launch-schedule", "2.10-2.12.26")));
+        assert_eq!(split("launch_schedule_2.10-2.12.26", Style::Snake), Some(("launch_schedule", "2.10-2.12.26")));
+        assert_eq!(split("LaunchSchedule2.10-2.12.26", Style::Pascal), Some(("Launch", "Schedule2.10-2.12.26")));
+        assert_eq!(split("1.0-2.0-3.0", Style::Kebab), None);
+        // A hyphen between a version and a plain number is still a separator
+        assert_eq!(split("photo-2024-01", Style::Kebab), Some(("photo-2024", "01")));
+        assert_eq!(split("v1.2-beta", Style::Kebab), Some(("v1.2", "beta")));
+    }
+
+    #[test]
+    fn test_slug_word_count() {
+        assert_eq!(slug_word_count("my-long-document-name", &Style::Kebab), 4);
+        assert_eq!(slug_word_count("launch-schedule-2.10-2.12.26", &Style::Kebab), 3);
+        assert_eq!(slug_word_count("my_file_v1.2.3", &Style::Snake), 3);
+        assert_eq!(slug_word_count("MyCoolFile.txt", &Style::Pascal), 3);
+        assert_eq!(slug_word_count("img20240301", &Style::Pascal), 1);
+        assert_eq!(slug_word_count("a--b", &Style::Kebab), 2);
+        assert_eq!(slug_word_count("-a-", &Style::Kebab), 1);
+        assert_eq!(slug_word_count("word", &Style::Kebab), 1);
+        assert_eq!(slug_word_count("", &Style::Kebab), 0);
+    }
+
+    #[test]
+    fn test_slug_length_estimate_never_underestimates() {
+        let long_dotfile = format!(".{}", "x".repeat(300));
+        let long_ext = format!("Name.{}", "é".repeat(200));
+        let long_words = "Word ".repeat(100);
+        let corpus = [
+            "",
+            "my-file.txt",
+            "My Résumé (Final).pdf",
+            "Report (2) (copy).pdf",
+            "Screenshot 2024-03-01 at 2.15.32\u{202f}PM.png",
+            "你好世界.txt",
+            "深圳 トヨタ Борис.md",
+            "👑 Crown & Co @ Home.png",
+            "İstanbul ßtraße KELVIN \u{212A}.txt",
+            "ﷺ ﷽.txt",
+            ".gitignore",
+            ".env.local",
+            "con.txt",
+            "Launch Schedule 2.10-2.12.26.png",
+            "app v1.2.3 release.tar.gz",
+            "aBcDeFgHiJkL.txt",
+            "a\0b\tc\u{202E}gpj.exe",
+            long_dotfile.as_str(),
+            long_ext.as_str(),
+            long_words.as_str(),
+        ];
+        let custom = BTreeMap::from([('&', "and".to_string()), ('@', "at".to_string()), ('k', "kk".to_string()), ('ø', "ö".to_string())]);
+        let variants = vec![
+            SlugifyOptions::default(),
+            SlugifyOptions { keep_unicode: true, ..Default::default() },
+            SlugifyOptions { style: Style::Pascal, sentence_case: true, ..Default::default() },
+            SlugifyOptions { style: Style::Snake, keep_unicode: true, sentence_case: true, ..Default::default() },
+            SlugifyOptions { max_length: Some(10), cross_platform: true, ..Default::default() },
+            SlugifyOptions { custom_transliteration: Some(custom), ..Default::default() },
+            SlugifyOptions::preset_cross_platform(),
+            SlugifyOptions::preset_url(),
+            SlugifyOptions { smart_case: true, ..Default::default() },
+        ];
+        #[cfg(feature = "locale")]
+        let variants = [variants, vec![SlugifyOptions { locale: Some("de".to_string()), ..Default::default() }]].concat();
+        for opts in &variants {
+            for name in corpus {
+                let actual = slugify(name, opts).len();
+                let estimate = slug_length_estimate(name, opts);
+                assert!(estimate >= actual, "{name:?}: estimate {estimate} < actual {actual} with {opts:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_slug_length_estimate_is_close() {
+        let opts = SlugifyOptions::default();
+        assert_eq!(slug_length_estimate("my-file.txt", &opts), 11);
+        assert_eq!(slug_length_estimate("", &opts), 0);
+        // Each 3-byte CJK character may become 4 ASCII bytes, but a name this long
+        // is truncated (or at most returned as is)
+        assert_eq!(slug_length_estimate(&"深".repeat(100), &opts), 300);
+        let short = SlugifyOptions { max_length: Some(20), ..Default::default() };
+        assert_eq!(slug_length_estimate("深圳", &short), 8);
+    }
+
+    #[test]
+    fn test_slugify_batch() {
+        let opts = SlugifyOptions::default();
+        let pairs = slugify_batch(&["My File.txt", "My_File.txt", "Other.txt"], &opts);
+        let slugs: Vec<&str> = pairs.iter().map(|(_, slug)| slug.as_str()).collect();
+        assert_eq!(slugs, vec!["my-file.txt", "my-file-2.txt", "other.txt"]);
+        assert_eq!(pairs[1].0, "My_File.txt");
+    }
+
+    // --- preset and word filtering tests ---
+
+    #[test]
+    fn test_preset_url_drops_articles() {
+        let opts = SlugifyOptions::preset_url();
+        assert_eq!(slugify_string("A Tale of the Two Cities", &opts), "tale-of-two-cities");
+    }
+
+    #[test]
+    fn test_preset_url_max_length() {
+        let opts = SlugifyOptions::preset_url();
+        let result = slugify_string(&"word ".repeat(50), &opts);
+        assert!(result.len() <= 100, "result is {} bytes", result.len());
+        assert!(!result.ends_with('-'));
+    }
+
+    #[test]
+    fn test_preset_identifier() {
+        let opts = SlugifyOptions::preset_identifier();
+        assert_eq!(slugify_string("Hello World!", &opts), "hello_world");
+        assert!(slugify_string(&"x ".repeat(100), &opts).len() <= 64);
+    }
+
+    #[test]
+    fn test_preset_filesystem_is_default() {
+        assert_eq!(SlugifyOptions::preset_filesystem(), SlugifyOptions::default());
+    }
+
+    #[test]
+    fn test_preset_tag() {
+        let opts = SlugifyOptions::preset_tag();
+        assert_eq!(slugify_string("a b Rust Programming", &opts), "rust-programming");
+        assert!(slugify_string(&"tag ".repeat(20), &opts).len() <= 30);
+    }
+
+    #[test]
+    fn test_stop_words_case_insensitive() {
+        let opts = SlugifyOptions { stop_words: vec!["THE".to_string()], ..Default::default() };
+        assert_eq!(slugify_string("The End of the Road", &opts), "end-of-road");
+    }
+
+    #[test]
+    fn test_builtin_stop_words() {
+        let cases = [
+            (StopWords::English, "The Cat and the Hat", "cat-hat"),
+            (StopWords::French, "Le Petit Prince et la Rose", "petit-prince-rose"),
+            (StopWords::German, "Die Welt und der Mond", "welt-mond"),
+            (StopWords::Spanish, "Cien Años de Soledad", "cien-anos-soledad"),
+        ];
+        for (list, input, expected) in cases {
+            let opts = SlugifyOptions { stop_words: list.to_vec(), ..Default::default() };
+            assert_eq!(opts.validate(), Ok(()), "{list:?}");
+            assert_eq!(slugify_string(input, &opts), expected, "{list:?}");
+        }
+        assert_eq!(SlugifyOptions::with_english_stop_words().stop_words, StopWords::English.to_vec());
+        assert_eq!(FRENCH_STOP_WORDS.len(), 20);
+        assert_eq!(GERMAN_STOP_WORDS.len(), 20);
+        assert_eq!(SPANISH_STOP_WORDS.len(), 20);
+    }
+
+    #[test]
+    fn test_stop_words_all_removed_falls_back() {
+        let opts = SlugifyOptions::preset_url();
+        assert_eq!(slugify_string("The A", &opts), "the-a");
+    }
+
+    #[test]
+    fn test_min_word_length_filename() {
+        let opts = SlugifyOptions { min_word_length: 3, ..Default::default() };
+        assert_eq!(slugify("My Big Report.pdf", &opts), "big-report.pdf");
+    }
+
+    #[test]
+    fn test_max_length_filename_keeps_extension() {
+        let opts = SlugifyOptions { max_length: Some(20), ..Default::default() };
+        let result = slugify("Quarterly Financial Report.pdf", &opts);
+        assert!(result.len() <= 20, "result is {} bytes", result.len());
+        assert_eq!(result, "quarterly.pdf");
+    }
+
+    #[test]
+    fn test_max_length_capped_at_filesystem_limit() {
+        let opts = SlugifyOptions { max_length: Some(10_000), ..Default::default() };
+        let long_name = "a".repeat(300) + ".txt";
+        let result = slugify(&long_name, &opts);
+        assert!(result.len() <= 255, "result is {} bytes", result.len());
+    }
+
+    #[test]
+    #[cfg(feature = "locale")]
+    fn test_slugify_with_locale() {
+        let opts = SlugifyOptions { locale: Some("de".to_string()), ..Default::default() };
+        assert_eq!(slugify("Grüße aus Köln.txt", &opts), "gruesse-aus-koeln.txt");
+        let opts = SlugifyOptions { locale: Some("de".to_string()), keep_unicode: true, ..Default::default() };
+        assert_eq!(slugify("Grüße.txt", &opts), "grüße.txt");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-script")]
+    fn test_slugify_script_policies() {
+        use unicode_script::Script;
+
+        let policies = [
+            (UnicodeScript(Script::Cyrillic), ScriptPolicy::Transliterate),
+            (UnicodeScript(Script::Han), ScriptPolicy::Remove),
+            (UnicodeScript(Script::Latin), ScriptPolicy::Keep),
+        ];
+        let opts = SlugifyOptions { script_policies: Some(policies.into_iter().collect()), ..Default::default() };
+        assert_eq!(slugify("Привет 東京 Café.txt", &opts), "privet-café.txt");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-script")]
+    fn test_slugify_script_policies_keep_arabic() {
+        use unicode_script::Script;
+
+        let policies = [(UnicodeScript(Script::Arabic), ScriptPolicy::Keep)];
+        let opts = SlugifyOptions { script_policies: Some(policies.into_iter().collect()), ..Default::default() };
+        assert_eq!(slugify_string("مرحبا Привет", &opts), "مرحبا-privet");
+    }
+
+    // --- custom transliteration tests ---
+
+    #[test]
+    fn test_custom_transliteration_override() {
+        let opts = SlugifyOptions::with_transliteration_override(&[('ä', "ae")]);
+        assert_eq!(slugify("Hämisch Äpfel.txt", &opts), "haemisch-aepfel.txt");
+        assert_eq!(slugify("Hämisch Äpfel.txt", &SlugifyOptions::default()), "hamisch-apfel.txt");
+    }
+
+    #[test]
+    fn test_custom_transliteration_exact_case_wins() {
+        let opts = SlugifyOptions::with_transliteration_override(&[('ä', "ae"), ('Ä', "Ä")]);
+        assert_eq!(slugify("Ääh.txt", &opts), "aaeh.txt");
+    }
+
+    #[test]
+    fn test_custom_transliteration_with_keep_unicode() {
+        let mut opts = SlugifyOptions::with_transliteration_override(&[('&', "and")]);
+        opts.keep_unicode = true;
+        assert_eq!(slugify_string("Café & Bar", &opts), "café-and-bar");
+    }
+
+    #[test]
+    fn test_custom_transliteration_pascal() {
+        let mut opts = SlugifyOptions::with_transliteration_override(&[('ö', "oe")]);
+        opts.style = Style::Pascal;
+        assert_eq!(slugify("Öl Preis.txt", &opts), "OelPreis.txt");
+    }
+
+    // --- explain_slug tests ---
+
+    #[test]
+    fn test_explain_slug_stage_names() {
+        let steps = explain_slug("My File (v1.2).txt", &SlugifyOptions::default());
+        let stages: Vec<&str> = steps.iter().map(|s| s.stage.as_str()).collect();
+        assert_eq!(
+            stages,
+            vec![
+                "split extension",
+                "transliterate",
+                "strip brackets",
+                "preserve version dots",
+                "collect words",
+                "filter words",
+                "join",
+                "restore version dots",
+                "truncate",
+                "reattach extension",
+            ]
+        );
+        assert_eq!(steps.last().unwrap().value, "my-file-v1.2.txt");
+    }
+
+    #[test]
+    fn test_explain_slug_matches_slugify() {
+        let opts = SlugifyOptions { style: Style::Snake, ..Default::default() };
+        for name in ["Café Résumé.pdf", "archive.tar.gz", "NEW FOLDER (2)"] {
+            let steps = explain_slug(name, &opts);
+            assert_eq!(steps.last().unwrap().value, slugify(name, &opts), "input: {name}");
+        }
+    }
+
+    #[test]
+    fn test_explain_slug_dotfile_stops_early() {
+        let steps = explain_slug(".gitignore", &SlugifyOptions::default());
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].stage, "split extension");
+    }
+
+    #[test]
+    fn test_explain_slug_empty() {
+        assert!(explain_slug("", &SlugifyOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_format_slug_trace_escapes_placeholder() {
+        let steps = explain_slug("app-1.2.dmg", &SlugifyOptions::default());
+        let formatted = format_slug_trace(&steps);
+        assert_eq!(formatted.lines().count(), steps.len());
+        assert!(!formatted.contains(VERSION_DOT));
+        assert!(formatted.contains("preserve version dots  app-1\\u{1}2"));
+    }
+
+    // --- slug_diff tests ---
+
+    fn retained(s: &str) -> SlugDiffOp {
+        SlugDiffOp::Retained(s.to_string())
+    }
+
+    fn added(s: &str) -> SlugDiffOp {
+        SlugDiffOp::Added(s.to_string())
+    }
+
+    fn removed(s: &str) -> SlugDiffOp {
+        SlugDiffOp::Removed(s.to_string())
+    }
+
+    #[test]
+    fn test_slug_diff_unchanged() {
+        assert_eq!(slug_diff("clean.txt", "clean.txt"), vec![retained("clean.txt")]);
+        assert!(slug_diff("", "").is_empty());
+    }
+
+    #[test]
+    fn test_slug_diff_lowercase_and_separator() {
+        assert_eq!(
+            slug_diff("My File.txt", "my-file.txt"),
+            vec![removed("M"), added("m"), retained("y"), removed(" F"), added("-f"), retained("ile.txt")]
+        );
+    }
+
+    #[test]
+    fn test_slug_diff_brackets_removed() {
+        assert_eq!(
+            slug_diff("report (final).pdf", "report-final.pdf"),
+            vec![retained("report"), removed(" ("), added("-"), retained("final"), removed(")"), retained(".pdf")]
+        );
+    }
+
+    #[test]
+    fn test_slug_diff_transliteration() {
+        assert_eq!(slug_diff("café.txt", "cafe.txt"), vec![retained("caf"), removed("é"), added("e"), retained(".txt")]);
+    }
+
+    #[test]
+    fn test_slug_diff_reassembles_both_sides() {
+        let opts = SlugifyOptions::default();
+        for name in ["Report (Final) [2024].txt", "你好世界.txt", "file@name#with$symbols.txt", "  x  "] {
+            let slug = slugify(name, &opts);
+            let diff = slug_diff(name, &slug);
+            let before: String = diff
+                .iter()
+                .filter_map(|op| match op {
+                    SlugDiffOp::Retained(s) | SlugDiffOp::Removed(s) => Some(s.as_str()),
+                    SlugDiffOp::Added(_) => None,
+                })
+                .collect();
+            let after: String = diff
+                .iter()
+                .filter_map(|op| match op {
+                    SlugDiffOp::Retained(s) | SlugDiffOp::Added(s) => Some(s.as_str()),
+                    SlugDiffOp::Removed(_) => None,
+                })
+                .collect();
+            assert_eq!(before, name);
+            assert_eq!(after, slug);
+        }
+    }
+
+    #[test]
+    fn test_format_slug_diff_escapes_controls() {
+        assert_eq!(format_slug_diff(&slug_diff("a\tb", "a-b")), "a[-\\t-]{+-+}b");
+    }
+
+    // --- git branch tests ---
+
+    #[test]
+    fn test_slugify_git_branch_request_example() {
+        let opts = SlugifyOptions::preset_git_branch();
+        assert_eq!(slugify_git_branch("feature: My New Feature (WIP).lock", &opts), "feature-my-new-feature-wip");
+    }
+
+    #[test]
+    fn test_slugify_git_branch_rules() {
+        let opts = SlugifyOptions::preset_git_branch();
+        assert_eq!(slugify_git_branch("a..b", &opts), "a-b");
+        assert_eq!(slugify_git_branch(".hidden.", &opts), "hidden");
+        assert_eq!(slugify_git_branch("-leading-", &opts), "leading");
+        assert_eq!(slugify_git_branch("x.lock.lock", &opts), "x-lock");
+        assert_eq!(slugify_git_branch("what? ~really^ [yes]*", &opts), "what-really-yes");
+        assert_eq!(slugify_git_branch("feature/login", &opts), "feature-login");
+        assert_eq!(slugify_git_branch("@{}", &opts), "");
+    }
+
+    #[test]
+    fn test_slugify_git_branch_keep_unicode_whitespace() {
+        let opts = SlugifyOptions { keep_unicode: true, ..SlugifyOptions::preset_git_branch() };
+        let slug = slugify_git_branch("café\u{3000}crème", &opts);
+        assert!(!slug.chars().any(char::is_whitespace), "{slug:?}");
+    }
+
+    #[test]
+    fn test_slugify_git_branch_length_limit() {
+        let long = "word ".repeat(100);
+        for opts in [SlugifyOptions::preset_git_branch(), SlugifyOptions::default()] {
+            let slug = slugify_git_branch(&long, &opts);
+            assert!(slug.len() <= 255, "{} bytes", slug.len());
+            assert!(!slug.ends_with('-'));
+        }
+        let path = slugify_git_branch_path(&format!("team/{long}/{long}"), &SlugifyOptions::default());
+        assert!(path.len() <= 255, "{} bytes", path.len());
+        assert!(path.starts_with("team/word-"));
+    }
+
+    #[test]
+    fn test_slugify_git_branch_path() {
+        let opts = SlugifyOptions::preset_git_branch();
+        assert_eq!(slugify_git_branch_path("Users/Jane Doe/Fix Bug.lock", &opts), "users/jane-doe/fix-bug");
+        assert_eq!(slugify_git_branch_path("a/../b", &opts), "a/b");
+        assert_eq!(slugify_git_branch_path("///", &opts), "");
+    }
+
+    #[test]
+    fn test_preset_git_branch_is_valid() {
+        assert!(SlugifyOptions::preset_git_branch().validate().is_ok());
+    }
+
+    // --- docker tag tests ---
+
+    #[test]
+    fn test_slugify_for_docker_tag_filename() {
+        let opts = SlugifyOptions::preset_docker_tag();
+        assert_eq!(slugify_for_docker_tag("My App (latest).txt", &opts).unwrap(), "my-app-latest");
+        assert_eq!(slugify_for_docker_tag("backup.tar.gz", &opts).unwrap(), "backup");
+    }
+
+    #[test]
+    fn test_slugify_for_docker_tag_leading_dots_and_dashes() {
+        let opts = SlugifyOptions::preset_docker_tag();
+        assert_eq!(slugify_for_docker_tag("...hidden", &opts).unwrap(), "hidden");
+        assert_eq!(slugify_for_docker_tag(".env", &opts).unwrap(), "env");
+        assert_eq!(slugify_for_docker_tag("--rc--", &opts).unwrap(), "rc");
+    }
+
+    #[test]
+    fn test_slugify_for_docker_tag_prepends_v() {
+        let opts = SlugifyOptions { style: Style::Snake, ..SlugifyOptions::preset_docker_tag() };
+        let tag = slugify_for_docker_tag("ünïcode", &SlugifyOptions { keep_unicode: true, ..opts }).unwrap();
+        assert!(tag.starts_with(|c: char| c.is_ascii_alphanumeric()), "{tag:?}");
+        assert!(tag.chars().all(|c| c.is_ascii_alphanumeric() || "_.-".contains(c)), "{tag:?}");
+    }
+
+    #[test]
+    fn test_slugify_for_docker_tag_versions_kept() {
+        let opts = SlugifyOptions::preset_docker_tag();
+        assert_eq!(slugify_for_docker_tag("v1.2.3", &opts).unwrap(), "v1.2.3");
+        assert_eq!(slugify_for_docker_tag("1.0", &opts).unwrap(), "1.0");
+    }
+
+    #[test]
+    fn test_slugify_for_docker_tag_length() {
+        let long = "segment ".repeat(40);
+        for opts in [SlugifyOptions::preset_docker_tag(), SlugifyOptions::default()] {
+            let tag = slugify_for_docker_tag(&long, &opts).unwrap();
+            assert!(tag.len() <= 128, "{} bytes", tag.len());
+            assert!(!tag.ends_with('-'));
+        }
+    }
+
+    #[test]
+    fn test_slugify_for_docker_tag_empty() {
+        let opts = SlugifyOptions::preset_docker_tag();
+        assert_eq!(slugify_for_docker_tag("", &opts), Err(SlugifyError::EmptyResult));
+        assert_eq!(slugify_for_docker_tag("...", &opts), Err(SlugifyError::EmptyResult));
+        assert_eq!(SlugifyError::EmptyResult.to_string(), "input slugifies to an empty string");
+    }
+
+    // --- python package tests ---
+
+    #[test]
+    fn test_slugify_for_python_package_pep503_vectors() {
+        // The equivalent names listed in PEP 503
+        for name in [
+            "friendly-bard",
+            "Friendly-Bard",
+            "FRIENDLY-BARD",
+            "friendly.bard",
+            "friendly_bard",
+            "friendly--bard",
+            "FrIeNdLy-._.-bArD",
+        ] {
+            assert_eq!(slugify_for_python_package(name), "friendly-bard", "{name}");
+        }
+    }
+
+    #[test]
+    fn test_slugify_for_python_package_cleanup() {
+        assert_eq!(slugify_for_python_package("  My Package (v2)  "), "my-package-v2");
+        assert_eq!(slugify_for_python_package("__init__"), "init");
+        assert_eq!(slugify_for_python_package("Zürich-Utils"), "zurich-utils");
+        assert_eq!(slugify_for_python_package("!!!"), "");
+    }
+
+    #[test]
+    fn test_slugify_for_python_package_output_is_valid() {
+        for name in ["A", "a.b_c-d", "_x_", "Ω mega", "foo..bar"] {
+            let slug = slugify_for_python_package(name);
+            assert!(is_valid_python_package_name(&slug), "{name} -> {slug}");
+            assert_eq!(slugify_for_python_package(&slug), slug, "not idempotent for {name}");
+        }
+    }
+
+    #[test]
+    fn test_is_valid_python_package_name() {
+        for name in ["a", "A1", "friendly-bard", "Friendly_Bard", "zope.interface", "a--b"] {
+            assert!(is_valid_python_package_name(name), "{name}");
+        }
+        for name in ["", "-a", "a-", ".a", "a_", "my package", "café"] {
+            assert!(!is_valid_python_package_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_is_safe_for_all_platforms_clean_names() {
+        for name in ["my-file.txt", ".env", "archive.tar.gz", "app-1.2.3.dmg", "console.log", "con-file"] {
+            let report = is_safe_for_all_platforms(name);
+            assert!(report.linux && report.macos && report.windows && report.fat32, "{name}: {:?}", report.issues);
+        }
+    }
+
+    #[test]
+    fn test_is_safe_for_all_platforms_per_platform() {
+        // (name, linux, macos, windows, fat32)
+        let cases = [
+            ("", false, false, false, false),
+            ("..", false, false, false, false),
+            ("a/b", false, false, false, false),
+            ("a\0b", false, false, false, false),
+            ("a:b", true, false, false, false),
+            ("a?b", true, true, false, false),
+            ("a\\b", true, true, false, false),
+            ("a\tb", true, true, false, false),
+            ("Con", true, true, false, false),
+            ("lpt9.tar.gz", true, true, false, false),
+            ("name.", true, true, false, false),
+            ("name ", true, true, false, false),
+            ("a+b", true, true, true, false),
+            ("a[1]", true, true, true, false),
+            ("café", true, true, true, false),
+        ];
+        for (name, linux, macos, windows, fat32) in cases {
+            let report = is_safe_for_all_platforms(name);
+            assert_eq!(
+                (report.linux, report.macos, report.windows, report.fat32),
+                (linux, macos, windows, fat32),
+                "{name:?}: {:?}",
+                report.issues
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_safe_for_all_platforms_lengths() {
+        let report = is_safe_for_all_platforms(&"a".repeat(256));
+        assert!(!report.linux && !report.macos && report.windows && !report.fat32);
+
+        // 128 two-byte characters: 256 bytes but only 128 UTF-16 units
+        let report = is_safe_for_all_platforms(&"é".repeat(128));
+        assert!(!report.linux && report.macos && report.windows);
+        assert!(report.issues.contains(&FileSafetyIssue::TooLong { platform: Platform::Linux, length: 256, max: 255 }));
+
+        let report = is_safe_for_all_platforms(&"a".repeat(261));
+        assert!(!report.windows);
+    }
+
+    #[test]
+    fn test_is_safe_for_all_platforms_reports_each_char_once() {
+        let report = is_safe_for_all_platforms("a??b?");
+        assert_eq!(report.issues, [FileSafetyIssue::ForbiddenChar('?')]);
+        assert_eq!(report.issues[0].to_string(), "contains '?'");
+    }
+
+    #[test]
+    fn test_preset_cross_platform() {
+        let opts = SlugifyOptions::preset_cross_platform();
+        let cases = [
+            ("CON.txt", "con_.txt"),
+            ("nul", "nul_"),
+            ("LPT1.tar.gz", "lpt1_.tar.gz"),
+            ("a.c++", "a.c"),
+            ("name.", "name"),
+            ("x.T[1]", "x.T1"),
+            ("foo.bar+baz", "foo.barbaz"),
+            ("My File.txt", "my-file.txt"),
+            (".env", ".env"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(slugify(input, &opts), expected, "{input}");
+        }
+        assert_eq!(slugify_string("aux", &opts), "aux_");
+        // slugify_stem keeps the extension verbatim
+        assert_eq!(slugify_stem("a.c++", &opts), "a.c++");
+    }
+
+    #[test]
+    fn test_preset_cross_platform_output_is_safe() {
+        let opts = SlugifyOptions::preset_cross_platform();
+        let inputs = ["Café: Menu?.txt", "COM3.log", "über.∂ata", "a<b>c|d", "prn .md", "file.é+", &"x".repeat(300)];
+        for input in inputs {
+            let slug = slugify(input, &opts);
+            assert!(is_safe_for_all_platforms(&slug).is_safe_everywhere(), "{input} -> {slug}");
+        }
+    }
+
+    #[test]
+    fn test_cross_platform_off_by_default() {
+        assert!(!SlugifyOptions::default().cross_platform);
+        assert_eq!(slugify("CON.txt", &SlugifyOptions::default()), "con.txt");
+    }
 }
@@ -0,0 +1,147 @@
+//! Language-specific transliteration.
+//!
+//! `any_ascii` picks one generic romanization per character, which isn't
+//! always what a given language prefers — German writes `ü` as `ue`, not `u`.
+//! [`locale_transliterate`] applies a small per-language table first and hands
+//! everything else to `any_ascii`.
+
+/// Transliterate `s` to ASCII using the conventions of `locale`.
+///
+/// `locale` is a language tag such as `de`, `de-AT`, or `sv_SE`; only the
+/// primary language subtag is used, case-insensitively. Supported languages
+/// are German (`de`), Swedish (`sv`), Turkish (`tr`), and Slovenian (`sl`).
+/// Unknown locales fall back to plain `any_ascii`.
+///
+/// # Examples
+///
+/// ```
+/// use fileslug::locale::locale_transliterate;
+///
+/// assert_eq!(locale_transliterate("Grüße aus München", "de"), "Gruesse aus Muenchen");
+/// assert_eq!(locale_transliterate("Grüße aus München", "fr"), "Grusse aus Munchen");
+/// ```
+#[must_use]
+pub fn locale_transliterate(s: &str, locale: &str) -> String {
+    let lang = locale.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+    let table: fn(char) -> Option<&'static str> = match lang.as_str() {
+        "de" => german,
+        "sv" => swedish,
+        "tr" => turkish,
+        "sl" => slovenian,
+        _ => return any_ascii::any_ascii(s),
+    };
+
+    let mut mapped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match table(c) {
+            Some(replacement) => mapped.push_str(replacement),
+            None => mapped.push(c),
+        }
+    }
+    any_ascii::any_ascii(&mapped)
+}
+
+/// Umlauts expand to a trailing `e`; `ß` becomes `ss`.
+fn german(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ä' => "ae",
+        'ö' => "oe",
+        'ü' => "ue",
+        'Ä' => "Ae",
+        'Ö' => "Oe",
+        'Ü' => "Ue",
+        'ß' => "ss",
+        'ẞ' => "SS",
+        _ => return None,
+    })
+}
+
+/// Swedish drops the diacritic: `å`, `ä` → `a`, `ö` → `o` (never `aa`/`ae`/`oe`).
+fn swedish(c: char) -> Option<&'static str> {
+    Some(match c {
+        'å' | 'ä' => "a",
+        'ö' => "o",
+        'Å' | 'Ä' => "A",
+        'Ö' => "O",
+        _ => return None,
+    })
+}
+
+/// Turkish dotted/dotless `i` pairs map to plain `i`/`I`.
+fn turkish(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ı' => "i",
+        'İ' => "I",
+        'ş' => "s",
+        'Ş' => "S",
+        'ğ' => "g",
+        'Ğ' => "G",
+        'ç' => "c",
+        'Ç' => "C",
+        'ö' => "o",
+        'Ö' => "O",
+        'ü' => "u",
+        'Ü' => "U",
+        _ => return None,
+    })
+}
+
+/// Slovenian carons drop to the base letter; `đ` (common in names) becomes `dj`.
+fn slovenian(c: char) -> Option<&'static str> {
+    Some(match c {
+        'č' | 'ć' => "c",
+        'Č' | 'Ć' => "C",
+        'š' => "s",
+        'Š' => "S",
+        'ž' => "z",
+        'Ž' => "Z",
+        'đ' => "dj",
+        'Đ' => "Dj",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_german_umlauts() {
+        assert_eq!(locale_transliterate("Äpfel Öl Übung", "de"), "Aepfel Oel Uebung");
+        assert_eq!(locale_transliterate("Straße", "de"), "Strasse");
+    }
+
+    #[test]
+    fn test_german_region_subtag() {
+        assert_eq!(locale_transliterate("Müller", "de-AT"), "Mueller");
+        assert_eq!(locale_transliterate("Müller", "DE_ch"), "Mueller");
+    }
+
+    #[test]
+    fn test_swedish() {
+        assert_eq!(locale_transliterate("Smörgåsbord Ärtsoppa", "sv"), "Smorgasbord Artsoppa");
+    }
+
+    #[test]
+    fn test_turkish_dotless_i() {
+        assert_eq!(locale_transliterate("Işık İstanbul", "tr"), "Isik Istanbul");
+        assert_eq!(locale_transliterate("ağaç", "tr"), "agac");
+    }
+
+    #[test]
+    fn test_slovenian() {
+        assert_eq!(locale_transliterate("Čaša žganja", "sl"), "Casa zganja");
+        assert_eq!(locale_transliterate("Đoković", "sl"), "Djokovic");
+    }
+
+    #[test]
+    fn test_unknown_locale_falls_back() {
+        assert_eq!(locale_transliterate("Müller", "xx"), any_ascii::any_ascii("Müller"));
+        assert_eq!(locale_transliterate("Müller", ""), "Muller");
+    }
+
+    #[test]
+    fn test_unmapped_chars_use_any_ascii() {
+        assert_eq!(locale_transliterate("Über Café", "de"), "Ueber Cafe");
+    }
+}
@@ -0,0 +1,123 @@
+//! Per-script transliteration control.
+//!
+//! By default every non-ASCII character goes through transliteration. With
+//! [`SlugifyOptions::script_policies`](crate::SlugifyOptions::script_policies)
+//! each Unicode script can instead be kept verbatim or removed — e.g.
+//! romanize Cyrillic but keep Arabic.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use unicode_script::{Script, UnicodeScript as _};
+
+/// A Unicode script, as reported by the `unicode-script` crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnicodeScript(pub Script);
+
+// `Script` has no ordering of its own; order by its `u8` discriminant so
+// scripts can key a `BTreeMap`.
+impl Ord for UnicodeScript {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0 as u8).cmp(&(other.0 as u8))
+    }
+}
+
+impl PartialOrd for UnicodeScript {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// What to do with runs of text in a given script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScriptPolicy {
+    /// Transliterate to ASCII (the default for scripts without a policy).
+    #[default]
+    Transliterate,
+    /// Keep the characters as they are.
+    Keep,
+    /// Drop the characters, treating them as a word break.
+    Remove,
+}
+
+/// Split `input` into runs of the same script and apply each run's policy.
+///
+/// Combining marks (the `Inherited` script) stay with the run they follow.
+/// Runs without a policy — including `Common` characters such as spaces,
+/// digits, and punctuation — are passed to `transliterate`.
+pub(crate) fn apply_script_policies(
+    input: &str,
+    policies: &BTreeMap<UnicodeScript, ScriptPolicy>,
+    transliterate: impl Fn(&str) -> String,
+) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut run = String::new();
+    let mut run_script: Option<Script> = None;
+
+    let mut flush = |run: &mut String, script: Option<Script>| {
+        if run.is_empty() {
+            return;
+        }
+        let policy = script
+            .and_then(|s| policies.get(&UnicodeScript(s)).copied())
+            .unwrap_or_default();
+        match policy {
+            ScriptPolicy::Transliterate => result.push_str(&transliterate(run)),
+            ScriptPolicy::Keep => result.push_str(run),
+            ScriptPolicy::Remove => result.push(' '),
+        }
+        run.clear();
+    };
+
+    for c in input.chars() {
+        let script = match c.script() {
+            Script::Inherited => run_script.unwrap_or(Script::Inherited),
+            s => s,
+        };
+        if run_script != Some(script) {
+            flush(&mut run, run_script);
+            run_script = Some(script);
+        }
+        run.push(c);
+    }
+    flush(&mut run, run_script);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policies(pairs: &[(Script, ScriptPolicy)]) -> BTreeMap<UnicodeScript, ScriptPolicy> {
+        pairs.iter().map(|&(s, p)| (UnicodeScript(s), p)).collect()
+    }
+
+    #[test]
+    fn test_no_policies_transliterates_everything() {
+        let result = apply_script_policies("Привет мир", &BTreeMap::new(), any_ascii::any_ascii);
+        assert_eq!(result, "Privet mir");
+    }
+
+    #[test]
+    fn test_keep_and_remove() {
+        let map = policies(&[(Script::Arabic, ScriptPolicy::Keep), (Script::Han, ScriptPolicy::Remove)]);
+        let result = apply_script_policies("مرحبا東京Привет", &map, any_ascii::any_ascii);
+        assert_eq!(result, "مرحبا Privet");
+    }
+
+    #[test]
+    fn test_combining_marks_follow_their_base() {
+        let map = policies(&[(Script::Latin, ScriptPolicy::Keep)]);
+        let result = apply_script_policies("cafe\u{0301}", &map, any_ascii::any_ascii);
+        assert_eq!(result, "cafe\u{0301}");
+    }
+
+    #[test]
+    fn test_scripts_are_ordered_consistently_with_eq() {
+        let (latin, arabic) = (UnicodeScript(Script::Latin), UnicodeScript(Script::Arabic));
+        assert_eq!(latin.cmp(&latin), Ordering::Equal);
+        assert_eq!(latin.cmp(&arabic), arabic.cmp(&latin).reverse());
+        assert_ne!(latin.cmp(&arabic), Ordering::Equal);
+    }
+}
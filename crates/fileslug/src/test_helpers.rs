@@ -0,0 +1,14 @@
+//! Assertions shared by the unit tests and the integration tests, which
+//! include this file with `#[path]`. It only names items through `super`, so
+//! each includer imports them: the crate root does already, and an
+//! integration test does with `use fileslug::...`.
+
+use super::{slugify, SlugifyOptions};
+
+/// Panic unless slugifying the slug of `input` gives the same slug back.
+#[track_caller]
+pub fn assert_slugify_idempotent(input: &str, options: &SlugifyOptions) {
+    let once = slugify(input, options);
+    let twice = slugify(&once, options);
+    assert_eq!(twice, once, "slugify is not idempotent for {input:?} with {options:?}");
+}
@@ -0,0 +1,118 @@
+//! The fixture inputs shared by the golden and idempotency tests: wild
+//! filenames from the real world. Adding a fixture here adds its golden tests
+//! (create the files with `make update-golden`) and its idempotency check.
+
+/// Invoke `$callback!` with every fixture, as `name: input;` entries.
+macro_rules! with_fixtures {
+    ($callback:ident) => {
+        $callback! {
+            // =====================================================================
+            // Real-world messy — the Downloads folder hall of shame
+            // =====================================================================
+
+            download_duplicate: "Report (2) (copy).pdf";
+
+            macos_screenshot: "Screenshot 2024-03-01 at 2.15.32\u{202f}PM.png";
+
+            camera_roll: "IMG_20240301_142359.jpg";
+
+            raw_photo_dupe: "DSC_0042 (1).NEF";
+
+            music_track: "01 - Artist - Track Name (feat. Other Artist).mp3";
+
+            final_v2: "my_document - final FINAL (really final) v2.docx";
+
+            copy_of_copy: "Copy of Copy of Budget_2024 (1).xlsx";
+
+            brackets_mixed: "Document(1) [Reviewed].pdf";
+
+            scanned_tax: "tax return 2023 - SIGNED (scanned).PDF";
+
+            word_temp: "~$word temp file.docx";
+
+            photoshop_copy: "Untitled-1 (3rd copy).psd";
+
+            download_hell: "index (1) (1) (2).html";
+
+            // =====================================================================
+            // Unicode & international — the world is bigger than ASCII
+            // =====================================================================
+
+            spanish_tilde: "Ñoño café.txt";
+
+            german_umlauts: "Ünïcödé Fïlé.txt";
+
+            russian_cyrillic: "\u{041F}\u{0440}\u{0438}\u{0432}\u{0435}\u{0442} \u{043C}\u{0438}\u{0440}.txt";
+
+            japanese_mixed: "\u{6771}\u{4EAC}\u{30BF}\u{30EF}\u{30FC}.jpg";
+
+            arabic_rtl: "\u{0645}\u{0631}\u{062D}\u{0628}\u{0627} \u{0628}\u{0627}\u{0644}\u{0639}\u{0627}\u{0644}\u{0645}.txt";
+
+            emoji_overload: "\u{1F389} Party \u{1F382} Time \u{1F973}.txt";
+
+            french_cedilla: "Ça fait beau temps.txt";
+
+            mixed_scripts: "Tokyo \u{6771}\u{4EAC} 2024.txt";
+
+            nordic_french: "Ångström naïve.txt";
+
+            zero_width_chars: "hello\u{200B}world\u{FEFF}file.txt";
+
+            // =====================================================================
+            // Shell / security adversarial — should produce safe filenames
+            // =====================================================================
+
+            cmd_substitution: "$(rm -rf /).txt";
+
+            backtick_inject: "`whoami`.txt";
+
+            semicolon_chain: "file;rm -rf /;.txt";
+
+            quotes_mixed: "it's \"my\" file.txt";
+
+            xss_attempt: "<script>alert(1)</script>.html";
+
+            ampersand_chain: "file && echo pwned.txt";
+
+            env_expansion: "${HOME}/.secret.txt";
+
+            backslash_path: "path\\to\\file.txt";
+
+            // =====================================================================
+            // Filesystem edge cases
+            // =====================================================================
+
+            mixed_separators: "file--name__also  here.txt";
+
+            windows_reserved: "CON.txt";
+
+            compound_with_extras: "archive.backup.2024.tar.gz";
+
+            no_extension_dir: "NEW FOLDER (2)";
+
+            dotfile_with_spaces: ". hidden but spaces .txt";
+
+            many_dots: "a.b.c.d.e.f.g.txt";
+
+            all_caps: "ALL CAPS SHOUTING FILE.TXT";
+
+            all_dots: "...";
+
+            // =====================================================================
+            // Version number preservation — dots in version strings stay as dots
+            // =====================================================================
+
+            semver_dmg: "monarch-0.8.34.dmg";
+
+            version_two_part: "Shottr-1.9.dmg";
+
+            version_glued_to_name: "iStatMenus7.20.zip";
+
+            version_three_part: "NetNewsWire6.2.1.zip";
+
+            version_underscored: "Antinote_1.1.7.dmg";
+
+            version_multiple: "Launch Schedule 2.10-2.12.26.png";
+        }
+    };
+}
@@ -0,0 +1,75 @@
+//! Golden file tests: the expected slug for each fixture and style lives in
+//! `tests/golden/{fixture}.{style}.golden`, so a pipeline change that alters
+//! many outputs is a data update rather than a code edit.
+//!
+//! Run with `cargo test -p fileslug --test golden`. After an intended change,
+//! regenerate the files with `make update-golden` (or `UPDATE_GOLDEN=1`) and
+//! review the diff.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use fileslug::{slugify, SlugifyOptions, Style};
+
+#[macro_use]
+mod fixtures;
+
+/// Compare `slugify(input)` in `style` against the fixture's golden file, or
+/// with `UPDATE_GOLDEN=1` set, write the actual output to it.
+///
+/// Golden files hold the slug followed by a newline.
+fn run_golden_test(name: &str, input: &str, style: Style) {
+    let style_name = match style {
+        Style::Kebab => "kebab",
+        Style::Snake => "snake",
+        Style::Pascal => "pascal",
+    };
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.{style_name}.golden"));
+    let actual = slugify(input, &SlugifyOptions { style, ..Default::default() });
+
+    if env::var_os("UPDATE_GOLDEN").is_some_and(|v| v == "1") {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, format!("{actual}\n")).unwrap();
+        return;
+    }
+
+    let golden = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("cannot read {}: {e}\n  (run `make update-golden` to create it)", path.display()));
+    let expected = golden.strip_suffix('\n').unwrap_or(&golden);
+    assert_eq!(
+        actual,
+        expected,
+        "\n  input: {input:?}\n  style: {style_name}\n  golden: {}",
+        path.display()
+    );
+}
+
+macro_rules! golden_tests {
+    ($($name:ident: $input:expr;)*) => {
+        $(
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn kebab() {
+                    run_golden_test(stringify!($name), $input, Style::Kebab);
+                }
+
+                #[test]
+                fn snake() {
+                    run_golden_test(stringify!($name), $input, Style::Snake);
+                }
+
+                #[test]
+                fn pascal() {
+                    run_golden_test(stringify!($name), $input, Style::Pascal);
+                }
+            }
+        )*
+    };
+}
+
+with_fixtures!(golden_tests);
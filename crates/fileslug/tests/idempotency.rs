@@ -0,0 +1,102 @@
+//! Idempotency: slugifying a slug gives it back unchanged, for every
+//! fixture in every style and for generated names across option sets.
+//!
+//! A failure prints the input and options; add the input to the shared
+//! fixtures in `fixtures/mod.rs` once the pipeline is fixed.
+
+use fileslug::{slugify, DotfilePolicy, ExtensionConfig, SlugifyOptions, Style};
+use proptest::prelude::*;
+
+#[path = "../src/test_helpers.rs"]
+mod test_helpers;
+
+use test_helpers::assert_slugify_idempotent;
+
+#[macro_use]
+mod fixtures;
+
+macro_rules! fixture_inputs {
+    ($($name:ident: $input:expr;)*) => {
+        /// The inputs of the shared fixtures in `fixtures/mod.rs`.
+        const FIXTURES: &[&str] = &[$($input),*];
+    };
+}
+
+with_fixtures!(fixture_inputs);
+
+const STYLES: [Style; 3] = [Style::Kebab, Style::Snake, Style::Pascal];
+
+/// Option sets to check each name with, in every style.
+fn option_sets() -> Vec<SlugifyOptions> {
+    let base = [
+        SlugifyOptions::default(),
+        SlugifyOptions { keep_unicode: true, ..Default::default() },
+        SlugifyOptions { preserve_version_numbers: false, ..Default::default() },
+        SlugifyOptions { preserve_date: true, ..Default::default() },
+        SlugifyOptions { preserve_date: true, preserve_version_numbers: false, ..Default::default() },
+        SlugifyOptions { cross_platform: true, ..Default::default() },
+        SlugifyOptions { sentence_case: true, ..Default::default() },
+        SlugifyOptions { strip_punctuation_only: true, ..Default::default() },
+        SlugifyOptions { preserve_leading_separator: true, preserve_trailing_separator: true, ..Default::default() },
+        SlugifyOptions { smart_case: true, ..Default::default() },
+        SlugifyOptions { strip_copy_annotations: true, ..Default::default() },
+        SlugifyOptions::default()
+            .with_extensions(ExtensionConfig { dotfile_policy: DotfilePolicy::SplitAtFirst, ..Default::default() }),
+        SlugifyOptions::default().with_extensions(ExtensionConfig { normalize: true, ..Default::default() }),
+        SlugifyOptions { max_length: Some(16), ..Default::default() },
+        SlugifyOptions { stop_words: vec!["the".into(), "of".into()], min_word_length: 2, ..Default::default() },
+        SlugifyOptions::preset_url(),
+        SlugifyOptions::preset_tag(),
+    ];
+    base.iter()
+        .flat_map(|opts| STYLES.map(|style| SlugifyOptions { style, ..opts.clone() }))
+        .collect()
+}
+
+#[test]
+fn fixtures_are_idempotent() {
+    for options in option_sets() {
+        for input in FIXTURES {
+            assert_slugify_idempotent(input, &options);
+        }
+    }
+}
+
+#[test]
+fn numbers_next_to_dates_are_idempotent() {
+    for input in ["1192.21.34-2.0", "5-8955.45.07.2"] {
+        for options in option_sets() {
+            assert_slugify_idempotent(input, &options);
+        }
+    }
+}
+
+/// Filename-like strings: words from a mix of scripts and punctuation,
+/// optionally with an extension.
+fn filename() -> impl Strategy<Value = String> {
+    let word = prop::string::string_regex("[a-zA-Z0-9]{1,8}|[àéîõüßÆØ]{1,4}|[Ѐ-ӿ]{1,4}|[一-龥]{1,3}|[0-9]{1,3}(\\.[0-9]{1,3}){0,2}")
+        .unwrap();
+    let sep = prop::string::string_regex("[ _.&()'\\-]{1,2}").unwrap();
+    let ext = prop::option::of(prop::string::string_regex("\\.[a-zA-Z0-9]{1,4}").unwrap());
+    (prop::collection::vec((word, sep), 1..6), ext).prop_map(|(parts, ext)| {
+        let mut name: String = parts.into_iter().flat_map(|(w, s)| [w, s]).collect();
+        name.extend(ext);
+        name
+    })
+}
+
+proptest! {
+    #[test]
+    fn generated_names_are_idempotent(name in filename()) {
+        for options in option_sets() {
+            assert_slugify_idempotent(&name, &options);
+        }
+    }
+
+    #[test]
+    fn arbitrary_strings_are_idempotent(name in "\\PC{0,40}") {
+        for style in STYLES {
+            assert_slugify_idempotent(&name, &SlugifyOptions { style, ..Default::default() });
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Slugify arbitrary names with arbitrary options.
+//!
+//! Run with `cargo +nightly fuzz run fuzz_all_options` from this directory.
+
+#![no_main]
+
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+
+use fileslug::{slug_length_estimate, slugify, slugify_os, slugify_string, SlugifyOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (SlugifyOptions, &str, &[u8])| {
+    let (options, name, bytes) = input;
+
+    let slug = slugify(name, &options);
+    assert!(slug.len() <= 255, "{name:?} -> {slug:?} is longer than 255 bytes");
+    assert!(slug_length_estimate(name, &options) >= slug.len(), "{name:?} -> {slug:?} is longer than estimated");
+
+    let text = slugify_string(name, &options);
+    assert!(text.len() <= 1024, "{name:?} -> {text:?} is longer than 1024 bytes");
+
+    // Undecodable bytes never make it into the result
+    let os_slug = slugify_os(OsStr::from_bytes(bytes), &options);
+    assert!(os_slug.to_str().is_some(), "{bytes:?} -> {} is not valid UTF-8", os_slug.display());
+    assert!(os_slug.len() <= 255, "{bytes:?} -> {} is longer than 255 bytes", os_slug.display());
+});
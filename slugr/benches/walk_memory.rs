@@ -0,0 +1,66 @@
+//! Peak heap usage of the eager and lazy walkers on a 10,000-file tree.
+//!
+//! Run with `cargo bench -p slugr --bench walk_memory`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// slugr is a binary crate, so the walker is compiled in directly
+#[allow(dead_code)]
+#[path = "../src/walk.rs"]
+mod walk;
+
+use walk::{collect_paths, collect_paths_iter, WalkBackend, WalkOrder};
+
+/// Tracks live and peak heap bytes.
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(live, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Peak bytes allocated while `f` runs, above what was live beforehand.
+fn peak_during(f: impl FnOnce() -> usize) -> (usize, usize) {
+    let before = LIVE.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let count = f();
+    (count, PEAK.load(Ordering::Relaxed) - before)
+}
+
+fn main() {
+    let dir = tempfile::tempdir().unwrap();
+    for d in 0..100 {
+        let sub = dir.path().join(format!("Directory Number {d}"));
+        fs::create_dir(&sub).unwrap();
+        for f in 0..100 {
+            fs::write(sub.join(format!("Some File Name {f}.txt")), "").unwrap();
+        }
+    }
+    let roots = [PathBuf::from(dir.path())];
+
+    let (eager_count, eager_peak) =
+        peak_during(|| collect_paths(&roots, true, WalkBackend::WalkDir, WalkOrder::BottomUp, false).len());
+    let (lazy_count, lazy_peak) = peak_during(|| collect_paths_iter(&roots, true, None).count());
+    assert_eq!(eager_count, lazy_count);
+
+    println!("{eager_count} paths");
+    println!("eager collect_paths:      {eager_peak:>9} bytes peak");
+    println!("lazy  collect_paths_iter: {lazy_peak:>9} bytes peak");
+}
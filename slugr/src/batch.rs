@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use rayon::prelude::*;
+
+use fileslug::{slugify, SlugifyOptions};
+
+use crate::rename::{rename_file, CaseCollisionDetector, RenameOptions, RenameResult, RenameStats};
+
+/// Rename `paths` in chunks of `batch_size`, renaming each chunk in parallel.
+/// Results are handed to `on_result` as each group of parallel renames completes.
+///
+/// Only one chunk of paths is held in memory at a time, so this pairs with
+/// the lazy walk from `collect_paths_iter`. Within a chunk, entries that land
+/// in the same directory are renamed one after another so collision suffixes
+/// stay correct, and a directory is only renamed once everything listed
+/// before it is done, which keeps the bottom-up order intact. Targets are
+/// resolved through one [`CaseCollisionDetector`] for the whole run.
+pub fn process_batch(
+    mut paths: impl Iterator<Item = PathBuf>,
+    batch_size: usize,
+    opts: &RenameOptions,
+    slug_opts: &SlugifyOptions,
+    mut on_result: impl FnMut(&RenameResult),
+) -> RenameStats {
+    let mut stats = RenameStats::default();
+    let detector = Mutex::new(CaseCollisionDetector::new());
+    let mut record = |result: RenameResult| {
+        stats.record(&result);
+        on_result(&result);
+    };
+
+    loop {
+        let chunk: Vec<PathBuf> = paths.by_ref().take(batch_size.max(1)).collect();
+        if chunk.is_empty() {
+            break;
+        }
+
+        let mut files = Vec::new();
+        for path in chunk {
+            if !path.is_dir() {
+                files.push(path);
+                continue;
+            }
+            // Everything before a directory may live inside it
+            rename_files(std::mem::take(&mut files), opts, slug_opts, &detector).into_iter().for_each(&mut record);
+            if let Some(result) = rename_one(&path, opts, slug_opts, &detector) {
+                record(result);
+            }
+        }
+        rename_files(files, opts, slug_opts, &detector).into_iter().for_each(&mut record);
+    }
+
+    stats
+}
+
+/// Rename non-directory entries in parallel, one worker per target directory.
+fn rename_files(
+    files: Vec<PathBuf>,
+    opts: &RenameOptions,
+    slug_opts: &SlugifyOptions,
+    detector: &Mutex<CaseCollisionDetector>,
+) -> Vec<RenameResult> {
+    let mut by_dir: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for path in files {
+        let parent = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        by_dir.entry(parent).or_default().push(path);
+    }
+
+    by_dir
+        .into_par_iter()
+        .map(|(_, paths)| paths.iter().filter_map(|p| rename_one(p, opts, slug_opts, detector)).collect::<Vec<_>>())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Slugify and rename one path; `None` if there is nothing to do.
+fn rename_one(
+    path: &Path,
+    opts: &RenameOptions,
+    slug_opts: &SlugifyOptions,
+    detector: &Mutex<CaseCollisionDetector>,
+) -> Option<RenameResult> {
+    let filename = path.file_name()?.to_string_lossy();
+
+    // Hard links to directories are not permitted
+    if opts.link_mode && path.is_dir() {
+        eprintln!("slugr: warning: skipping directory '{}': cannot hard link directories", path.display());
+        return None;
+    }
+
+    let new_name = slugify(&filename, slug_opts);
+
+    // Empty/dot slugs would resolve to the parent directory
+    if new_name.is_empty() || *new_name == *"." || *new_name == *".." {
+        return Some(RenameResult::Failed {
+            path: path.to_path_buf(),
+            error: io::Error::new(io::ErrorKind::InvalidInput, "slugified name is invalid"),
+        });
+    }
+
+    let target = path.parent().unwrap_or(Path::new(".")).join(&*new_name);
+    // Each directory is handled by one worker, so the lock is never contended for the same name
+    let resolved = detector.lock().unwrap_or_else(std::sync::PoisonError::into_inner).resolve(path, &target, opts);
+    Some(match resolved {
+        Ok(target) => rename_file(path, &target, opts),
+        Err(error) => RenameResult::Failed { path: path.to_path_buf(), error },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use crate::walk::collect_paths_iter;
+
+    fn executing() -> RenameOptions {
+        RenameOptions { dry_run: false, ..RenameOptions::default() }
+    }
+
+    #[test]
+    fn test_process_batch_renames_1000_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for d in 0..10 {
+            let sub = dir.path().join(format!("Sub Dir {d}"));
+            fs::create_dir(&sub).unwrap();
+            for f in 0..100 {
+                fs::write(sub.join(format!("File Number {f}.txt")), f.to_string()).unwrap();
+            }
+        }
+
+        let paths = collect_paths_iter(&[dir.path().to_path_buf()], true, None);
+        let stats = process_batch(paths, 10, &executing(), &SlugifyOptions::default(), |_| {});
+
+        assert_eq!(stats, RenameStats { renamed: 1010, ..RenameStats::default() });
+        for d in 0..10 {
+            let sub = dir.path().join(format!("sub-dir-{d}"));
+            for f in 0..100 {
+                assert_eq!(fs::read_to_string(sub.join(format!("file-number-{f}.txt"))).unwrap(), f.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_process_batch_collisions_in_same_chunk() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["My File.txt", "my  file.txt", "MY_FILE.txt"] {
+            fs::write(dir.path().join(name), name).unwrap();
+        }
+
+        let paths = collect_paths_iter(&[dir.path().to_path_buf()], true, None);
+        let stats = process_batch(paths, 10, &executing(), &SlugifyOptions::default(), |_| {});
+
+        assert_eq!(stats.renamed, 3);
+        for name in ["my-file.txt", "my-file-2.txt", "my-file-3.txt"] {
+            assert!(dir.path().join(name).exists(), "{name} missing");
+        }
+    }
+
+    #[test]
+    fn test_process_batch_dry_run_plans_distinct_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["My File.txt", "my  file.txt"] {
+            fs::write(dir.path().join(name), name).unwrap();
+        }
+
+        let mut targets = Vec::new();
+        let paths = collect_paths_iter(&[dir.path().to_path_buf()], true, None);
+        process_batch(paths, 10, &RenameOptions::default(), &SlugifyOptions::default(), |result| {
+            if let RenameResult::Renamed { to, .. } = result {
+                targets.push(to.file_name().unwrap().to_string_lossy().into_owned());
+            }
+        });
+
+        targets.sort();
+        assert_eq!(targets, ["my-file-2.txt", "my-file.txt"]);
+    }
+
+    #[test]
+    fn test_process_batch_dry_run_reports_every_result() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("A B.txt"), "").unwrap();
+        fs::write(dir.path().join("clean.txt"), "").unwrap();
+
+        let mut seen = 0;
+        let paths = collect_paths_iter(&[dir.path().to_path_buf()], true, None);
+        let stats = process_batch(paths, 1, &RenameOptions::default(), &SlugifyOptions::default(), |_| seen += 1);
+
+        assert_eq!(seen, 2);
+        assert_eq!(stats, RenameStats { renamed: 1, skipped: 1, ..RenameStats::default() });
+        assert!(dir.path().join("A B.txt").exists());
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use crate::rename::RenameJournal;
+
+/// Markup for the `--changelog` file.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChangelogFormat {
+    #[default]
+    Markdown,
+    Org,
+    Rst,
+}
+
+impl ChangelogFormat {
+    fn heading(self, title: &str) -> String {
+        match self {
+            ChangelogFormat::Markdown => format!("## {title}\n"),
+            ChangelogFormat::Org => format!("* {title}\n"),
+            ChangelogFormat::Rst => format!("{title}\n{}\n\n", "=".repeat(title.chars().count())),
+        }
+    }
+
+    fn item(self, old: &str, new: &str) -> String {
+        match self {
+            ChangelogFormat::Markdown => format!("- `{old}` → `{new}`\n"),
+            ChangelogFormat::Org => format!("- ={old}= → ={new}=\n"),
+            ChangelogFormat::Rst => format!("- ``{old}`` → ``{new}``\n"),
+        }
+    }
+}
+
+/// Format the renames in `journal` as a list per source directory, sorted by
+/// directory. A new name that lives in another directory (`--to-dir`) is
+/// shown as a full path.
+pub fn format_changelog(journal: &RenameJournal, format: ChangelogFormat) -> String {
+    let mut by_dir: BTreeMap<&Path, Vec<(String, String)>> = BTreeMap::new();
+    for (from, to) in journal.entries() {
+        let dir = from.parent().unwrap_or(Path::new(""));
+        let old = from.file_name().unwrap_or(from.as_os_str()).to_string_lossy().into_owned();
+        let new = match to.file_name() {
+            Some(name) if to.parent() == Some(dir) => name.to_string_lossy().into_owned(),
+            _ => to.to_string_lossy().into_owned(),
+        };
+        by_dir.entry(dir).or_default().push((old, new));
+    }
+
+    let mut out = String::new();
+    for (dir, renames) in by_dir {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let title = if dir.as_os_str().is_empty() { ".".into() } else { dir.to_string_lossy() };
+        out.push_str(&format.heading(&title));
+        for (old, new) in renames {
+            out.push_str(&format.item(&old, &new));
+        }
+    }
+    out
+}
+
+/// Write the renames in `journal` to `output`, grouped by directory.
+pub fn write_changelog(journal: &RenameJournal, output: &Path, format: ChangelogFormat) -> io::Result<()> {
+    fs::write(output, format_changelog(journal, format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn journal(entries: &[(&str, &str)]) -> RenameJournal {
+        let mut journal = RenameJournal::default();
+        for (from, to) in entries {
+            journal.record(PathBuf::from(from), PathBuf::from(to));
+        }
+        journal
+    }
+
+    #[test]
+    fn test_format_changelog_groups_by_directory() {
+        let journal = journal(&[
+            ("/photos/b/Beach Day.jpg", "/photos/b/beach-day.jpg"),
+            ("/photos/a/My Cat.png", "/photos/a/my-cat.png"),
+            ("/photos/b/Sunset.JPG", "/photos/b/sunset.JPG"),
+        ]);
+        assert_eq!(
+            format_changelog(&journal, ChangelogFormat::Markdown),
+            "## /photos/a\n- `My Cat.png` → `my-cat.png`\n\n\
+             ## /photos/b\n- `Beach Day.jpg` → `beach-day.jpg`\n- `Sunset.JPG` → `sunset.JPG`\n"
+        );
+    }
+
+    #[test]
+    fn test_format_changelog_org_and_rst() {
+        let journal = journal(&[("notes/Read Me.md", "notes/read-me.md")]);
+        assert_eq!(format_changelog(&journal, ChangelogFormat::Org), "* notes\n- =Read Me.md= → =read-me.md=\n");
+        assert_eq!(
+            format_changelog(&journal, ChangelogFormat::Rst),
+            "notes\n=====\n\n- ``Read Me.md`` → ``read-me.md``\n"
+        );
+    }
+
+    #[test]
+    fn test_format_changelog_relative_and_moved() {
+        let journal = journal(&[("Old File.txt", "old-file.txt"), ("in/My Doc.txt", "out/my-doc.txt")]);
+        assert_eq!(
+            format_changelog(&journal, ChangelogFormat::Markdown),
+            "## .\n- `Old File.txt` → `old-file.txt`\n\n## in\n- `My Doc.txt` → `out/my-doc.txt`\n"
+        );
+    }
+
+    #[test]
+    fn test_format_changelog_empty() {
+        assert_eq!(format_changelog(&RenameJournal::default(), ChangelogFormat::Markdown), "");
+    }
+}
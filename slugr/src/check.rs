@@ -0,0 +1,78 @@
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Output for the `--check` report.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CheckFormat {
+    /// One `path -> slug` line per file that is not clean
+    #[default]
+    Text,
+    /// A JSON array of `{"path": ..., "slug": ...}` objects, empty if all are clean
+    Json,
+}
+
+/// One file in the JSON `--check` report.
+#[derive(Debug, Serialize)]
+struct CheckEntry<'a> {
+    path: Cow<'a, str>,
+    slug: &'a str,
+}
+
+/// Format the files that `--check` found would be renamed, as `(path, slug)` pairs.
+pub fn format_check_report(unclean: &[(PathBuf, String)], format: CheckFormat) -> String {
+    match format {
+        CheckFormat::Text => unclean.iter().fold(String::new(), |mut out, (path, slug)| {
+            let _ = writeln!(out, "{} -> {slug}", path.display());
+            out
+        }),
+        CheckFormat::Json => {
+            let entries: Vec<CheckEntry> =
+                unclean.iter().map(|(path, slug)| CheckEntry { path: path.to_string_lossy(), slug }).collect();
+            // Serializing strings and a plain struct cannot fail
+            let json = serde_json::to_string(&entries).unwrap_or_default();
+            format!("{json}\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unclean() -> Vec<(PathBuf, String)> {
+        vec![
+            (PathBuf::from("photos/Beach Day.jpg"), "beach-day.jpg".to_string()),
+            (PathBuf::from("Say \"Hi\"\t.txt"), "say-hi.txt".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_format_check_report_text() {
+        assert_eq!(
+            format_check_report(&unclean(), CheckFormat::Text),
+            "photos/Beach Day.jpg -> beach-day.jpg\nSay \"Hi\"\t.txt -> say-hi.txt\n"
+        );
+        assert_eq!(format_check_report(&[], CheckFormat::Text), "");
+    }
+
+    #[test]
+    fn test_format_check_report_json() {
+        assert_eq!(
+            format_check_report(&unclean(), CheckFormat::Json),
+            "[{\"path\":\"photos/Beach Day.jpg\",\"slug\":\"beach-day.jpg\"},\
+             {\"path\":\"Say \\\"Hi\\\"\\t.txt\",\"slug\":\"say-hi.txt\"}]\n"
+        );
+        assert_eq!(format_check_report(&[], CheckFormat::Json), "[]\n");
+    }
+
+    #[test]
+    fn test_format_check_report_json_escapes_controls() {
+        let unclean = [(PathBuf::from("a\\b\u{1}.txt"), "a-b.txt".to_string())];
+        let report: serde_json::Value = serde_json::from_str(&format_check_report(&unclean, CheckFormat::Json)).unwrap();
+        assert_eq!(report[0]["path"], "a\\b\u{1}.txt");
+    }
+}
@@ -1,8 +1,66 @@
+use std::io;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{CommandFactory, Parser, ValueEnum};
+use clap_complete::Shell;
 
-use fileslug::Style;
+use fileslug::{SlugifyOptions, SlugifyOptionsOverride, StopWords, Style};
+
+use crate::changelog::ChangelogFormat;
+use crate::check::CheckFormat;
+use crate::content_hash::HashAlgorithm;
+use crate::env_config::EnvConfig;
+use crate::pipe::PipeOptions;
+use crate::rename::{CollisionSuffix, RenameOptions, MAX_COLLISION_SUFFIX};
+use crate::walk::{EntryFilter, WalkBackend, WalkOrder};
+
+/// Upper bound for `--max-collisions`; past this, probing for a free name
+/// costs more than it is worth.
+const MAX_COLLISIONS_LIMIT: i64 = 100_000;
+
+/// Named option presets, see `SlugifyOptions::preset_*`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preset {
+    /// URL slugs: kebab-case, max 100 bytes, articles removed
+    Url,
+    /// Programming identifiers: `snake_case`, max 64 bytes, no version dots
+    #[value(name = "id")]
+    Identifier,
+    /// Filenames (the default behavior)
+    #[value(name = "fs")]
+    Filesystem,
+    /// Short tags: kebab-case, max 30 bytes, single-character words dropped
+    Tag,
+}
+
+/// Built-in stop word lists for `--stop-words`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopWordsLang {
+    /// English articles, prepositions, and conjunctions
+    En,
+    /// French
+    Fr,
+    /// German
+    De,
+    /// Spanish
+    Es,
+    /// No stop words, even if the preset has some
+    None,
+}
+
+impl StopWordsLang {
+    /// The stop words to use.
+    fn words(self) -> Vec<String> {
+        match self {
+            StopWordsLang::En => StopWords::English.to_vec(),
+            StopWordsLang::Fr => StopWords::French.to_vec(),
+            StopWordsLang::De => StopWords::German.to_vec(),
+            StopWordsLang::Es => StopWords::Spanish.to_vec(),
+            StopWordsLang::None => Vec::new(),
+        }
+    }
+}
 
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Parser, Debug)]
@@ -22,18 +80,103 @@ pub struct Cli {
     #[arg(short, long)]
     pub verbose: bool,
 
+    /// Print a summary of renamed, unchanged, and failed files when done
+    #[arg(long, conflicts_with_all = ["count", "explain", "diff"])]
+    pub stats: bool,
+
+    /// Print a warning for each name that is already clean
+    #[arg(long, conflicts_with_all = ["batch_size", "from_pairs", "edit_plan", "template", "explain", "diff", "pipe"])]
+    pub warn_unchanged: bool,
+
     /// Allow overwriting existing files (default: no-clobber)
     #[arg(long)]
     pub clobber: bool,
 
+    /// How to disambiguate a target name that is already taken [default: counter]
+    #[arg(long, value_enum, value_name = "KIND", conflicts_with = "clobber")]
+    pub collision: Option<CollisionSuffix>,
+
+    /// Give up on a name after N collision suffixes (0 fails on any collision)
+    #[arg(long, value_name = "N", default_value_t = MAX_COLLISION_SUFFIX, value_parser = clap::value_parser!(u32).range(0..=MAX_COLLISIONS_LIMIT), conflicts_with = "clobber")]
+    pub max_collisions: u32,
+
+    /// Zero-pad collision counters to N digits (`-002` for 3), so they sort in order
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=10), conflicts_with = "clobber")]
+    pub collision_padding: Option<u32>,
+
+    /// Append the first LENGTH hex chars (default 8) of the SHA-256 of each file's first 64 KiB to its slug
+    #[arg(long, value_name = "LENGTH", num_args = 0..=1, require_equals = true, default_missing_value = "8", value_parser = clap::value_parser!(u8).range(1..=64), conflicts_with_all = ["batch_size", "from_pairs", "edit_plan", "template", "explain", "diff", "pipe"])]
+    pub content_hash: Option<u8>,
+
+    /// Like --content-hash, appending the full 64-char hash
+    #[arg(long, conflicts_with_all = ["content_hash", "batch_size", "from_pairs", "edit_plan", "template", "explain", "diff", "pipe"])]
+    pub content_hash_full: bool,
+
+    /// Append a counter zero-padded to N digits (`-001`, `-002`, ...) to every renamed name.
+    /// Names already ending in such a counter keep it, so a rerun doesn't add another
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(1..=20), conflicts_with_all = ["batch_size", "from_pairs", "edit_plan", "template", "explain", "diff", "count", "check", "pipe"])]
+    pub suffix_digits: Option<u8>,
+
+    /// Rename each file to the hex digest of its content, keeping the extension
+    /// (`{hash}.{ext}`). A file identical to one already named so is skipped.
+    /// Directories are left alone
+    #[arg(long, value_name = "ALGORITHM", value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "sha256", conflicts_with_all = ["content_hash", "content_hash_full", "suffix_digits", "to_dir", "batch_size", "from_pairs", "edit_plan", "template", "export_pairs", "explain", "diff", "count", "check", "pipe"])]
+    pub rename_to_hash: Option<HashAlgorithm>,
+
+    /// Shorten --rename-to-hash names to the first N hex chars of the digest
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(1..=64), requires = "rename_to_hash")]
+    pub hash_prefix: Option<u8>,
+
+    /// First value of the --suffix-digits counter
+    #[arg(long, value_name = "M", default_value_t = 1, requires = "suffix_digits")]
+    pub start_at: u64,
+
     /// Prompt before each rename
     #[arg(short, long)]
     pub interactive: bool,
 
+    /// Keep the current name when the slug differs from it by fewer than N
+    /// character edits, e.g. 2 to skip case-only changes like `File.PDF` → `file.PDF`
+    #[arg(long, value_name = "N", conflicts_with_all = ["batch_size", "edit_plan", "template", "rename_to_hash", "from_pairs", "explain", "diff", "pipe"])]
+    pub min_change: Option<usize>,
+
+    /// Before renaming, list each set of files that would be renamed to the
+    /// same name, and ask whether to proceed if there are any (requires -x to ask)
+    #[arg(long, conflicts_with_all = ["batch_size", "suffix_digits", "from_pairs", "edit_plan", "template", "rename_to_hash", "explain", "diff", "count", "check", "pipe"])]
+    pub check_conflicts: bool,
+
     /// Recurse into directories
     #[arg(short, long)]
     pub recursive: bool,
 
+    /// Rename directories only, leaving files alone
+    #[arg(long, conflicts_with = "files_only")]
+    pub dirs_only: bool,
+
+    /// Rename files only, leaving directories alone
+    #[arg(long, conflicts_with = "dirs_only")]
+    pub files_only: bool,
+
+    /// Process paths in sorted order instead of filesystem order, for reproducible output
+    #[arg(long)]
+    pub sort: bool,
+
+    /// Order of renames when recursing. WARNING: top-down renames each directory
+    /// before its contents, which then fail with "no such file"; use it only
+    /// when no directory is renamed (e.g. with --files-only)
+    #[arg(long, value_enum, value_name = "ORDER", default_value_t = WalkOrder::BottomUp, requires = "recursive")]
+    pub rename_order: WalkOrder,
+
+    /// Review the planned renames in an interactive table; Enter renames the enabled ones
+    #[cfg(feature = "tui")]
+    #[arg(long, conflicts_with_all = ["interactive", "atomic", "batch_size", "to_dir", "edit_plan", "explain", "diff", "count", "export_pairs", "from_pairs", "changelog", "extension_map", "content_hash", "content_hash_full", "warn_unchanged", "check", "suffix_digits", "log_jsonl", "rename_to_hash", "check_conflicts", "min_change", "pipe"])]
+    pub tui: bool,
+
+    /// Don't skip gitignored and hidden files when recursing
+    #[cfg(feature = "ignore")]
+    #[arg(long, requires = "recursive")]
+    pub no_ignore: bool,
+
     /// Use `snake_case` instead of kebab-case
     #[arg(long, conflicts_with = "pascal")]
     pub snake: bool,
@@ -46,16 +189,197 @@ pub struct Cli {
     #[arg(long)]
     pub keep_unicode: bool,
 
+    /// Treat dots in version numbers as separators (`app-1.2.3` → `app-1-2-3`)
+    #[arg(long)]
+    pub no_version_dots: bool,
+
+    /// Replace extension aliases with canonical ones (`.jpeg` → `.jpg`) from a TOML FILE of `alias = "ext"` lines, on top of built-in common aliases
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["batch_size", "from_pairs", "edit_plan", "template", "explain", "pipe"])]
+    pub extension_map: Option<PathBuf>,
+
+    /// Keep the compound extensions listed in FILE (one per line, e.g. `.warc.gz`) together, on top of built-in ones like `.tar.gz`
+    #[arg(long, value_name = "FILE")]
+    pub extra_extensions: Option<PathBuf>,
+
+    /// Start from a named preset; --snake, --pascal, --keep-unicode, and --no-version-dots still apply on top
+    #[arg(long, value_enum)]
+    pub preset: Option<Preset>,
+
+    /// Drop the common short words of LANG (articles, prepositions, conjunctions) from names
+    #[arg(long, value_enum, value_name = "LANG")]
+    pub stop_words: Option<StopWordsLang>,
+
+    /// Create hard links with slugified names instead of renaming
+    #[arg(long)]
+    pub link: bool,
+
+    /// Keep each file's access and modification times, even when it is copied to another filesystem
+    #[arg(long, conflicts_with = "link")]
+    pub preserve_timestamps: bool,
+
+    /// Rename all files or none: roll back completed renames if one fails
+    #[arg(long, conflicts_with_all = ["link", "clobber"])]
+    pub atomic: bool,
+
+    /// Rename N paths at a time, in parallel, holding only N paths in memory
+    #[arg(long, value_name = "N", conflicts_with_all = ["interactive", "atomic", "to_dir", "explain", "diff", "count", "export_pairs", "from_pairs"])]
+    pub batch_size: Option<NonZeroUsize>,
+
+    /// Move renamed files into DIR instead of renaming in place (created if missing)
+    #[arg(long, value_name = "DIR")]
+    pub to_dir: Option<PathBuf>,
+
+    /// Apply tab-separated `old<TAB>new` rename pairs from FILE instead of slugifying
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["files", "recursive", "to_dir", "count", "explain", "diff"])]
+    pub from_pairs: Option<PathBuf>,
+
+    /// Review and edit the planned renames in $EDITOR before applying them
+    #[arg(long, conflicts_with_all = ["batch_size", "to_dir", "from_pairs", "export_pairs", "explain", "diff", "count", "pipe"])]
+    pub edit_plan: bool,
+
+    /// Name files from TEMPLATE, e.g. "{mtime_year}-{mtime_month}-{slug}.{ext}" (see README for variables)
+    #[arg(long, value_name = "TEMPLATE", conflicts_with_all = ["batch_size", "to_dir", "from_pairs", "export_pairs", "edit_plan", "explain", "diff", "count", "pipe"])]
+    pub template: Option<String>,
+
+    /// Write the planned renames to FILE as `old<TAB>new` pairs (dry-run only)
+    #[arg(long, value_name = "FILE", conflicts_with = "execute")]
+    pub export_pairs: Option<PathBuf>,
+
+    /// After renaming, write each `old → new` pair to FILE, grouped by directory
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["batch_size", "explain", "diff", "count", "pipe"])]
+    pub changelog: Option<PathBuf>,
+
+    /// Append a JSON object per rename to FILE as each one completes (requires -x)
+    #[arg(long, value_name = "FILE", requires = "execute", conflicts_with_all = ["atomic", "batch_size", "from_pairs", "edit_plan", "template", "explain", "diff", "count", "check", "rename_to_hash", "pipe"])]
+    pub log_jsonl: Option<PathBuf>,
+
+    /// Markup for the --changelog file
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = ChangelogFormat::Markdown, requires = "changelog")]
+    pub changelog_fmt: ChangelogFormat,
+
+    /// Print a step-by-step trace of how each name is slugified
+    #[arg(long, conflicts_with_all = ["execute", "interactive", "count"])]
+    pub explain: bool,
+
+    /// Print a character-level diff between each name and its slug
+    #[arg(long, conflicts_with_all = ["execute", "interactive", "count", "explain"])]
+    pub diff: bool,
+
+    /// Only print the number of files that would be renamed
+    #[arg(long, conflicts_with_all = ["execute", "interactive"])]
+    pub count: bool,
+
+    /// Break the --count total down by extension. Requires --count
+    #[arg(long, requires = "count")]
+    pub count_by_ext: bool,
+
+    /// Exit 1 if any file would be renamed, listing them on stderr, and 0 if all are already clean
+    #[arg(long, conflicts_with_all = ["execute", "interactive", "count", "explain", "diff", "stats", "batch_size", "to_dir", "from_pairs", "edit_plan", "template", "export_pairs", "changelog", "print0"])]
+    pub check: bool,
+
+    /// Output for the --check list
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = CheckFormat::Text, requires = "check")]
+    pub check_format: CheckFormat,
+
     /// Pipe mode: read text from stdin, write slugified output to stdout
-    #[arg(long, conflicts_with_all = ["execute", "clobber", "interactive", "recursive", "files"])]
+    #[arg(long, conflicts_with_all = ["execute", "clobber", "interactive", "recursive", "count", "to_dir", "link", "explain", "from_pairs", "export_pairs", "collision", "atomic", "stats", "sort", "batch_size", "diff", "check", "files"])]
     pub pipe: bool,
 
     /// Treat input as raw text, not filenames (skip extension handling). Requires --pipe
     #[arg(long, requires = "pipe", conflicts_with = "files")]
     pub raw: bool,
 
+    /// Only output lines whose slug differs from the input. Requires --pipe
+    #[arg(long, requires = "pipe", conflicts_with = "files")]
+    pub filter_unchanged: bool,
+
+    /// Slugify only the last component of each path, keeping its directory as is. Requires --pipe
+    #[arg(long, requires = "pipe", conflicts_with = "files")]
+    pub only_basename: bool,
+
+    /// Write each input line before its slug, as `original<TAB>slug`. Requires --pipe
+    #[arg(long, requires = "pipe", conflicts_with = "files")]
+    pub print_original: bool,
+
+    /// Write buffer size in bytes for pipe output. Requires --pipe
+    #[arg(long, value_name = "N", requires = "pipe", conflicts_with = "files")]
+    pub pipe_batch_size: Option<NonZeroUsize>,
+
+    /// Read NUL-separated paths from stdin (as written by `find -print0`) instead of lines
+    #[arg(short = '0', long, conflicts_with_all = ["files", "pipe", "from_pairs"])]
+    pub from_null: bool,
+
+    /// Print each new path followed by a NUL byte instead of `old -> new` lines.
+    /// In pipe mode, end each output field with a NUL byte instead
+    #[arg(long, conflicts_with_all = ["count", "explain", "diff", "export_pairs"])]
+    pub print0: bool,
+
+    /// Print a completion script for SHELL to stdout and exit
+    #[arg(long, value_enum, value_name = "SHELL", exclusive = true)]
+    pub generate_completions: Option<Shell>,
+
+    /// Print the slugr man page (troff) to stdout and exit
+    #[arg(long, exclusive = true)]
+    pub man: bool,
+
     /// Files and directories to rename
     pub files: Vec<PathBuf>,
+
+    /// Defaults from `SLUGR_*` environment variables and the config file,
+    /// overridden by flags
+    #[arg(skip)]
+    pub defaults: EnvConfig,
+}
+
+/// Write the completion script for `shell` to stdout.
+pub fn generate_completions(shell: Shell) {
+    clap_complete::generate(shell, &mut Cli::command(), "slugr", &mut io::stdout());
+}
+
+/// The EXAMPLES section of the man page, from the README.
+const MAN_EXAMPLES: &str = r#".SH EXAMPLES
+.TP
+\fBslugr *.pdf\fR
+Preview renames (dry\-run).
+.TP
+\fBslugr \-x *.pdf\fR
+Actually rename.
+.TP
+\fBslugr \-rx my\-project/\fR
+Recurse into directories.
+.TP
+\fBfind . \-name "*.txt" | slugr \-x\fR
+Rename paths read from stdin.
+.TP
+\fBslugr \-ix *.jpg\fR
+Prompt before each rename.
+.TP
+\fBslugr \-r \-\-export\-pairs plan.tsv photos/\fR
+Write the rename plan to a file for review ...
+.TP
+\fBslugr \-x \-\-from\-pairs plan.tsv\fR
+\&... then apply it.
+.TP
+\fBslugr \-r \-\-count ~/Downloads\fR
+Count the files that would change.
+.TP
+\fBecho "My Blog Post Title!" | slugr \-\-pipe \-\-raw\fR
+Slugify text instead of files; prints \fBmy\-blog\-post\-title\fR.
+"#;
+
+/// Render the man page: the title, name, synopsis, description, and options
+/// generated from the `Cli` definition, followed by examples and the version.
+pub fn render_man_page() -> String {
+    let mut page = Vec::new();
+    clap_mangen::Man::new(Cli::command()).render(&mut page).expect("writing to a Vec cannot fail");
+    let page = String::from_utf8(page).expect("help text is UTF-8");
+
+    // roff puts its apostrophe string definition first; man pages conventionally open with .TH
+    let title_start = page.find(".TH ").expect("man page has a title");
+    let (preamble, rest) = page.split_at(title_start);
+    let (title, body) = rest.split_once('\n').unwrap_or((rest, ""));
+    let (sections, version) = body.split_at(body.find(".SH VERSION").unwrap_or(body.len()));
+    format!("{title}\n{preamble}{sections}{MAN_EXAMPLES}{version}")
 }
 
 impl Cli {
@@ -66,6 +390,85 @@ impl Cli {
             _ => Style::Kebab,
         }
     }
+
+    /// Build slugify options from the environment and config file, then the
+    /// preset (if any), then the style/unicode flags. An explicit preset
+    /// replaces the environment and config file's settings.
+    pub fn options(&self) -> SlugifyOptions {
+        let base = match self.preset {
+            Some(Preset::Url) => SlugifyOptions::preset_url(),
+            Some(Preset::Identifier) => SlugifyOptions::preset_identifier(),
+            Some(Preset::Tag) => SlugifyOptions::preset_tag(),
+            Some(Preset::Filesystem) => SlugifyOptions::preset_filesystem(),
+            None => SlugifyOptions::preset_filesystem().merge(&self.defaults.slugify_overrides()),
+        };
+        base.merge(&self.flag_overrides())
+    }
+
+    /// The slugify settings given as flags.
+    fn flag_overrides(&self) -> SlugifyOptionsOverride {
+        SlugifyOptionsOverride {
+            style: (self.snake || self.pascal).then(|| self.style()),
+            keep_unicode: self.keep_unicode.then_some(true),
+            preserve_version_numbers: self.no_version_dots.then_some(false),
+            warn_on_unchanged: self.warn_unchanged.then_some(true),
+            stop_words: self.stop_words.map(StopWordsLang::words),
+            ..SlugifyOptionsOverride::default()
+        }
+    }
+
+    /// Settings for `--pipe` mode.
+    pub fn pipe_options(&self) -> PipeOptions {
+        PipeOptions {
+            raw: self.raw,
+            filter_unchanged: self.filter_unchanged,
+            only_basename: self.only_basename,
+            print_original: self.print_original,
+            print0: self.print0,
+            buffer_size: self.pipe_batch_size,
+        }
+    }
+
+    /// Rename settings from the command line.
+    pub fn rename_options(&self) -> RenameOptions {
+        RenameOptions {
+            no_clobber: !self.clobber,
+            dry_run: !self.execute,
+            link_mode: self.link,
+            collision: self.collision.or(self.defaults.collision).unwrap_or_default(),
+            max_collisions: self.max_collisions,
+            collision_suffix_padding: self.collision_padding,
+            preserve_timestamps: self.preserve_timestamps,
+        }
+    }
+
+    /// How many hex chars of the content hash to append to each name, if any.
+    pub fn content_hash_length(&self) -> Option<usize> {
+        if self.content_hash_full {
+            Some(64)
+        } else {
+            self.content_hash.map(usize::from)
+        }
+    }
+
+    /// The traversal backend for `--recursive`.
+    #[cfg_attr(not(feature = "ignore"), allow(clippy::unused_self))]
+    pub fn walk_backend(&self) -> WalkBackend {
+        #[cfg(feature = "ignore")]
+        if !self.no_ignore {
+            return WalkBackend::Ignore;
+        }
+        WalkBackend::WalkDir
+    }
+
+    /// Which entries `--dirs-only` / `--files-only` keep.
+    pub fn entry_filter(&self) -> EntryFilter {
+        match (self.dirs_only, self.files_only) {
+            (true, _) => EntryFilter::DirsOnly,
+            (_, true) => EntryFilter::FilesOnly,
+            _ => EntryFilter::All,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -77,14 +480,29 @@ mod tests {
         let args = Cli::parse_from(["slugr", "file.txt"]);
         assert!(!args.execute);
         assert!(!args.verbose);
+        assert!(!args.stats);
         assert!(!args.clobber);
+        assert_eq!(args.rename_options().collision, CollisionSuffix::Counter);
         assert!(!args.interactive);
         assert!(!args.recursive);
         assert!(!args.snake);
         assert!(!args.pascal);
         assert!(!args.keep_unicode);
+        assert!(!args.no_version_dots);
+        assert!(args.options().preserve_version_numbers);
         assert!(!args.pipe);
         assert!(!args.raw);
+        assert!(!args.count);
+        assert!(!args.count_by_ext);
+        assert!(args.to_dir.is_none());
+        assert!(!args.link);
+        assert!(!args.atomic);
+        assert!(args.preset.is_none());
+        assert!(!args.explain);
+        assert!(args.from_pairs.is_none());
+        assert!(args.export_pairs.is_none());
+        assert!(!args.filter_unchanged);
+        assert!(args.pipe_batch_size.is_none());
         assert_eq!(args.files, vec![PathBuf::from("file.txt")]);
     }
 
@@ -177,4 +595,289 @@ mod tests {
         assert!(args.pipe);
         assert!(args.snake);
     }
+
+    #[test]
+    fn test_count_flag() {
+        let args = Cli::parse_from(["slugr", "--count", "file.txt"]);
+        assert!(args.count);
+        assert!(!args.count_by_ext);
+    }
+
+    #[test]
+    fn test_count_conflicts_with_execute() {
+        let result = Cli::try_parse_from(["slugr", "--count", "-x", "file.txt"]);
+        assert!(result.is_err(), "--count should conflict with -x");
+    }
+
+    #[test]
+    fn test_count_by_ext_requires_count() {
+        let result = Cli::try_parse_from(["slugr", "--count-by-ext", "file.txt"]);
+        assert!(result.is_err(), "--count-by-ext without --count should error");
+    }
+
+    #[test]
+    fn test_to_dir_flag() {
+        let args = Cli::parse_from(["slugr", "--to-dir", "out", "file.txt"]);
+        assert_eq!(args.to_dir, Some(PathBuf::from("out")));
+        assert_eq!(args.files, vec![PathBuf::from("file.txt")]);
+    }
+
+    #[test]
+    fn test_pipe_conflicts_with_to_dir() {
+        let result = Cli::try_parse_from(["slugr", "--pipe", "--to-dir", "out"]);
+        assert!(result.is_err(), "--pipe should conflict with --to-dir");
+    }
+
+    #[test]
+    fn test_preset_values() {
+        let args = Cli::parse_from(["slugr", "--preset", "id", "file.txt"]);
+        assert_eq!(args.preset, Some(Preset::Identifier));
+        assert_eq!(args.options(), SlugifyOptions::preset_identifier());
+        let args = Cli::parse_from(["slugr", "--preset", "fs", "file.txt"]);
+        assert_eq!(args.options(), SlugifyOptions::default());
+    }
+
+    #[test]
+    fn test_preset_invalid_value() {
+        let result = Cli::try_parse_from(["slugr", "--preset", "bogus", "file.txt"]);
+        assert!(result.is_err(), "unknown preset should error");
+    }
+
+    #[test]
+    fn test_preset_style_flag_overrides() {
+        let args = Cli::parse_from(["slugr", "--preset", "url", "--snake", "file.txt"]);
+        let options = args.options();
+        assert_eq!(options.style, Style::Snake);
+        assert_eq!(options.max_length, Some(100));
+    }
+
+    #[test]
+    fn test_stop_words_flag() {
+        let args = Cli::parse_from(["slugr", "--stop-words", "de", "file.txt"]);
+        assert_eq!(args.options().stop_words, StopWords::German.to_vec());
+        let args = Cli::parse_from(["slugr", "--preset", "url", "--stop-words", "none", "file.txt"]);
+        assert!(args.options().stop_words.is_empty());
+        assert!(Cli::try_parse_from(["slugr", "--stop-words", "it", "file.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_explain_conflicts_with_execute() {
+        let result = Cli::try_parse_from(["slugr", "--explain", "-x", "file.txt"]);
+        assert!(result.is_err(), "--explain should conflict with -x");
+    }
+
+    #[test]
+    fn test_from_pairs_conflicts_with_files() {
+        let result = Cli::try_parse_from(["slugr", "--from-pairs", "pairs.tsv", "file.txt"]);
+        assert!(result.is_err(), "--from-pairs should conflict with file arguments");
+    }
+
+    #[test]
+    fn test_export_pairs_conflicts_with_execute() {
+        let result = Cli::try_parse_from(["slugr", "--export-pairs", "pairs.tsv", "-x", "file.txt"]);
+        assert!(result.is_err(), "--export-pairs should conflict with -x");
+    }
+
+    #[test]
+    fn test_filter_unchanged_requires_pipe() {
+        let result = Cli::try_parse_from(["slugr", "--filter-unchanged", "file.txt"]);
+        assert!(result.is_err(), "--filter-unchanged without --pipe should error");
+    }
+
+    #[test]
+    #[cfg(not(feature = "ignore"))]
+    fn test_walk_backend_without_ignore_feature() {
+        let args = Cli::parse_from(["slugr", "-r", "dir"]);
+        assert_eq!(args.walk_backend(), WalkBackend::WalkDir);
+    }
+
+    #[test]
+    #[cfg(feature = "ignore")]
+    fn test_walk_backend_ignore() {
+        let args = Cli::parse_from(["slugr", "-r", "dir"]);
+        assert_eq!(args.walk_backend(), WalkBackend::Ignore);
+        let args = Cli::parse_from(["slugr", "-r", "--no-ignore", "dir"]);
+        assert_eq!(args.walk_backend(), WalkBackend::WalkDir);
+    }
+
+    #[test]
+    #[cfg(feature = "ignore")]
+    fn test_no_ignore_requires_recursive() {
+        let result = Cli::try_parse_from(["slugr", "--no-ignore", "dir"]);
+        assert!(result.is_err(), "--no-ignore without -r should error");
+    }
+
+    #[test]
+    fn test_collision_values() {
+        let args = Cli::parse_from(["slugr", "--collision", "hash", "file.txt"]);
+        assert_eq!(args.collision, Some(CollisionSuffix::Hash));
+        let args = Cli::parse_from(["slugr", "--collision", "timestamp", "file.txt"]);
+        assert_eq!(args.collision, Some(CollisionSuffix::Timestamp));
+        assert!(Cli::try_parse_from(["slugr", "--collision", "random", "file.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_collision_conflicts_with_clobber() {
+        let result = Cli::try_parse_from(["slugr", "--collision", "hash", "--clobber", "file.txt"]);
+        assert!(result.is_err(), "--collision should conflict with --clobber");
+    }
+
+    #[test]
+    fn test_atomic_conflicts_with_link() {
+        let result = Cli::try_parse_from(["slugr", "--atomic", "--link", "file.txt"]);
+        assert!(result.is_err(), "--atomic should conflict with --link");
+    }
+
+    #[test]
+    fn test_atomic_conflicts_with_clobber() {
+        let result = Cli::try_parse_from(["slugr", "--atomic", "--clobber", "file.txt"]);
+        assert!(result.is_err(), "--atomic should conflict with --clobber");
+    }
+
+    #[test]
+    fn test_style_mapping() {
+        assert_eq!(Cli::parse_from(["slugr", "f"]).style(), Style::Kebab);
+        assert_eq!(Cli::parse_from(["slugr", "--snake", "f"]).style(), Style::Snake);
+        assert_eq!(Cli::parse_from(["slugr", "--pascal", "f"]).style(), Style::Pascal);
+        let args = Cli::parse_from(["slugr", "--preset", "id", "--pascal", "f"]);
+        assert_eq!(args.options().style, Style::Pascal);
+    }
+
+    #[test]
+    fn test_pipe_batch_size() {
+        let args = Cli::parse_from(["slugr", "--pipe", "--pipe-batch-size", "65536"]);
+        assert_eq!(args.pipe_batch_size, NonZeroUsize::new(65536));
+        assert!(Cli::try_parse_from(["slugr", "--pipe", "--pipe-batch-size", "0"]).is_err());
+        assert!(Cli::try_parse_from(["slugr", "--pipe-batch-size", "10", "file.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_no_version_dots() {
+        let args = Cli::parse_from(["slugr", "--no-version-dots", "file.txt"]);
+        assert!(!args.options().preserve_version_numbers);
+    }
+
+    #[test]
+    fn test_stats_conflicts_with_count() {
+        let result = Cli::try_parse_from(["slugr", "--stats", "--count", "file.txt"]);
+        assert!(result.is_err(), "--stats should conflict with --count");
+    }
+
+    #[test]
+    fn test_sort_conflicts_with_pipe() {
+        assert!(Cli::parse_from(["slugr", "--sort", "-r", "dir"]).sort);
+        let result = Cli::try_parse_from(["slugr", "--pipe", "--sort"]);
+        assert!(result.is_err(), "--sort should conflict with --pipe");
+    }
+
+    #[test]
+    fn test_rename_options() {
+        assert_eq!(Cli::parse_from(["slugr", "file.txt"]).rename_options(), RenameOptions::default());
+        let options = Cli::parse_from(["slugr", "-x", "--clobber", "--link", "file.txt"]).rename_options();
+        assert!(!options.no_clobber && !options.dry_run && options.link_mode);
+    }
+
+    #[test]
+    fn test_batch_size() {
+        let args = Cli::parse_from(["slugr", "-r", "--batch-size", "10", "dir"]);
+        assert_eq!(args.batch_size, NonZeroUsize::new(10));
+        assert!(Cli::try_parse_from(["slugr", "--batch-size", "0", "dir"]).is_err());
+        assert!(Cli::try_parse_from(["slugr", "--batch-size", "10", "--atomic", "dir"]).is_err());
+    }
+
+    #[test]
+    fn test_diff_conflicts_with_execute() {
+        assert!(Cli::parse_from(["slugr", "--diff", "file.txt"]).diff);
+        let result = Cli::try_parse_from(["slugr", "--diff", "-x", "file.txt"]);
+        assert!(result.is_err(), "--diff should conflict with --execute");
+    }
+
+    #[test]
+    fn test_env_config_applies_under_flags() {
+        let env = EnvConfig {
+            style: Some(Style::Snake),
+            keep_unicode: Some(true),
+            max_length: NonZeroUsize::new(20),
+            collision: Some(CollisionSuffix::Hash),
+        };
+
+        let mut args = Cli::parse_from(["slugr", "file.txt"]);
+        args.defaults = env;
+        let options = args.options();
+        assert_eq!(options.style, Style::Snake);
+        assert!(options.keep_unicode);
+        assert_eq!(options.max_length, Some(20));
+        assert_eq!(args.rename_options().collision, CollisionSuffix::Hash);
+
+        let mut args = Cli::parse_from(["slugr", "--pascal", "--collision", "timestamp", "file.txt"]);
+        args.defaults = env;
+        assert_eq!(args.options().style, Style::Pascal);
+        assert_eq!(args.rename_options().collision, CollisionSuffix::Timestamp);
+    }
+
+    #[test]
+    fn test_preset_overrides_env_config() {
+        let env = EnvConfig {
+            style: Some(Style::Kebab),
+            keep_unicode: Some(true),
+            max_length: NonZeroUsize::new(20),
+            collision: Some(CollisionSuffix::Hash),
+        };
+
+        let mut args = Cli::parse_from(["slugr", "--preset", "id", "file.txt"]);
+        args.defaults = env;
+        assert_eq!(args.options(), SlugifyOptions::preset_identifier());
+        // Rename settings are not part of the preset
+        assert_eq!(args.rename_options().collision, CollisionSuffix::Hash);
+
+        let mut args = Cli::parse_from(["slugr", "--preset", "id", "--pascal", "file.txt"]);
+        args.defaults = env;
+        assert_eq!(args.options().style, Style::Pascal);
+        assert_eq!(args.options().max_length, Some(64));
+    }
+
+    #[test]
+    fn test_generate_completions_is_exclusive() {
+        let args = Cli::parse_from(["slugr", "--generate-completions", "zsh"]);
+        assert_eq!(args.generate_completions, Some(Shell::Zsh));
+        assert!(Cli::try_parse_from(["slugr", "--generate-completions", "tcsh"]).is_err());
+        assert!(Cli::try_parse_from(["slugr", "--generate-completions", "bash", "file.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_man_page_sections() {
+        let page = render_man_page();
+        assert!(page.starts_with(".TH slugr 1"), "{}", &page[..40]);
+        for section in [".SH NAME", ".SH SYNOPSIS", ".SH DESCRIPTION", ".SH OPTIONS", ".SH EXAMPLES", ".SH VERSION"] {
+            assert_eq!(page.matches(section).count(), 1, "{section}");
+        }
+        assert!(page.find(".SH EXAMPLES") < page.find(".SH VERSION"));
+        assert!(Cli::try_parse_from(["slugr", "--man", "-x"]).is_err());
+    }
+
+    #[test]
+    fn test_entry_filter_flags() {
+        assert_eq!(Cli::parse_from(["slugr", "dir"]).entry_filter(), EntryFilter::All);
+        assert_eq!(Cli::parse_from(["slugr", "-r", "--dirs-only", "dir"]).entry_filter(), EntryFilter::DirsOnly);
+        assert_eq!(Cli::parse_from(["slugr", "-r", "--files-only", "dir"]).entry_filter(), EntryFilter::FilesOnly);
+        assert!(Cli::try_parse_from(["slugr", "--dirs-only", "--files-only", "dir"]).is_err());
+    }
+
+    #[test]
+    fn test_max_collisions() {
+        assert_eq!(Cli::parse_from(["slugr", "f"]).rename_options().max_collisions, MAX_COLLISION_SUFFIX);
+        assert_eq!(Cli::parse_from(["slugr", "--max-collisions", "0", "f"]).rename_options().max_collisions, 0);
+        assert_eq!(Cli::parse_from(["slugr", "--max-collisions", "100000", "f"]).max_collisions, 100_000);
+        assert!(Cli::try_parse_from(["slugr", "--max-collisions", "100001", "f"]).is_err());
+        assert!(Cli::try_parse_from(["slugr", "--max-collisions", "-1", "f"]).is_err());
+    }
+
+    #[test]
+    fn test_collision_padding() {
+        assert_eq!(Cli::parse_from(["slugr", "f"]).rename_options().collision_suffix_padding, None);
+        let args = Cli::parse_from(["slugr", "--collision-padding", "3", "f"]);
+        assert_eq!(args.rename_options().collision_suffix_padding, Some(3));
+        assert!(Cli::try_parse_from(["slugr", "--collision-padding", "0", "f"]).is_err());
+        assert!(Cli::try_parse_from(["slugr", "--collision-padding", "3", "--clobber", "f"]).is_err());
+    }
 }
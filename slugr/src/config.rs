@@ -0,0 +1,111 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::env_config::{parse_collision, parse_style, EnvConfig, COLLISION_VALUES, STYLE_VALUES};
+
+/// The keys of `config.toml`: the same settings as the `SLUGR_*` variables.
+///
+/// ```toml
+/// style = "snake"
+/// keep_unicode = true
+/// max_length = 80
+/// collision = "hash"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    style: Option<String>,
+    keep_unicode: Option<bool>,
+    max_length: Option<NonZeroUsize>,
+    collision: Option<String>,
+}
+
+/// `$XDG_CONFIG_HOME/slugr/config.toml`, or `~/.config/slugr/config.toml`
+/// when `XDG_CONFIG_HOME` is unset or not absolute. `None` if the file
+/// does not exist.
+pub fn find_config_file() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+    let path = base.join("slugr").join("config.toml");
+    path.is_file().then_some(path)
+}
+
+/// Parse the contents of a config file.
+pub fn parse_config(text: &str) -> io::Result<EnvConfig> {
+    let file: ConfigFile = toml::from_str(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.message().to_string()))?;
+    let invalid = |key: &str, value: &str, expected: &str| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("invalid {key} value '{value}': expected {expected}"))
+    };
+
+    let style = file
+        .style
+        .map(|v| parse_style(&v).ok_or_else(|| invalid("style", &v, STYLE_VALUES)))
+        .transpose()?;
+    let collision = file
+        .collision
+        .map(|v| parse_collision(&v).ok_or_else(|| invalid("collision", &v, COLLISION_VALUES)))
+        .transpose()?;
+
+    Ok(EnvConfig { style, keep_unicode: file.keep_unicode, max_length: file.max_length, collision })
+}
+
+/// Read and parse the config file at `path`.
+pub fn read_config(path: &Path) -> io::Result<EnvConfig> {
+    parse_config(&fs::read_to_string(path)?)
+}
+
+/// Settings from the config file, if there is one. A file that cannot be
+/// read or parsed is reported and otherwise ignored.
+pub fn load_config() -> EnvConfig {
+    let Some(path) = find_config_file() else {
+        return EnvConfig::default();
+    };
+    read_config(&path).unwrap_or_else(|e| {
+        eprintln!("slugr: warning: ignoring '{}': {e}", path.display());
+        EnvConfig::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fileslug::Style;
+
+    use crate::rename::CollisionSuffix;
+
+    #[test]
+    fn test_parse_config_all_keys() {
+        let config = parse_config("style = \"snake\"\nkeep_unicode = true\nmax_length = 80\ncollision = \"hash\"\n").unwrap();
+        assert_eq!(config.style, Some(Style::Snake));
+        assert_eq!(config.keep_unicode, Some(true));
+        assert_eq!(config.max_length, NonZeroUsize::new(80));
+        assert_eq!(config.collision, Some(CollisionSuffix::Hash));
+    }
+
+    #[test]
+    fn test_parse_config_empty() {
+        assert_eq!(parse_config("").unwrap(), EnvConfig::default());
+        assert_eq!(parse_config("# just a comment\n").unwrap(), EnvConfig::default());
+    }
+
+    #[test]
+    fn test_parse_config_malformed() {
+        for text in ["style = ", "style = \"camel\"", "max_length = 0", "colour = \"red\"", "keep_unicode = \"yes\""] {
+            let err = parse_config(text).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_read_config_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_config(&dir.path().join("config.toml")).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
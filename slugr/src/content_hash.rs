@@ -0,0 +1,75 @@
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use clap::ValueEnum;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+/// Digest that `--rename-to-hash` names files by.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashAlgorithm {
+    /// SHA-256, 64 hex chars
+    #[default]
+    Sha256,
+    /// SHA-1, 40 hex chars
+    Sha1,
+    /// BLAKE3, 64 hex chars, and much faster on large files
+    Blake3,
+}
+
+/// Lowercase hex digest of the whole content of the file at `path`.
+pub fn hash_file(path: &Path, algorithm: HashAlgorithm) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    match algorithm {
+        HashAlgorithm::Sha256 => digest_hex::<Sha256>(&mut file),
+        HashAlgorithm::Sha1 => digest_hex::<Sha1>(&mut file),
+        HashAlgorithm::Blake3 => {
+            let mut hasher = blake3::Hasher::new();
+            io::copy(&mut file, &mut hasher)?;
+            Ok(hasher.finalize().to_hex().to_string())
+        }
+    }
+}
+
+fn digest_hex<D: Digest + io::Write>(file: &mut File) -> io::Result<String> {
+    let mut hasher = D::new();
+    io::copy(file, &mut hasher)?;
+    Ok(hasher.finalize().iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_hash_file_known_digests() {
+        let dir = tempfile::tempdir().unwrap();
+        let abc = dir.path().join("abc.txt");
+        let empty = dir.path().join("empty");
+        fs::write(&abc, "abc").unwrap();
+        fs::write(&empty, "").unwrap();
+
+        assert_eq!(
+            hash_file(&abc, HashAlgorithm::Sha256).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(hash_file(&abc, HashAlgorithm::Sha1).unwrap(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            hash_file(&empty, HashAlgorithm::Blake3).unwrap(),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+    }
+
+    #[test]
+    fn test_hash_file_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = hash_file(&dir.path().join("missing"), HashAlgorithm::Sha256).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
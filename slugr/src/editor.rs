@@ -0,0 +1,134 @@
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use fileslug::{slugify, SlugifyOptions};
+
+use crate::pairs::{self, Pair};
+
+const PLAN_HEADER: &str = "\
+# slugr: planned renames, one 'old<TAB>new' per line.
+# Change a target to rename it differently, or delete a line to skip it.
+# Lines starting with '#' are ignored.
+";
+
+/// The editor command: `$EDITOR`, then `$VISUAL`, then `vi`.
+fn editor_command() -> OsString {
+    ["EDITOR", "VISUAL"]
+        .into_iter()
+        .filter_map(env::var_os)
+        .find(|v| !v.is_empty())
+        .unwrap_or_else(|| "vi".into())
+}
+
+/// Write `plan` to a temporary file, open it in the user's editor, and read
+/// back the edited plan once the editor exits. Deleted lines drop out of the
+/// result. The editor command is run by the shell, so it may include
+/// arguments and quoting (`code --wait`).
+pub fn open_editor_with_plan(plan: &[Pair]) -> io::Result<Vec<Pair>> {
+    // Created with a random name and owner-only permissions, never through an existing file
+    let mut file = tempfile::Builder::new().prefix("slugr-plan-").suffix(".tsv").tempfile()?;
+    write!(file, "{PLAN_HEADER}{}", pairs::format_pairs(plan))?;
+    file.flush()?;
+
+    edit_file(file.path())?;
+    pairs::read_pairs(file.path())
+}
+
+fn edit_file(file: &Path) -> io::Result<()> {
+    let editor = editor_command();
+    let editor = editor.to_string_lossy();
+
+    let status = shell_command(&editor, file).status().map_err(|e| {
+        io::Error::new(e.kind(), format!("cannot run editor '{editor}': {e}"))
+    })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("editor '{editor}' exited with {status}")))
+    }
+}
+
+/// `editor` run by the shell on `file`, as git runs `$EDITOR`. The path is
+/// passed as a positional argument rather than spliced into the command.
+#[cfg(unix)]
+fn shell_command(editor: &str, file: &Path) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(format!("{editor} \"$1\"")).arg("sh").arg(file);
+    command
+}
+
+#[cfg(not(unix))]
+fn shell_command(editor: &str, file: &Path) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(editor).arg(file);
+    command
+}
+
+/// The renames for `paths` whose names change when slugified.
+pub fn plan_renames(paths: impl IntoIterator<Item = PathBuf>, options: &SlugifyOptions) -> Vec<Pair> {
+    paths
+        .into_iter()
+        .filter(|path| pairs::is_representable(path))
+        .filter_map(|path| {
+            let filename = path.file_name()?.to_string_lossy().into_owned();
+            let new_name = slugify(&filename, options);
+            if *new_name == *filename || new_name.is_empty() || *new_name == *"." || *new_name == *".." {
+                return None;
+            }
+            let target = path.parent().unwrap_or(Path::new(".")).join(&*new_name);
+            Some((path, target))
+        })
+        .collect()
+}
+
+/// Check an edited rename: the source must still exist and the target's
+/// name must already be a slug under `options`.
+pub fn check_pair((from, to): &Pair, options: &SlugifyOptions) -> Result<(), String> {
+    if from.symlink_metadata().is_err() {
+        return Err(format!("'{}' does not exist", from.display()));
+    }
+    let Some(name) = to.file_name().map(|n| n.to_string_lossy()) else {
+        return Err(format!("'{}' is not a valid target", to.display()));
+    };
+    if slugify(&name, options) != name {
+        return Err(format!("'{name}' is not a valid slug"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_plan_renames_skips_unchanged() {
+        let paths = ["dir/My File.txt", "dir/clean.txt"].map(PathBuf::from);
+        let plan = plan_renames(paths, &SlugifyOptions::default());
+        assert_eq!(plan, vec![(PathBuf::from("dir/My File.txt"), PathBuf::from("dir/my-file.txt"))]);
+    }
+
+    #[test]
+    fn test_plan_file_round_trips() {
+        let plan = vec![(PathBuf::from("a/My File.txt"), PathBuf::from("a/my-file.txt"))];
+        let text = format!("{PLAN_HEADER}{}", pairs::format_pairs(&plan));
+        assert_eq!(pairs::parse_pairs(&text).unwrap(), plan);
+    }
+
+    #[test]
+    fn test_check_pair() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("My File.txt");
+        fs::write(&from, "").unwrap();
+        let opts = SlugifyOptions::default();
+
+        assert_eq!(check_pair(&(from.clone(), dir.path().join("notes.txt")), &opts), Ok(()));
+        assert!(check_pair(&(from.clone(), dir.path().join("Not A Slug.txt")), &opts).unwrap_err().contains("not a valid slug"));
+        assert!(check_pair(&(from, dir.path().join("..")), &opts).is_err());
+        assert!(check_pair(&(dir.path().join("gone.txt"), dir.path().join("x.txt")), &opts).unwrap_err().contains("does not exist"));
+    }
+}
@@ -0,0 +1,228 @@
+use std::env;
+use std::fmt;
+use std::num::NonZeroUsize;
+
+use clap::ValueEnum;
+
+use fileslug::{SlugifyOptionsOverride, Style};
+
+use crate::rename::CollisionSuffix;
+
+/// Settings read from `SLUGR_*` environment variables. Each one stands in
+/// for the matching flag; flags given on the command line take precedence.
+///
+/// - `SLUGR_STYLE`: `kebab`, `snake`, or `pascal` (like `--snake` / `--pascal`)
+/// - `SLUGR_KEEP_UNICODE`: `1`/`true`/`yes` or `0`/`false`/`no` (like `--keep-unicode`)
+/// - `SLUGR_MAX_LENGTH`: maximum slug length in bytes, at least 1
+/// - `SLUGR_COLLISION`: `counter`, `timestamp`, or `hash` (like `--collision`)
+///
+/// Unset and empty variables are ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnvConfig {
+    pub style: Option<Style>,
+    pub keep_unicode: Option<bool>,
+    pub max_length: Option<NonZeroUsize>,
+    pub collision: Option<CollisionSuffix>,
+}
+
+/// A `SLUGR_*` variable with a value that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvConfigError {
+    pub var: &'static str,
+    pub value: String,
+    pub expected: &'static str,
+}
+
+impl fmt::Display for EnvConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} value '{}': expected {}", self.var, self.value, self.expected)
+    }
+}
+
+impl std::error::Error for EnvConfigError {}
+
+impl EnvConfig {
+    /// Read the `SLUGR_*` variables from the process environment.
+    pub fn load() -> Result<Self, EnvConfigError> {
+        Self::from_lookup(|var| env::var(var).ok())
+    }
+
+    /// Read the variables through `lookup`, which returns a variable's value if set.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, EnvConfigError> {
+        let get = |var: &str| lookup(var).filter(|v| !v.is_empty());
+
+        let style = get("SLUGR_STYLE")
+            .map(|v| parse_style(&v).ok_or_else(|| invalid("SLUGR_STYLE", v, STYLE_VALUES)))
+            .transpose()?;
+
+        let keep_unicode = get("SLUGR_KEEP_UNICODE")
+            .map(|v| match v.to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" => Ok(true),
+                "0" | "false" | "no" => Ok(false),
+                _ => Err(invalid("SLUGR_KEEP_UNICODE", v, "1, true, yes, 0, false, or no")),
+            })
+            .transpose()?;
+
+        let max_length = get("SLUGR_MAX_LENGTH")
+            .map(|v| v.trim().parse().map_err(|_| invalid("SLUGR_MAX_LENGTH", v, "a positive integer")))
+            .transpose()?;
+
+        let collision = get("SLUGR_COLLISION")
+            .map(|v| parse_collision(&v).ok_or_else(|| invalid("SLUGR_COLLISION", v, COLLISION_VALUES)))
+            .transpose()?;
+
+        Ok(Self { style, keep_unicode, max_length, collision })
+    }
+
+    /// These settings, with any unset ones taken from `fallback`.
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            style: self.style.or(fallback.style),
+            keep_unicode: self.keep_unicode.or(fallback.keep_unicode),
+            max_length: self.max_length.or(fallback.max_length),
+            collision: self.collision.or(fallback.collision),
+        }
+    }
+
+    /// The slugify settings among these, to merge over a preset.
+    pub fn slugify_overrides(&self) -> SlugifyOptionsOverride {
+        SlugifyOptionsOverride {
+            style: self.style,
+            keep_unicode: self.keep_unicode,
+            max_length: self.max_length.map(|n| Some(n.get())),
+            ..SlugifyOptionsOverride::default()
+        }
+    }
+}
+
+/// The accepted style names, for error messages.
+pub const STYLE_VALUES: &str = "kebab, snake, or pascal";
+
+/// The accepted collision suffix names, for error messages.
+pub const COLLISION_VALUES: &str = "counter, timestamp, or hash";
+
+/// Parse a style name, ignoring case.
+pub fn parse_style(value: &str) -> Option<Style> {
+    match value.to_ascii_lowercase().as_str() {
+        "kebab" => Some(Style::Kebab),
+        "snake" => Some(Style::Snake),
+        "pascal" => Some(Style::Pascal),
+        _ => None,
+    }
+}
+
+/// Parse a collision suffix name, ignoring case.
+pub fn parse_collision(value: &str) -> Option<CollisionSuffix> {
+    CollisionSuffix::from_str(value, true).ok()
+}
+
+fn invalid(var: &'static str, value: String, expected: &'static str) -> EnvConfigError {
+    EnvConfigError { var, value, expected }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Serializes tests that modify the process environment.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn parse(vars: &[(&str, &str)]) -> Result<EnvConfig, EnvConfigError> {
+        let vars: HashMap<String, String> = vars.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect();
+        EnvConfig::from_lookup(|var| vars.get(var).cloned())
+    }
+
+    #[test]
+    fn test_empty_environment() {
+        assert_eq!(parse(&[]).unwrap(), EnvConfig::default());
+        assert_eq!(parse(&[("SLUGR_STYLE", "")]).unwrap(), EnvConfig::default());
+    }
+
+    #[test]
+    fn test_all_variables() {
+        let config = parse(&[
+            ("SLUGR_STYLE", "Snake"),
+            ("SLUGR_KEEP_UNICODE", "yes"),
+            ("SLUGR_MAX_LENGTH", "40"),
+            ("SLUGR_COLLISION", "hash"),
+        ])
+        .unwrap();
+        assert_eq!(config.style, Some(Style::Snake));
+        assert_eq!(config.keep_unicode, Some(true));
+        assert_eq!(config.max_length, NonZeroUsize::new(40));
+        assert_eq!(config.collision, Some(CollisionSuffix::Hash));
+    }
+
+    #[test]
+    fn test_keep_unicode_values() {
+        for (value, expected) in [("1", true), ("TRUE", true), ("0", false), ("no", false)] {
+            assert_eq!(parse(&[("SLUGR_KEEP_UNICODE", value)]).unwrap().keep_unicode, Some(expected), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        for (var, value) in [
+            ("SLUGR_STYLE", "camel"),
+            ("SLUGR_KEEP_UNICODE", "maybe"),
+            ("SLUGR_MAX_LENGTH", "0"),
+            ("SLUGR_MAX_LENGTH", "-5"),
+            ("SLUGR_COLLISION", "random"),
+        ] {
+            let err = parse(&[(var, value)]).unwrap_err();
+            assert_eq!(err.var, var);
+            assert_eq!(err.value, value);
+        }
+    }
+
+    #[test]
+    fn test_or_prefers_self() {
+        let env = EnvConfig { style: Some(Style::Snake), ..EnvConfig::default() };
+        let file = EnvConfig { style: Some(Style::Pascal), keep_unicode: Some(true), ..EnvConfig::default() };
+        let merged = env.or(file);
+        assert_eq!(merged.style, Some(Style::Snake));
+        assert_eq!(merged.keep_unicode, Some(true));
+    }
+
+    #[test]
+    fn test_slugify_overrides() {
+        assert_eq!(EnvConfig::default().slugify_overrides(), SlugifyOptionsOverride::default());
+        let config = EnvConfig { max_length: NonZeroUsize::new(30), collision: Some(CollisionSuffix::Hash), ..EnvConfig::default() };
+        let overrides = config.slugify_overrides();
+        assert_eq!(overrides.max_length, Some(Some(30)));
+        assert_eq!(overrides.style, None);
+    }
+
+    #[test]
+    fn test_error_message() {
+        let err = parse(&[("SLUGR_STYLE", "camel")]).unwrap_err();
+        assert_eq!(err.to_string(), "invalid SLUGR_STYLE value 'camel': expected kebab, snake, or pascal");
+    }
+
+    #[test]
+    fn test_load_from_process_environment() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        env::set_var("SLUGR_STYLE", "pascal");
+        env::set_var("SLUGR_MAX_LENGTH", "12");
+        let config = EnvConfig::load();
+        env::remove_var("SLUGR_STYLE");
+        env::remove_var("SLUGR_MAX_LENGTH");
+
+        let config = config.unwrap();
+        assert_eq!(config.style, Some(Style::Pascal));
+        assert_eq!(config.max_length, NonZeroUsize::new(12));
+    }
+
+    #[test]
+    fn test_load_reports_invalid_process_environment() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        env::set_var("SLUGR_COLLISION", "sometimes");
+        let result = EnvConfig::load();
+        env::remove_var("SLUGR_COLLISION");
+
+        assert_eq!(result.unwrap_err().var, "SLUGR_COLLISION");
+    }
+}
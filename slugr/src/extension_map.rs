@@ -0,0 +1,151 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use fileslug::{split_extension, DEFAULT_EXTENSION_ALIASES};
+
+/// Canonical extensions for `--extension-map`, keyed by lowercase alias.
+///
+/// The file is TOML with one alias per line:
+///
+/// ```toml
+/// jpeg = "jpg"
+/// htm = "html"
+/// ```
+///
+/// Either side may have a leading dot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionMap {
+    map: HashMap<String, String>,
+}
+
+/// An `--extension-map` file that could not be read or parsed.
+#[derive(Debug)]
+pub enum ExtensionMapError {
+    Read(io::Error),
+    Parse(String),
+    /// A canonical extension that is empty or contains a path separator.
+    InvalidExtension { alias: String, extension: String },
+}
+
+impl fmt::Display for ExtensionMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtensionMapError::Read(e) => write!(f, "{e}"),
+            ExtensionMapError::Parse(message) => write!(f, "{message}"),
+            ExtensionMapError::InvalidExtension { alias, extension } => {
+                write!(f, "invalid extension '{extension}' for '{alias}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExtensionMapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExtensionMapError::Read(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl ExtensionMap {
+    /// The built-in aliases, such as `jpeg` → `jpg` and `htm` → `html`, from
+    /// [`DEFAULT_EXTENSION_ALIASES`].
+    pub fn common_aliases() -> Self {
+        let map = DEFAULT_EXTENSION_ALIASES.iter().map(|&(alias, ext)| (alias.to_string(), format!(".{ext}"))).collect();
+        ExtensionMap { map }
+    }
+
+    /// Read the map file at `path`. Its entries add to, or override, the
+    /// [common aliases](Self::common_aliases).
+    pub fn load(path: &Path) -> Result<Self, ExtensionMapError> {
+        Self::parse(&fs::read_to_string(path).map_err(ExtensionMapError::Read)?)
+    }
+
+    fn parse(text: &str) -> Result<Self, ExtensionMapError> {
+        let entries: HashMap<String, String> =
+            toml::from_str(text).map_err(|e| ExtensionMapError::Parse(e.message().to_string()))?;
+
+        let mut extension_map = Self::common_aliases();
+        for (alias, extension) in entries {
+            let canonical = extension.strip_prefix('.').unwrap_or(&extension);
+            if canonical.is_empty() || canonical.contains(['/', '\\', '\0']) {
+                return Err(ExtensionMapError::InvalidExtension { alias, extension });
+            }
+            let alias = alias.strip_prefix('.').unwrap_or(&alias).to_lowercase();
+            extension_map.map.insert(alias, format!(".{canonical}"));
+        }
+        Ok(extension_map)
+    }
+
+    /// The canonical form of `ext` (with its leading dot, as returned by
+    /// [`split_extension`]), or `ext` itself if it is not an alias.
+    pub fn normalize<'a>(&'a self, ext: &'a str) -> &'a str {
+        let Some(alias) = ext.strip_prefix('.') else {
+            return ext;
+        };
+        self.map.get(&alias.to_lowercase()).map_or(ext, String::as_str)
+    }
+
+    /// `name` with its extension replaced by the canonical one.
+    pub fn apply<'a>(&self, name: Cow<'a, str>) -> Cow<'a, str> {
+        let (base, ext) = split_extension(&name);
+        let canonical = self.normalize(ext);
+        if canonical == ext {
+            return name;
+        }
+        Cow::Owned(format!("{base}{canonical}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_case_insensitive() {
+        let map = ExtensionMap::common_aliases();
+        assert_eq!(map.normalize(".JPEG"), ".jpg");
+        assert_eq!(map.normalize(".jpeg"), ".jpg");
+        assert_eq!(map.normalize(".Htm"), ".html");
+        assert_eq!(map.normalize(".png"), ".png");
+        assert_eq!(map.normalize(""), "");
+    }
+
+    #[test]
+    fn test_apply() {
+        let map = ExtensionMap::common_aliases();
+        assert_eq!(map.apply("beach-day.JPEG".into()), "beach-day.jpg");
+        assert_eq!(map.apply("index.htm".into()), "index.html");
+        assert!(matches!(map.apply("notes.txt".into()), Cow::Borrowed("notes.txt")));
+        assert!(matches!(map.apply("jpeg".into()), Cow::Borrowed("jpeg")));
+    }
+
+    #[test]
+    fn test_parse_adds_and_overrides() {
+        let map = ExtensionMap::parse("tiff = \"tif\"\n\".MOV\" = \".mp4\"\n").unwrap();
+        assert_eq!(map.normalize(".tiff"), ".tif");
+        assert_eq!(map.normalize(".mov"), ".mp4");
+        assert_eq!(map.normalize(".JPEG"), ".jpg");
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        for text in ["jpeg = ", "jpeg = 1", "jpeg = \"\"", "jpeg = \".\"", "jpeg = \"a/b\""] {
+            assert!(ExtensionMap::parse(text).is_err(), "{text}");
+        }
+        let err = ExtensionMap::parse("htm = \"\"").unwrap_err();
+        assert_eq!(err.to_string(), "invalid extension '' for 'htm'");
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = ExtensionMap::load(&dir.path().join("map.toml")).unwrap_err();
+        assert!(matches!(err, ExtensionMapError::Read(ref e) if e.kind() == io::ErrorKind::NotFound));
+    }
+}
@@ -0,0 +1,124 @@
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::rename::RenameResult;
+
+/// One line of the `--log-jsonl` file.
+#[derive(Debug, Serialize)]
+struct LogRecord<'a> {
+    /// Unix time in seconds when the rename finished
+    ts: u64,
+    from: Cow<'a, str>,
+    /// `None` for a failed rename
+    to: Option<Cow<'a, str>>,
+    /// `ok` or `failed`
+    status: &'static str,
+    /// How long the rename took
+    ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Appends a JSON object per rename to the `--log-jsonl` file as each one
+/// completes, instead of keeping them all like `RenameJournal`.
+pub struct JsonlLogger {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl JsonlLogger {
+    /// Open `path` for appending, creating it if needed.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(JsonlLogger { path: path.to_path_buf(), writer: BufWriter::new(file) })
+    }
+
+    /// The file being written.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write a line for `result`, which took `elapsed`. Names already clean
+    /// are not logged.
+    pub fn log(&mut self, result: &RenameResult, elapsed: Duration) -> io::Result<()> {
+        let (from, to, error) = match result {
+            RenameResult::Renamed { from, to } | RenameResult::MovedViaCopy { from, to } | RenameResult::Linked { from, to } => {
+                (from, Some(to), None)
+            }
+            RenameResult::Failed { path, error } => (path, None, Some(error.to_string())),
+            RenameResult::Skipped(_) => return Ok(()),
+        };
+        let record = LogRecord {
+            ts: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs()),
+            from: from.to_string_lossy(),
+            to: to.map(|to| to.to_string_lossy()),
+            status: if error.is_some() { "failed" } else { "ok" },
+            ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            error,
+        };
+        serde_json::to_writer(&mut self.writer, &record)?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// Write out any buffered lines.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_log_writes_one_object_per_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("renames.jsonl");
+        let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        let mut logger = JsonlLogger::open(&log).unwrap();
+        let renamed = RenameResult::Renamed { from: PathBuf::from("/a/My File.txt"), to: PathBuf::from("/a/my-file.txt") };
+        let failed =
+            RenameResult::Failed { path: PathBuf::from("/a/Gone.txt"), error: io::Error::from(io::ErrorKind::NotFound) };
+        logger.log(&renamed, Duration::from_millis(3)).unwrap();
+        logger.log(&RenameResult::Skipped(PathBuf::from("/a/clean.txt")), Duration::ZERO).unwrap();
+        logger.log(&failed, Duration::ZERO).unwrap();
+        logger.flush().unwrap();
+
+        let text = fs::read_to_string(&log).unwrap();
+        let lines: Vec<serde_json::Value> = text.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["from"], "/a/My File.txt");
+        assert_eq!(lines[0]["to"], "/a/my-file.txt");
+        assert_eq!(lines[0]["status"], "ok");
+        assert_eq!(lines[0]["ms"], 3);
+        assert!(lines[0]["ts"].as_u64().unwrap() >= before);
+        assert!(lines[0].get("error").is_none());
+        assert_eq!(lines[1]["from"], "/a/Gone.txt");
+        assert!(lines[1]["to"].is_null());
+        assert_eq!(lines[1]["status"], "failed");
+        assert_eq!(lines[1]["error"], "entity not found");
+    }
+
+    #[test]
+    fn test_open_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("renames.jsonl");
+        fs::write(&log, "{}\n").unwrap();
+
+        let mut logger = JsonlLogger::open(&log).unwrap();
+        let renamed = RenameResult::Linked { from: PathBuf::from("A.txt"), to: PathBuf::from("a.txt") };
+        logger.log(&renamed, Duration::ZERO).unwrap();
+        logger.flush().unwrap();
+
+        let text = fs::read_to_string(&log).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.starts_with("{}\n{\"ts\":"), "got: {text}");
+    }
+}
@@ -1,142 +1,1098 @@
+mod batch;
+mod changelog;
+mod check;
 mod cli;
+mod config;
+mod content_hash;
+mod editor;
+mod env_config;
+mod extension_map;
+mod logger;
+mod metadata;
+mod pairs;
+mod pipe;
 mod rename;
+#[cfg(feature = "tui")]
+mod tui;
 mod walk;
 
-#[cfg(test)]
-mod fixtures;
-
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
 use std::io::{self, BufRead, IsTerminal, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use clap::Parser;
 
+use batch::process_batch;
+use check::format_check_report;
 use cli::Cli;
-use rename::{rename_file, RenameResult};
-use fileslug::{slugify, slugify_string, SlugifyOptions};
-use walk::collect_paths;
-
-/// Pipe mode: read lines from stdin, slugify each, write to stdout.
-fn run_pipe(options: &SlugifyOptions, raw: bool) -> ExitCode {
-    let stdout = io::stdout();
-    let mut out = io::BufWriter::new(stdout.lock());
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let line = match line {
-            Ok(l) => l,
+use content_hash::{hash_file, HashAlgorithm};
+use env_config::EnvConfig;
+use extension_map::ExtensionMap;
+use logger::JsonlLogger;
+use pipe::run_pipe;
+use rename::{
+    atomic_rename_batch, collect_rename_conflicts, content_hash_name, rename_file_with_progress, CaseCollisionDetector,
+    RenameJournal, RenameResult, RenameStats,
+};
+use fileslug::{
+    edit_distance, explain_slug, fill_template, format_slug_diff, format_slug_trace, load_compound_extensions_file,
+    slug_diff, slugify, split_extension, take_diagnostics, truncate_base, SlugDiagnostic, SlugifyOptions,
+};
+use walk::walk_paths;
+
+/// Input paths come from the command line, or from stdin (one per line) when piped.
+/// Returns `None` if there are no arguments and stdin is a terminal.
+fn read_input_paths(files: &[PathBuf], from_null: bool) -> Option<Vec<PathBuf>> {
+    if !files.is_empty() {
+        Some(files.to_vec())
+    } else if !io::stdin().is_terminal() {
+        let separator = if from_null { b'\0' } else { b'\n' };
+        Some(read_separated(&mut io::stdin().lock(), separator).map(PathBuf::from).collect())
+    } else {
+        None
+    }
+}
+
+/// Read paths separated by `separator`: a newline, or NUL (as written by
+/// `find -print0`) so names may contain newlines. A `\r` before a newline is
+/// dropped. Empty records are skipped, and records that aren't valid UTF-8 are
+/// skipped with a warning.
+fn read_separated(reader: &mut impl BufRead, separator: u8) -> impl Iterator<Item = String> + '_ {
+    reader
+        .split(separator)
+        .map_while(Result::ok)
+        .map(move |mut record| {
+            if separator == b'\n' && record.last() == Some(&b'\r') {
+                record.pop();
+            }
+            record
+        })
+        .filter(|record| !record.is_empty())
+        .filter_map(|record| match String::from_utf8(record) {
+            Ok(path) => Some(path),
             Err(e) => {
-                eprintln!("slugr: read error: {e}");
-                return ExitCode::FAILURE;
+                eprintln!(
+                    "slugr: warning: skipping input path that is not valid UTF-8: {}",
+                    String::from_utf8_lossy(e.as_bytes())
+                );
+                None
             }
+        })
+}
+
+/// Slugify `filename`, then give it its canonical extension under `--extension-map`.
+fn slug_name<'a>(filename: &'a str, options: &SlugifyOptions, extension_map: Option<&ExtensionMap>) -> Cow<'a, str> {
+    let slug = slugify(filename, options);
+    match extension_map {
+        Some(map) => map.apply(slug),
+        None => slug,
+    }
+}
+
+/// Whether slugifying the last name reported it already clean under `--warn-unchanged`.
+fn take_already_clean() -> bool {
+    take_diagnostics().iter().any(|diagnostic| matches!(diagnostic, SlugDiagnostic::Unchanged { .. }))
+}
+
+/// `new_name` with the hash of `path`'s content appended, under `--content-hash`.
+/// Directories and symlinks keep `new_name`.
+fn with_content_hash<'a>(path: &Path, new_name: Cow<'a, str>, length: Option<usize>) -> io::Result<Cow<'a, str>> {
+    let Some(length) = length else {
+        return Ok(new_name);
+    };
+    Ok(match content_hash_name(path, &new_name, length)? {
+        Some(name) => Cow::Owned(name),
+        None => new_name,
+    })
+}
+
+/// The `--suffix-digits` counter, shared by every rename in a run.
+#[derive(Debug, Clone)]
+struct GlobalCounter {
+    next: Arc<AtomicU64>,
+    digits: usize,
+    /// Byte limit for numbered names
+    max_bytes: usize,
+}
+
+impl GlobalCounter {
+    fn new(start: u64, digits: usize, max_bytes: usize) -> Self {
+        GlobalCounter { next: Arc::new(AtomicU64::new(start)), digits, max_bytes }
+    }
+
+    /// `name` with the next count, zero-padded to `digits`, appended to its
+    /// base name: `scan.pdf` → `scan-001.pdf`. Returns `None`, without using
+    /// up a count, if the base name already ends in `-` and exactly `digits`
+    /// digits, so that a rerun doesn't stack another counter on each name.
+    fn apply(&self, name: &str) -> Option<String> {
+        // A pure dotfile (e.g. ".bashrc") gets the count after the name, as collision suffixes do
+        let (stem, ext) = match split_extension(name) {
+            ("", ext) => (ext, ""),
+            split => split,
         };
-        if line.is_empty() {
-            continue;
+        let numbered = stem
+            .rsplit_once('-')
+            .is_some_and(|(_, n)| n.len() == self.digits && n.bytes().all(|b| b.is_ascii_digit()));
+        if numbered {
+            return None;
+        }
+
+        let count = self.next.fetch_add(1, Ordering::Relaxed);
+        let tail = format!("-{count:0width$}{ext}", width = self.digits);
+        Some(format!("{}{tail}", truncate_base(stem, &tail, self.max_bytes)))
+    }
+}
+
+/// Whether `filename` keeps its name under `--min-change`: `new_name` is
+/// fewer than `min_change` edits away from it.
+fn below_min_change(filename: &str, new_name: &str, min_change: Option<usize>) -> bool {
+    min_change.is_some_and(|min| edit_distance(filename, new_name) < min)
+}
+
+/// `new_name`, or `filename` itself if they are fewer than `--min-change`
+/// edits apart.
+fn with_min_change<'a>(filename: &'a str, new_name: Cow<'a, str>, min_change: Option<usize>) -> Cow<'a, str> {
+    if below_min_change(filename, &new_name, min_change) {
+        Cow::Borrowed(filename)
+    } else {
+        new_name
+    }
+}
+
+/// The name `path` would be renamed to: its slug, with `--extension-map`,
+/// `--content-hash` and the `--suffix-digits` counter applied, or its current
+/// name under `--min-change`. Prints the error and returns `None` if there is
+/// no usable name.
+fn planned_name<'a>(
+    path: &Path,
+    filename: &'a str,
+    args: &Cli,
+    options: &SlugifyOptions,
+    extension_map: Option<&ExtensionMap>,
+    counter: Option<&GlobalCounter>,
+) -> Option<Cow<'a, str>> {
+    let new_name = slug_name(filename, options, extension_map);
+    let already_clean = take_already_clean();
+
+    // Empty/dot slugs would resolve to the parent directory
+    if new_name.is_empty() || *new_name == *"." || *new_name == *".." {
+        eprintln!("slugr: cannot rename '{}': slugified name is invalid", path.display());
+        return None;
+    }
+
+    let new_name = match with_content_hash(path, new_name, args.content_hash_length()) {
+        Ok(name) => name,
+        Err(e) => {
+            eprintln!("slugr: cannot hash '{}': {e}", path.display());
+            return None;
+        }
+    };
+
+    // A name kept by --min-change is not numbered either
+    let new_name = if below_min_change(filename, &new_name, args.min_change) {
+        Cow::Borrowed(filename)
+    } else {
+        counter.and_then(|counter| counter.apply(&new_name)).map_or(new_name, Cow::Owned)
+    };
+
+    // Only once nothing else (a hash, a counter) changes the name either
+    if already_clean && new_name == filename {
+        eprintln!("slugr: warning: '{}' is already clean", path.display());
+    }
+    Some(new_name)
+}
+
+/// Explain mode: print the slugification trace for each path.
+fn run_explain(paths: impl IntoIterator<Item = PathBuf>, options: &SlugifyOptions) -> ExitCode {
+    for path in paths {
+        if let Some(filename) = path.file_name() {
+            println!("{}", path.display());
+            print!("{}", format_slug_trace(&explain_slug(&filename.to_string_lossy(), options)));
         }
-        let slugified = if raw {
-            slugify_string(&line, options)
+    }
+    ExitCode::SUCCESS
+}
+
+/// Diff mode: print what slugification changes in each path's name.
+fn run_diff(
+    paths: impl IntoIterator<Item = PathBuf>,
+    options: &SlugifyOptions,
+    extension_map: Option<&ExtensionMap>,
+) -> ExitCode {
+    for path in paths {
+        if let Some(filename) = path.file_name() {
+            let filename = filename.to_string_lossy();
+            println!("{}", format_slug_diff(&slug_diff(&filename, &slug_name(&filename, options, extension_map))));
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Interactive mode: ask before renaming `path` to `target`.
+fn confirm(path: &Path, target: &Path) -> bool {
+    eprint!("slugr: rename '{}' -> '{}'? [y/N] ", path.display(), target.display());
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).ok();
+    answer.trim().eq_ignore_ascii_case("y")
+}
+
+/// `--check-conflicts`: list the sets of `paths` that would be renamed to the
+/// same target. Returns false if there are any and, when executing, the user
+/// declines to proceed. A dry run only lists them, as it already shows the
+/// suffix each would get.
+fn review_conflicts(paths: &[PathBuf], args: &Cli, options: &SlugifyOptions, extension_map: Option<&ExtensionMap>) -> bool {
+    let plans: Vec<pairs::Pair> = paths
+        .iter()
+        .filter(|path| !(args.link && path.is_dir()))
+        .filter_map(|path| {
+            let filename = path.file_name()?.to_string_lossy();
+            let new_name = slug_name(&filename, options, extension_map);
+            // Warnings and errors are reported when renaming
+            let _ = take_diagnostics();
+            if new_name.is_empty() || *new_name == *"." || *new_name == *".." {
+                return None;
+            }
+            let new_name = with_content_hash(path, new_name, args.content_hash_length()).ok()?;
+            let new_name = with_min_change(&filename, new_name, args.min_change);
+            let parent = match &args.to_dir {
+                Some(dir) => dir.as_path(),
+                None => path.parent().unwrap_or(Path::new(".")),
+            };
+            Some((path.clone(), parent.join(&*new_name)))
+        })
+        .collect();
+
+    let conflicts = collect_rename_conflicts(&plans);
+    if conflicts.is_empty() {
+        return true;
+    }
+    for conflict in &conflicts {
+        if conflict.target_exists {
+            eprintln!("slugr: conflict: '{}' already exists and is the target of:", conflict.target.display());
         } else {
-            slugify(&line, options).into_owned()
-        };
-        if slugified.is_empty() {
-            eprintln!("slugr: warning: '{line}' slugifies to empty");
+            eprintln!("slugr: conflict: {} files would be renamed to '{}':", conflict.sources.len(), conflict.target.display());
+        }
+        for source in &conflict.sources {
+            eprintln!("  {}", source.display());
+        }
+    }
+    if !args.execute {
+        return true;
+    }
+
+    let noun = if conflicts.len() == 1 { "conflict" } else { "conflicts" };
+    eprint!("slugr: proceed with {} {noun}? [y/N] ", conflicts.len());
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).ok();
+    answer.trim().eq_ignore_ascii_case("y")
+}
+
+/// What `report` prints for a rename that succeeded. Failures are always printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Show {
+    Nothing,
+    /// `old -> new` lines
+    Renames,
+    /// Each new path followed by a NUL byte (`--print0`)
+    NewPaths0,
+}
+
+impl Show {
+    /// `--print0` output if requested, otherwise `old -> new` lines when `verbose`.
+    fn new(args: &Cli, verbose: bool) -> Self {
+        if args.print0 {
+            Show::NewPaths0
+        } else if verbose {
+            Show::Renames
+        } else {
+            Show::Nothing
+        }
+    }
+}
+
+/// Write `path` followed by a NUL byte to stdout.
+fn print0(path: &Path) {
+    let mut stdout = io::stdout().lock();
+    // A closed pipe (e.g. `| head -z`) shouldn't stop the renames
+    let _ = stdout.write_all(path.as_os_str().as_encoded_bytes()).and_then(|()| stdout.write_all(b"\0"));
+}
+
+/// Print the outcome of a rename. Returns true if it failed.
+fn report(result: &RenameResult, show: Show) -> bool {
+    match result {
+        RenameResult::Renamed { from, to } | RenameResult::Linked { from, to } => {
+            match show {
+                Show::Nothing => {}
+                Show::Renames => println!("{} -> {}", from.display(), to.display()),
+                Show::NewPaths0 => print0(to),
+            }
+            false
+        }
+        RenameResult::MovedViaCopy { from, to } => {
+            match show {
+                Show::Nothing => {}
+                Show::Renames => println!("{} -> {} (copied across filesystems)", from.display(), to.display()),
+                Show::NewPaths0 => print0(to),
+            }
+            false
+        }
+        RenameResult::Skipped(_) => false,
+        RenameResult::Failed { path, error } => {
+            eprintln!("slugr: error renaming '{}': {}", path.display(), error);
+            true
+        }
+    }
+}
+
+/// Write the `--changelog` file. Returns true if it failed.
+fn write_changelog(journal: &RenameJournal, file: &Path, format: changelog::ChangelogFormat) -> bool {
+    match changelog::write_changelog(journal, file, format) {
+        Ok(()) => false,
+        Err(e) => {
+            eprintln!("slugr: cannot write '{}': {e}", file.display());
+            true
+        }
+    }
+}
+
+/// Atomic mode: apply all renames or none. Returns true if it failed.
+fn run_atomic(ops: &[pairs::Pair], options: &rename::RenameOptions, show: Show, on_progress: impl Fn(&RenameResult)) -> bool {
+    match atomic_rename_batch(ops, options) {
+        Ok(results) => {
+            for result in &results {
+                on_progress(result);
+                report(result, show);
+            }
+            false
+        }
+        Err(e) => {
+            eprintln!("slugr: error renaming {e}");
+            if e.partial_rollback_failures.is_empty() {
+                eprintln!("slugr: no files were renamed");
+            }
+            for (path, error) in &e.partial_rollback_failures {
+                eprintln!("slugr: error: could not restore '{}': {error}", path.display());
+            }
+            true
+        }
+    }
+}
+
+/// Print the `--stats` summary.
+fn print_stats(stats: &RenameStats, dry_run: bool) {
+    if dry_run {
+        eprintln!("slugr: dry-run: {stats}");
+    } else {
+        eprintln!("slugr: {stats}");
+    }
+}
+
+/// Pairs mode: apply pre-computed `old<TAB>new` renames from a file.
+fn run_from_pairs(file: &Path, args: &Cli) -> ExitCode {
+    let pairs = match pairs::read_pairs(file) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("slugr: cannot read '{}': {e}", file.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    apply_pairs(&pairs, args)
+}
+
+/// Apply `old<TAB>new` renames, honoring dry-run, interactive, and atomic modes.
+fn apply_pairs(pairs: &[pairs::Pair], args: &Cli) -> ExitCode {
+    let rename_options = args.rename_options();
+    let dry_run = rename_options.dry_run;
+    if dry_run {
+        eprintln!("slugr: dry-run mode (use -x to execute)");
+    }
+
+    let mut had_error = false;
+    let mut atomic_ops = Vec::new();
+    let stats = RefCell::new(RenameStats::default());
+    let journal = RefCell::new(RenameJournal::default());
+    let tally = |r: &RenameResult| {
+        stats.borrow_mut().record(r);
+        if args.changelog.is_some() {
+            journal.borrow_mut().record_result(r);
+        }
+    };
+    for (from, to) in pairs {
+        if args.interactive && from != to && !confirm(from, to) {
             continue;
         }
-        if writeln!(out, "{slugified}").is_err() {
+        if args.atomic && !dry_run {
+            atomic_ops.push((from.clone(), to.clone()));
+            continue;
+        }
+        let result = rename_file_with_progress(from, to, &rename_options, tally);
+        had_error |= report(&result, Show::new(args, dry_run || args.verbose));
+    }
+
+    if !atomic_ops.is_empty() {
+        had_error |= run_atomic(&atomic_ops, &rename_options, Show::new(args, args.verbose), tally);
+    }
+
+    if args.stats {
+        print_stats(&stats.borrow(), dry_run);
+    }
+
+    if let Some(file) = &args.changelog {
+        had_error |= write_changelog(&journal.borrow(), file, args.changelog_fmt);
+    }
+
+    if had_error {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Edit-plan mode: open the planned renames in an editor, then apply the edited plan.
+fn run_edit_plan(paths: impl IntoIterator<Item = PathBuf>, args: &Cli, options: &SlugifyOptions) -> ExitCode {
+    let plan = editor::plan_renames(paths, options);
+    if plan.is_empty() {
+        eprintln!("slugr: nothing to rename");
+        return ExitCode::SUCCESS;
+    }
+
+    let edited = match editor::open_editor_with_plan(&plan) {
+        Ok(edited) => edited,
+        Err(e) => {
+            eprintln!("slugr: cannot edit the plan: {e}");
             return ExitCode::FAILURE;
         }
+    };
+
+    let mut had_error = false;
+    let edited: Vec<_> = edited
+        .into_iter()
+        .filter(|pair| match editor::check_pair(pair, options) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("slugr: skipping '{}': {e}", pair.0.display());
+                had_error = true;
+                false
+            }
+        })
+        .collect();
+
+    if apply_pairs(&edited, args) == ExitCode::FAILURE || had_error {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// The new name for `path` under `template`. Trailing dots and spaces, as
+/// `{slug}.{ext}` leaves for a name without an extension, are trimmed.
+fn template_name(path: &Path, template: &str, options: &SlugifyOptions) -> Result<String, String> {
+    let vars = metadata::extract_file_vars(path, options).map_err(|e| e.to_string())?;
+    let filled = fill_template(template, &vars).map_err(|e| e.to_string())?;
+    let name = filled.trim_end_matches(['.', ' ']);
+    if name.is_empty() || name.contains(std::path::is_separator) {
+        return Err(format!("template gives invalid name '{filled}'"));
+    }
+    Ok(name.to_string())
+}
+
+/// Template mode: name each file from `template` filled with its metadata
+/// (see [`metadata::extract_file_vars`]), then apply the renames.
+fn rename_with_template(
+    paths: impl IntoIterator<Item = PathBuf>,
+    template: &str,
+    args: &Cli,
+    options: &SlugifyOptions,
+) -> ExitCode {
+    let rename_options = args.rename_options();
+    let mut detector = CaseCollisionDetector::new();
+    let mut had_error = false;
+    let mut planned = Vec::new();
+
+    for path in paths {
+        let name = match template_name(&path, template, options) {
+            Ok(name) => name,
+            Err(e) => {
+                eprintln!("slugr: cannot rename '{}': {e}", path.display());
+                had_error = true;
+                continue;
+            }
+        };
+        let target = path.parent().unwrap_or(Path::new(".")).join(name);
+        match detector.resolve(&path, &target, &rename_options) {
+            Ok(target) => planned.push((path, target)),
+            Err(error) => had_error |= report(&RenameResult::Failed { path, error }, Show::Nothing),
+        }
     }
-    if out.flush().is_err() {
-        return ExitCode::FAILURE;
+
+    if apply_pairs(&planned, args) == ExitCode::FAILURE || had_error {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
     }
-    ExitCode::SUCCESS
 }
 
-fn main() -> ExitCode {
-    let args = Cli::parse();
+/// Hash mode: rename each regular file to the `algorithm` digest of its
+/// content plus the extension of its slug (after `--extension-map`), then
+/// apply the renames. A file whose content matches one already given a name
+/// in its directory (or the file already there under that name) is skipped,
+/// so nothing is lost.
+fn run_rename_to_hash(
+    paths: impl IntoIterator<Item = PathBuf>,
+    algorithm: HashAlgorithm,
+    args: &Cli,
+    options: &SlugifyOptions,
+    extension_map: Option<&ExtensionMap>,
+) -> ExitCode {
+    let rename_options = args.rename_options();
+    let mut detector = CaseCollisionDetector::new();
+    // The target for each (directory, full digest) planned so far
+    let mut named: HashMap<(PathBuf, String), PathBuf> = HashMap::new();
+    let mut had_error = false;
+    let mut planned = Vec::new();
+
+    for path in paths {
+        // Directories and symlinks have no content of their own to hash
+        if !fs::symlink_metadata(&path).is_ok_and(|m| m.is_file()) {
+            continue;
+        }
+        let hash = match hash_file(&path, algorithm) {
+            Ok(hash) => hash,
+            Err(e) => {
+                eprintln!("slugr: cannot hash '{}': {e}", path.display());
+                had_error = true;
+                continue;
+            }
+        };
+        let slug = slug_name(&path.file_name().unwrap_or_default().to_string_lossy(), options, extension_map).into_owned();
+        let ext = match options.extension_config().split_extension(&slug) {
+            ("", _) => "",
+            (_, ext) => ext,
+        };
+        let len = args.hash_prefix.map_or(hash.len(), |n| usize::from(n).min(hash.len()));
+        let parent = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let target = parent.join(format!("{}{ext}", &hash[..len]));
+        if target == path {
+            continue;
+        }
+
+        let existing = named.get(&(parent.clone(), hash.clone())).cloned().or_else(|| {
+            let on_disk = fs::symlink_metadata(&target).is_ok_and(|m| m.is_file());
+            (on_disk && hash_file(&target, algorithm).is_ok_and(|h| h == hash)).then(|| target.clone())
+        });
+        if let Some(existing) = existing {
+            eprintln!("slugr: skipping '{}': same content as '{}'", path.display(), existing.display());
+            continue;
+        }
+
+        match detector.resolve(&path, &target, &rename_options) {
+            Ok(target) => {
+                named.insert((parent, hash), target.clone());
+                planned.push((path, target));
+            }
+            Err(error) => had_error |= report(&RenameResult::Failed { path, error }, Show::Nothing),
+        }
+    }
+
+    if apply_pairs(&planned, args) == ExitCode::FAILURE || had_error {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
 
-    let style = args.style();
+/// TUI mode: review the planned renames, then apply the ones left enabled.
+#[cfg(feature = "tui")]
+fn run_tui(paths: impl IntoIterator<Item = PathBuf>, args: &Cli, options: &SlugifyOptions) -> ExitCode {
+    let rows = tui::plan_rows(paths, options);
+    if rows.is_empty() {
+        eprintln!("slugr: nothing to rename");
+        return ExitCode::SUCCESS;
+    }
 
-    let options = SlugifyOptions {
-        style,
-        keep_unicode: args.keep_unicode,
+    let planned = rows.len();
+    let rows = match tui::run(rows) {
+        Ok(Some(rows)) => rows,
+        Ok(None) => {
+            eprintln!("slugr: quit: 0 of {planned} renames applied");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("slugr: cannot run the TUI: {e}");
+            return ExitCode::FAILURE;
+        }
     };
 
-    if args.pipe {
-        return run_pipe(&options, args.raw);
+    // Pressing Enter in the table is the confirmation, so this always executes
+    let rename_options = rename::RenameOptions { dry_run: false, ..args.rename_options() };
+    let mut stats = RenameStats::default();
+    let mut had_error = false;
+    let mut left_out = 0;
+    for row in &rows {
+        if !row.enabled {
+            left_out += 1;
+            continue;
+        }
+        let result = rename::rename_file(&row.from, &row.to, &rename_options);
+        stats.record(&result);
+        had_error |= report(&result, Show::new(args, true));
+    }
+    eprintln!("slugr: {stats}, {left_out} left out");
+
+    if had_error {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
     }
+}
 
-    let dry_run = !args.execute;
-    let no_clobber = !args.clobber;
+/// Batch mode: rename `paths` in parallel chunks of `batch_size`.
+fn run_batch(paths: impl Iterator<Item = PathBuf>, batch_size: usize, args: &Cli, options: &SlugifyOptions) -> ExitCode {
+    let rename_options = args.rename_options();
+    let show = Show::new(args, rename_options.dry_run || args.verbose);
+    let stats = process_batch(paths, batch_size, &rename_options, options, |result| {
+        report(result, show);
+    });
 
-    let input_paths: Vec<PathBuf> = if !args.files.is_empty() {
-        args.files
-    } else if !io::stdin().is_terminal() {
-        io::stdin()
-            .lock()
-            .lines()
-            .map_while(Result::ok)
-            .filter(|line| !line.is_empty())
-            .map(PathBuf::from)
-            .collect()
+    if args.stats {
+        print_stats(&stats, rename_options.dry_run);
+    }
+
+    if stats.failed > 0 {
+        ExitCode::FAILURE
     } else {
-        eprintln!("slugr: no files specified");
-        return ExitCode::FAILURE;
+        ExitCode::SUCCESS
+    }
+}
+
+/// Make sure the `--to-dir` destination exists, creating it when executing.
+/// Returns false if the directory could not be created.
+fn prepare_to_dir(dir: &Path, dry_run: bool) -> bool {
+    if dir.is_dir() {
+        return true;
+    }
+    if dry_run {
+        eprintln!("slugr: warning: '{}' does not exist and would be created", dir.display());
+        return true;
+    }
+    eprintln!("slugr: warning: creating directory '{}'", dir.display());
+    if let Err(e) = fs::create_dir_all(dir) {
+        eprintln!("slugr: cannot create '{}': {e}", dir.display());
+        return false;
+    }
+    true
+}
+
+/// Print the `--count` summary, optionally broken down by extension.
+fn print_count(count: usize, by_ext: &BTreeMap<String, usize>) {
+    let noun = if count == 1 { "file" } else { "files" };
+    println!("{count} {noun} would be renamed");
+    for (ext, n) in by_ext {
+        let ext = if ext.is_empty() { "(none)" } else { ext };
+        println!("  {ext}: {n}");
+    }
+}
+
+/// Count mode: report how many files would be renamed, without renaming.
+fn run_count(
+    paths: impl IntoIterator<Item = PathBuf>,
+    args: &Cli,
+    options: &SlugifyOptions,
+    extension_map: Option<&ExtensionMap>,
+) -> ExitCode {
+    let mut had_error = false;
+    let mut count = 0;
+    let mut by_ext: BTreeMap<String, usize> = BTreeMap::new();
+
+    for path in paths {
+        let Some(filename) = path.file_name().map(|f| f.to_string_lossy()) else {
+            continue;
+        };
+
+        // Hard links to directories are not permitted
+        if args.link && path.is_dir() {
+            eprintln!("slugr: warning: skipping directory '{}': cannot hard link directories", path.display());
+            continue;
+        }
+
+        let Some(new_name) = planned_name(&path, &filename, args, options, extension_map, None) else {
+            had_error = true;
+            continue;
+        };
+
+        if new_name != filename {
+            count += 1;
+            if args.count_by_ext {
+                let (_, ext) = split_extension(&new_name);
+                *by_ext.entry(ext.to_string()).or_default() += 1;
+            }
+        }
+    }
+
+    print_count(count, &by_ext);
+
+    if had_error {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Check mode: list the files that would be renamed on stderr, without
+/// renaming. Fails if there are any.
+fn run_check(
+    paths: impl IntoIterator<Item = PathBuf>,
+    args: &Cli,
+    options: &SlugifyOptions,
+    extension_map: Option<&ExtensionMap>,
+) -> ExitCode {
+    let mut had_error = false;
+    let mut unclean = Vec::new();
+
+    for path in paths {
+        let Some(filename) = path.file_name().map(|f| f.to_string_lossy()) else {
+            continue;
+        };
+        let Some(new_name) = planned_name(&path, &filename, args, options, extension_map, None) else {
+            had_error = true;
+            continue;
+        };
+        if new_name != filename {
+            let new_name = new_name.into_owned();
+            unclean.push((path, new_name));
+        }
+    }
+
+    eprint!("{}", format_check_report(&unclean, args.check_format));
+
+    if had_error || !unclean.is_empty() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Open the `--log-jsonl` file, if any. Prints the error if it can't be opened.
+fn open_log(file: Option<&Path>) -> Result<Option<JsonlLogger>, ()> {
+    let Some(file) = file else {
+        return Ok(None);
     };
+    JsonlLogger::open(file).map(Some).map_err(|e| {
+        eprintln!("slugr: cannot write '{}': {e}", file.display());
+    })
+}
 
-    let paths = collect_paths(&input_paths, args.recursive);
+/// Add `result` to the `--log-jsonl` file, if any. Returns true if it failed,
+/// after which nothing more is logged.
+fn log_result(log: &mut Option<JsonlLogger>, result: &RenameResult, elapsed: Duration) -> bool {
+    let Some(logger) = log else {
+        return false;
+    };
+    if let Err(e) = logger.log(result, elapsed) {
+        eprintln!("slugr: cannot write '{}': {e}", logger.path().display());
+        *log = None;
+        return true;
+    }
+    false
+}
 
-    if dry_run {
-        eprintln!("slugr: dry-run mode (use -x to execute)");
+/// Flush the `--log-jsonl` file. Returns true if it failed.
+fn finish_log(logger: &mut JsonlLogger) -> bool {
+    match logger.flush() {
+        Ok(()) => false,
+        Err(e) => {
+            eprintln!("slugr: cannot write '{}': {e}", logger.path().display());
+            true
+        }
+    }
+}
+
+/// Rename mode: slugify and rename each path in turn.
+fn run_rename(
+    paths: impl IntoIterator<Item = PathBuf>,
+    args: &Cli,
+    options: &SlugifyOptions,
+    extension_map: Option<&ExtensionMap>,
+) -> ExitCode {
+    let rename_options = args.rename_options();
+    let dry_run = rename_options.dry_run;
+
+    if let Some(dir) = &args.to_dir {
+        if !prepare_to_dir(dir, dry_run) {
+            return ExitCode::FAILURE;
+        }
     }
 
+    let Ok(mut log) = open_log(args.log_jsonl.as_deref()) else {
+        return ExitCode::FAILURE;
+    };
+
     let mut had_error = false;
+    let mut planned: Vec<pairs::Pair> = Vec::new();
+    let mut atomic_ops: Vec<pairs::Pair> = Vec::new();
+    let mut detector = CaseCollisionDetector::new();
+    let max_bytes = options.max_length.map_or(255, |max| max.min(255));
+    let counter = args.suffix_digits.map(|digits| GlobalCounter::new(args.start_at, usize::from(digits), max_bytes));
+    let stats = RefCell::new(RenameStats::default());
+    let journal = RefCell::new(RenameJournal::default());
+    let tally = |r: &RenameResult| {
+        stats.borrow_mut().record(r);
+        if args.changelog.is_some() {
+            journal.borrow_mut().record_result(r);
+        }
+    };
 
-    for path in &paths {
+    for path in paths {
         let filename = match path.file_name() {
             Some(f) => f.to_string_lossy(),
             None => continue,
         };
 
-        let new_name = slugify(&filename, &options);
-
-        // Empty/dot slugs would resolve to the parent directory
-        if new_name.is_empty() || *new_name == *"." || *new_name == *".." {
-            eprintln!("slugr: cannot rename '{}': slugified name is invalid", path.display());
-            had_error = true;
+        // Hard links to directories are not permitted
+        if args.link && path.is_dir() {
+            eprintln!("slugr: warning: skipping directory '{}': cannot hard link directories", path.display());
             continue;
         }
 
-        let parent = path.parent().unwrap_or(std::path::Path::new("."));
-        let target = parent.join(&*new_name);
+        let Some(new_name) = planned_name(&path, &filename, args, options, extension_map, counter.as_ref()) else {
+            stats.borrow_mut().failed += 1;
+            had_error = true;
+            continue;
+        };
 
-        if args.interactive && path != &target {
-            eprint!("slugr: rename '{}' -> '{}'? [y/N] ", path.display(), target.display());
-            let mut answer = String::new();
-            io::stdin().read_line(&mut answer).ok();
-            if !answer.trim().eq_ignore_ascii_case("y") {
+        let parent = match &args.to_dir {
+            Some(dir) => dir.as_path(),
+            None => path.parent().unwrap_or(Path::new(".")),
+        };
+        let target = match detector.resolve(&path, &parent.join(&*new_name), &rename_options) {
+            Ok(target) => target,
+            Err(error) => {
+                let result = RenameResult::Failed { path, error };
+                tally(&result);
+                had_error |= report(&result, Show::Nothing);
                 continue;
             }
+        };
+
+        if args.interactive && path != target && !confirm(&path, &target) {
+            continue;
         }
 
-        let result = rename_file(path, &target, no_clobber, dry_run);
+        if args.atomic && !dry_run {
+            atomic_ops.push((path, target));
+            continue;
+        }
 
-        match &result {
-            RenameResult::Renamed { from, to } => {
-                if dry_run || args.verbose {
-                    println!("{} -> {}", from.display(), to.display());
-                }
-            }
-            RenameResult::Skipped(_) => {}
-            RenameResult::Failed { path, error } => {
-                eprintln!("slugr: error renaming '{}': {}", path.display(), error);
-                had_error = true;
+        let started = Instant::now();
+        let result = rename_file_with_progress(&path, &target, &rename_options, tally);
+        had_error |= report(&result, Show::new(args, dry_run || args.verbose));
+        had_error |= log_result(&mut log, &result, started.elapsed());
+
+        if let (
+            Some(_),
+            RenameResult::Renamed { from, to } | RenameResult::MovedViaCopy { from, to } | RenameResult::Linked { from, to },
+        ) = (&args.export_pairs, &result)
+        {
+            if pairs::is_representable(from) {
+                planned.push((from.clone(), to.clone()));
+            } else {
+                eprintln!("slugr: warning: cannot export '{}': name contains a tab or newline", from.display());
             }
         }
     }
 
+    if !atomic_ops.is_empty() {
+        had_error |= run_atomic(&atomic_ops, &rename_options, Show::new(args, args.verbose), tally);
+    }
+
+    if let Some(logger) = &mut log {
+        had_error |= finish_log(logger);
+    }
+
+    if args.stats {
+        print_stats(&stats.borrow(), dry_run);
+    }
+
+    if let Some(file) = &args.changelog {
+        had_error |= write_changelog(&journal.borrow(), file, args.changelog_fmt);
+    }
+
+    if let Some(file) = &args.export_pairs {
+        if let Err(e) = pairs::write_pairs(file, &planned) {
+            eprintln!("slugr: cannot write '{}': {e}", file.display());
+            had_error = true;
+        }
+    }
+
     if had_error {
         ExitCode::FAILURE
     } else {
         ExitCode::SUCCESS
     }
 }
+
+fn main() -> ExitCode {
+    let mut args = Cli::parse();
+    if let Some(shell) = args.generate_completions {
+        cli::generate_completions(shell);
+        return ExitCode::SUCCESS;
+    }
+    if args.man {
+        print!("{}", cli::render_man_page());
+        return ExitCode::SUCCESS;
+    }
+
+    args.defaults = match EnvConfig::load() {
+        Ok(env) => env.or(config::load_config()),
+        Err(e) => {
+            eprintln!("slugr: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let options = match &args.extra_extensions {
+        Some(file) => match load_compound_extensions_file(file) {
+            Ok(exts) => args.options().with_extra_compound_extensions(&exts),
+            Err(e) => {
+                eprintln!("slugr: cannot read extra extensions '{}': {e}", file.display());
+                return ExitCode::FAILURE;
+            }
+        },
+        None => args.options(),
+    };
+
+    if args.pipe {
+        return run_pipe(&options, args.pipe_options());
+    }
+
+    let extension_map = match &args.extension_map {
+        Some(file) => match ExtensionMap::load(file) {
+            Ok(map) => Some(map),
+            Err(e) => {
+                eprintln!("slugr: cannot read extension map '{}': {e}", file.display());
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+
+    if let Some(file) = &args.from_pairs {
+        return run_from_pairs(file, &args);
+    }
+
+    let Some(input_paths) = read_input_paths(&args.files, args.from_null) else {
+        eprintln!("slugr: no files specified");
+        return ExitCode::FAILURE;
+    };
+
+    // Walked lazily unless sorted, so each entry is renamed as soon as it is reached
+    let paths = walk_paths(&input_paths, args.recursive, args.walk_backend(), args.rename_order, args.sort);
+    let entry_filter = args.entry_filter();
+    let paths = paths.filter(move |path| entry_filter.matches(path));
+
+    if args.explain {
+        return run_explain(paths, &options);
+    }
+
+    if args.diff {
+        return run_diff(paths, &options, extension_map.as_ref());
+    }
+
+    if args.count {
+        return run_count(paths, &args, &options, extension_map.as_ref());
+    }
+
+    if args.check {
+        return run_check(paths, &args, &options, extension_map.as_ref());
+    }
+
+    #[cfg(feature = "tui")]
+    if args.tui {
+        return run_tui(paths, &args, &options);
+    }
+
+    if args.edit_plan {
+        return run_edit_plan(paths, &args, &options);
+    }
+
+    if let Some(template) = &args.template {
+        return rename_with_template(paths, template, &args, &options);
+    }
+
+    if let Some(algorithm) = args.rename_to_hash {
+        return run_rename_to_hash(paths, algorithm, &args, &options, extension_map.as_ref());
+    }
+
+    if !args.execute {
+        eprintln!("slugr: dry-run mode (use -x to execute)");
+    }
+
+    if let Some(batch_size) = args.batch_size {
+        return run_batch(paths, batch_size.get(), &args, &options);
+    }
+
+    // The --suffix-digits counter is numbered over the full list, so no entry can come round twice
+    if args.check_conflicts || args.suffix_digits.is_some() {
+        let paths: Vec<PathBuf> = paths.collect();
+        if args.check_conflicts && !review_conflicts(&paths, &args, &options, extension_map.as_ref()) {
+            eprintln!("slugr: aborted, nothing renamed");
+            return ExitCode::FAILURE;
+        }
+        return run_rename(paths, &args, &options, extension_map.as_ref());
+    }
+
+    run_rename(paths, &args, &options, extension_map.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_null_separated() {
+        let mut input = io::Cursor::new(b"a b.txt\0line\nbreak.txt\0\0bad\xff.txt\0last.txt".to_vec());
+        let paths: Vec<String> = read_separated(&mut input, b'\0').collect();
+        assert_eq!(paths, ["a b.txt", "line\nbreak.txt", "last.txt"]);
+    }
+
+    #[test]
+    fn test_read_newline_separated_reads_past_invalid_utf8() {
+        let mut input = io::Cursor::new(b"a b.txt\r\n\nbad\xff.txt\nlast.txt\n".to_vec());
+        let paths: Vec<String> = read_separated(&mut input, b'\n').collect();
+        assert_eq!(paths, ["a b.txt", "last.txt"]);
+    }
+
+    #[test]
+    fn test_global_counter() {
+        let counter = GlobalCounter::new(9, 2, 255);
+        let shared = counter.clone();
+        assert_eq!(counter.apply("scan.pdf").unwrap(), "scan-09.pdf");
+        assert_eq!(shared.apply("archive.tar.gz").unwrap(), "archive-10.tar.gz");
+        assert_eq!(counter.apply(".bashrc").unwrap(), ".bashrc-11");
+        assert_eq!(counter.apply("notes").unwrap(), "notes-12");
+
+        let narrow = GlobalCounter::new(100, 2, 255);
+        assert_eq!(narrow.apply("scan.pdf").unwrap(), "scan-100.pdf");
+        assert_eq!(narrow.apply(&format!("{}.txt", "a".repeat(300))).unwrap().len(), 255);
+
+        let short = GlobalCounter::new(1, 3, 12);
+        assert_eq!(short.apply("my-long-scan.pdf").unwrap(), "my-001.pdf");
+    }
+
+    #[test]
+    fn test_global_counter_skips_numbered_names() {
+        let counter = GlobalCounter::new(1, 3, 255);
+        assert_eq!(counter.apply("scan-001.pdf"), None);
+        assert_eq!(counter.apply(".bashrc-002"), None);
+        // Not this counter's width
+        assert_eq!(counter.apply("scan-01.pdf").unwrap(), "scan-01-001.pdf");
+        assert_eq!(counter.apply("scan-2024.pdf").unwrap(), "scan-2024-002.pdf");
+    }
+}
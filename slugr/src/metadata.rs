@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use fileslug::{slugify, slugify_string, split_extension, SlugifyOptions};
+
+/// MIME types by lowercase extension, for the `{mime}` and `{mime_type}` variables.
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("png", "image/png"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("heic", "image/heic"),
+    ("svg", "image/svg+xml"),
+    ("mp3", "audio/mpeg"),
+    ("flac", "audio/flac"),
+    ("wav", "audio/wav"),
+    ("ogg", "audio/ogg"),
+    ("mp4", "video/mp4"),
+    ("mov", "video/quicktime"),
+    ("mkv", "video/x-matroska"),
+    ("webm", "video/webm"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("tar", "application/x-tar"),
+    ("json", "application/json"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("csv", "text/csv"),
+    ("html", "text/html"),
+    ("css", "text/css"),
+    ("js", "text/javascript"),
+];
+
+/// The MIME type for a file extension (without the dot), by its last component.
+fn mime_type(ext: &str) -> &'static str {
+    let last = ext.rsplit('.').next().unwrap_or_default().to_ascii_lowercase();
+    MIME_TYPES
+        .iter()
+        .find(|(e, _)| *e == last)
+        .map_or("application/octet-stream", |(_, mime)| mime)
+}
+
+/// The UTC `(year, month, day)` of `time`.
+fn utc_date(time: SystemTime) -> (i64, u32, u32) {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => i64::try_from(d.as_secs()).unwrap_or(i64::MAX),
+        Err(e) => -i64::try_from(e.duration().as_secs()).unwrap_or(i64::MAX),
+    };
+    civil_from_days(secs.div_euclid(86_400))
+}
+
+/// Convert days since 1970-01-01 to a proleptic Gregorian date
+/// (Howard Hinnant's `civil_from_days`).
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn insert_date(vars: &mut HashMap<String, String>, prefix: &str, time: SystemTime) {
+    let (year, month, day) = utc_date(time);
+    vars.insert(format!("{prefix}_year"), year.to_string());
+    vars.insert(format!("{prefix}_month"), format!("{month:02}"));
+    vars.insert(format!("{prefix}_day"), format!("{day:02}"));
+}
+
+/// The `--template` variables for `path`, each ready to insert as is:
+///
+/// - `{mtime_year}`, `{mtime_month}`, `{mtime_day}`: modification date (UTC, zero-padded)
+/// - `{created_year}`, `{created_month}`, `{created_day}`: creation date, where the
+///   platform and filesystem record it
+/// - `{size_bytes}`: file size
+/// - `{slug}`: the slugified name without its extension
+/// - `{ext}`: the extension without its dot, unchanged as slugify leaves it (`JPG`, `tar.gz`)
+/// - `{mime}`, `{mime_type}`: MIME type guessed from the extension as a slug
+///   (`image-jpeg`) and its top-level type (`image`)
+pub fn extract_file_vars(path: &Path, options: &SlugifyOptions) -> io::Result<HashMap<String, String>> {
+    let metadata = fs::metadata(path)?;
+    let filename = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let slug = slugify(&filename, options);
+    let (base, ext) = if metadata.is_dir() { (&*slug, "") } else { split_extension(&slug) };
+    let ext = ext.strip_prefix('.').unwrap_or(ext);
+    let mime = mime_type(ext);
+
+    let mut vars = HashMap::from([
+        ("size_bytes".to_string(), metadata.len().to_string()),
+        ("slug".to_string(), base.to_string()),
+        ("ext".to_string(), ext.to_string()),
+        ("mime".to_string(), slugify_string(mime, options)),
+        ("mime_type".to_string(), slugify_string(mime.split('/').next().unwrap_or(mime), options)),
+    ]);
+    insert_date(&mut vars, "mtime", metadata.modified()?);
+    if let Ok(created) = metadata.created() {
+        insert_date(&mut vars, "created", created);
+    }
+    Ok(vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+
+    #[test]
+    fn test_mime_type() {
+        assert_eq!(mime_type("JPG"), "image/jpeg");
+        assert_eq!(mime_type("tar.gz"), "application/gzip");
+        assert_eq!(mime_type(""), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_extract_file_vars() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Holiday Photo.JPG");
+        fs::write(&path, "12345").unwrap();
+        // 2024-03-05 12:00:00 UTC
+        let mtime = UNIX_EPOCH + Duration::from_hours(474_900);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(mtime).unwrap();
+
+        let vars = extract_file_vars(&path, &SlugifyOptions::default()).unwrap();
+        assert_eq!(vars["mtime_year"], "2024");
+        assert_eq!(vars["mtime_month"], "03");
+        assert_eq!(vars["mtime_day"], "05");
+        assert_eq!(vars["size_bytes"], "5");
+        assert_eq!(vars["slug"], "holiday-photo");
+        assert_eq!(vars["ext"], "JPG");
+        assert_eq!(vars["mime"], "image-jpeg");
+        assert_eq!(vars["mime_type"], "image");
+    }
+
+    #[test]
+    fn test_extract_file_vars_compound_extension_and_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("Old Backup.tar.gz");
+        fs::write(&archive, "").unwrap();
+        let vars = extract_file_vars(&archive, &SlugifyOptions::default()).unwrap();
+        assert_eq!((vars["slug"].as_str(), vars["ext"].as_str()), ("old-backup", "tar.gz"));
+
+        let sub = dir.path().join("Photos v1.2");
+        fs::create_dir(&sub).unwrap();
+        let vars = extract_file_vars(&sub, &SlugifyOptions::default()).unwrap();
+        assert_eq!((vars["slug"].as_str(), vars["ext"].as_str()), ("photos-v1.2", ""));
+    }
+}
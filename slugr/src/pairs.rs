@@ -0,0 +1,118 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A planned rename: `(from, to)`.
+pub type Pair = (PathBuf, PathBuf);
+
+/// Parse tab-separated `old<TAB>new` lines.
+/// Blank lines and lines starting with `#` are ignored.
+pub fn parse_pairs(text: &str) -> io::Result<Vec<Pair>> {
+    let mut pairs = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('\t') {
+            Some((from, to)) if !from.is_empty() && !to.is_empty() && !to.contains('\t') => {
+                pairs.push((PathBuf::from(from), PathBuf::from(to)));
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: expected 'old<TAB>new'", n + 1),
+                ));
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+/// Read rename pairs from `path`. See [`parse_pairs`] for the format.
+pub fn read_pairs(path: &Path) -> io::Result<Vec<Pair>> {
+    parse_pairs(&fs::read_to_string(path)?)
+}
+
+/// Whether `path` can be written to a pairs file (no tabs or newlines).
+pub fn is_representable(path: &Path) -> bool {
+    !path.to_string_lossy().contains(['\t', '\n', '\r'])
+}
+
+/// Format rename pairs as `old<TAB>new` lines, the inverse of [`parse_pairs`].
+/// Callers should filter with [`is_representable`] first.
+pub fn format_pairs(pairs: &[Pair]) -> String {
+    let mut out = String::new();
+    for (from, to) in pairs {
+        out.push_str(&from.to_string_lossy());
+        out.push('\t');
+        out.push_str(&to.to_string_lossy());
+        out.push('\n');
+    }
+    out
+}
+
+/// Write rename pairs to `path`, one `old<TAB>new` line each.
+/// Callers should filter with [`is_representable`] first.
+pub fn write_pairs(path: &Path, pairs: &[Pair]) -> io::Result<()> {
+    fs::write(path, format_pairs(pairs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pairs_basic() {
+        let pairs = parse_pairs("a.txt\tb.txt\ndir/My File\tdir/my-file\n").unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                (PathBuf::from("a.txt"), PathBuf::from("b.txt")),
+                (PathBuf::from("dir/My File"), PathBuf::from("dir/my-file")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pairs_skips_blank_and_comments() {
+        let pairs = parse_pairs("# planned renames\n\na\tb\n   \n").unwrap();
+        assert_eq!(pairs, vec![(PathBuf::from("a"), PathBuf::from("b"))]);
+    }
+
+    #[test]
+    fn test_parse_pairs_missing_tab() {
+        let err = parse_pairs("a\tb\nno tab here\n").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 2"), "got: {err}");
+    }
+
+    #[test]
+    fn test_parse_pairs_too_many_fields() {
+        assert!(parse_pairs("a\tb\tc\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_pairs_empty_field() {
+        assert!(parse_pairs("a\t\n").is_err());
+        assert!(parse_pairs("\tb\n").is_err());
+    }
+
+    #[test]
+    fn test_write_then_read_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("pairs.tsv");
+        let pairs = vec![
+            (PathBuf::from("My File.txt"), PathBuf::from("my-file.txt")),
+            (PathBuf::from("Café.pdf"), PathBuf::from("cafe.pdf")),
+        ];
+        write_pairs(&file, &pairs).unwrap();
+        assert_eq!(read_pairs(&file).unwrap(), pairs);
+    }
+
+    #[test]
+    fn test_is_representable() {
+        assert!(is_representable(Path::new("My File.txt")));
+        assert!(!is_representable(Path::new("bad\tname")));
+        assert!(!is_representable(Path::new("bad\nname")));
+    }
+}
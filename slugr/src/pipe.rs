@@ -0,0 +1,256 @@
+use std::io::{self, BufRead, Write};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use fileslug::{slugify, slugify_string, SlugifyOptions};
+
+/// How long to back off when a non-blocking stdout is full.
+const WOULD_BLOCK_BACKOFF: Duration = Duration::from_millis(1);
+
+/// Pipe mode settings from the command line.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PipeOptions {
+    /// Treat lines as raw text rather than filenames.
+    pub raw: bool,
+    /// Drop lines that are already clean.
+    pub filter_unchanged: bool,
+    /// Slugify only each line's last path component, keeping its directory as is.
+    pub only_basename: bool,
+    /// Write each input line before its slug, as `original<TAB>slug`.
+    pub print_original: bool,
+    /// End each output field with a NUL byte instead of a tab or newline.
+    pub print0: bool,
+    /// Write buffer size in bytes (`BufWriter`'s default if `None`).
+    pub buffer_size: Option<NonZeroUsize>,
+}
+
+#[derive(Debug)]
+enum PipeError {
+    Read(io::Error),
+    Write,
+}
+
+/// Retries writes that fail with `Interrupted` or `WouldBlock`, so a
+/// non-blocking stdout applies backpressure instead of failing the run.
+///
+/// Sits underneath the `BufWriter`, which therefore never sees a transient
+/// error and never re-sends bytes it has already handed over.
+struct RetryWriter<W>(W);
+
+impl<W: Write> RetryWriter<W> {
+    fn retry<T>(mut op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+        loop {
+            match op() {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(WOULD_BLOCK_BACKOFF),
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for RetryWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Self::retry(|| self.0.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Self::retry(|| self.0.flush())
+    }
+}
+
+/// Split `line` around its last path component: `(directory, name, trailing separators)`.
+/// Returns `None` when there is no name to slugify (`/`, `..`).
+fn split_basename(line: &str) -> Option<(&str, &str, &str)> {
+    let name = Path::new(line).file_name()?.to_str()?;
+    let start = line.rfind(name)?;
+    let end = start + name.len();
+    Some((&line[..start], &line[start..end], &line[end..]))
+}
+
+/// Slugify each line of `input` into `output`, one line at a time.
+fn process_lines<R: BufRead, W: Write>(
+    mut input: R,
+    output: W,
+    options: &SlugifyOptions,
+    pipe: PipeOptions,
+) -> Result<(), PipeError> {
+    let writer = RetryWriter(output);
+    let mut out = match pipe.buffer_size {
+        Some(size) => io::BufWriter::with_capacity(size.get(), writer),
+        None => io::BufWriter::new(writer),
+    };
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        match input.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => return Err(PipeError::Read(e)),
+        }
+        let line = line.strip_suffix('\n').unwrap_or(&line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.is_empty() {
+            continue;
+        }
+        let (dir, name, trailing) = if pipe.only_basename {
+            split_basename(line).unwrap_or(("", "", line))
+        } else {
+            ("", line, "")
+        };
+        let slugified = if pipe.raw {
+            slugify_string(name, options)
+        } else {
+            slugify(name, options).into_owned()
+        };
+        if slugified.is_empty() && !name.is_empty() {
+            eprintln!("slugr: warning: '{line}' slugifies to empty");
+            continue;
+        }
+        if pipe.filter_unchanged && slugified == name {
+            continue;
+        }
+        let (field_end, line_end) = if pipe.print0 { ('\0', '\0') } else { ('\t', '\n') };
+        if pipe.print_original {
+            write!(out, "{line}{field_end}").map_err(|_| PipeError::Write)?;
+        }
+        write!(out, "{dir}{slugified}{trailing}{line_end}").map_err(|_| PipeError::Write)?;
+    }
+    out.flush().map_err(|_| PipeError::Write)
+}
+
+/// Pipe mode: read lines from stdin, slugify each, write to stdout.
+/// With `filter_unchanged`, lines that are already clean are dropped.
+pub fn run_pipe(options: &SlugifyOptions, pipe: PipeOptions) -> ExitCode {
+    let stdin = io::stdin();
+    let input = io::BufReader::new(stdin.lock());
+    match process_lines(input, io::stdout().lock(), options, pipe) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(PipeError::Read(e)) => {
+            eprintln!("slugr: read error: {e}");
+            ExitCode::FAILURE
+        }
+        Err(PipeError::Write) => ExitCode::FAILURE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str, pipe: PipeOptions) -> String {
+        let mut out = Vec::new();
+        process_lines(input.as_bytes(), &mut out, &SlugifyOptions::default(), pipe).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// Fails every other call with the given error kind.
+    struct Flaky {
+        kind: io::ErrorKind,
+        fail_next: bool,
+        written: Vec<u8>,
+    }
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.fail_next = !self.fail_next;
+            if !self.fail_next {
+                return Err(self.kind.into());
+            }
+            // Accept at most 3 bytes at a time to force partial writes
+            let n = buf.len().min(3);
+            self.written.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_process_lines_basic() {
+        assert_eq!(run("My File.txt\n\nOther One.pdf", PipeOptions::default()), "my-file.txt\nother-one.pdf\n");
+    }
+
+    #[test]
+    fn test_process_lines_crlf() {
+        assert_eq!(run("My File.txt\r\n", PipeOptions::default()), "my-file.txt\n");
+    }
+
+    #[test]
+    fn test_process_lines_filter_unchanged() {
+        let pipe = PipeOptions { filter_unchanged: true, ..Default::default() };
+        assert_eq!(run("clean.txt\nNot Clean.txt\n", pipe), "not-clean.txt\n");
+    }
+
+    #[test]
+    fn test_process_lines_only_basename() {
+        let pipe = PipeOptions { only_basename: true, ..Default::default() };
+        assert_eq!(
+            run("/home/user/My Documents/Report 2024.pdf\nMy Dir/Sub Dir/\nPlain Name.txt\n/\n", pipe),
+            "/home/user/My Documents/report-2024.pdf\nMy Dir/sub-dir/\nplain-name.txt\n/\n"
+        );
+    }
+
+    #[test]
+    fn test_process_lines_only_basename_filter_unchanged() {
+        let pipe = PipeOptions { only_basename: true, filter_unchanged: true, ..Default::default() };
+        assert_eq!(run("My Dir/clean.txt\nMy Dir/Not Clean.txt\n", pipe), "My Dir/not-clean.txt\n");
+    }
+
+    #[test]
+    fn test_process_lines_print_original() {
+        let pipe = PipeOptions { print_original: true, ..Default::default() };
+        assert_eq!(run("My File.txt\r\nclean.txt\n", pipe), "My File.txt\tmy-file.txt\nclean.txt\tclean.txt\n");
+    }
+
+    #[test]
+    fn test_process_lines_print0() {
+        let pipe = PipeOptions { print0: true, ..Default::default() };
+        assert_eq!(run("A B\nC D\n", pipe), "a-b\0c-d\0");
+        let pipe = PipeOptions { print_original: true, print0: true, ..Default::default() };
+        assert_eq!(run("A B\n", pipe), "A B\0a-b\0");
+    }
+
+    #[test]
+    fn test_split_basename() {
+        assert_eq!(split_basename("/a b/c d.txt"), Some(("/a b/", "c d.txt", "")));
+        assert_eq!(split_basename("a/b/"), Some(("a/", "b", "/")));
+        assert_eq!(split_basename("name"), Some(("", "name", "")));
+        assert_eq!(split_basename("a/.."), None);
+    }
+
+    #[test]
+    fn test_process_lines_small_buffer() {
+        let pipe = PipeOptions { buffer_size: NonZeroUsize::new(1), ..Default::default() };
+        assert_eq!(run("A B\nC D\n", pipe), "a-b\nc-d\n");
+    }
+
+    #[test]
+    fn test_process_lines_invalid_utf8_is_read_error() {
+        let mut out = Vec::new();
+        let result = process_lines(&b"\xff\xfe\n"[..], &mut out, &SlugifyOptions::default(), PipeOptions::default());
+        assert!(matches!(result, Err(PipeError::Read(_))));
+    }
+
+    #[test]
+    fn test_retry_writer_would_block_and_interrupted() {
+        for kind in [io::ErrorKind::WouldBlock, io::ErrorKind::Interrupted] {
+            let mut writer = RetryWriter(Flaky { kind, fail_next: false, written: Vec::new() });
+            writer.write_all(b"hello world\n").unwrap();
+            assert_eq!(writer.0.written, b"hello world\n");
+        }
+    }
+
+    #[test]
+    fn test_retry_writer_passes_other_errors() {
+        let mut writer = RetryWriter(Flaky { kind: io::ErrorKind::BrokenPipe, fail_next: true, written: Vec::new() });
+        assert_eq!(writer.write(b"x").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    }
+}
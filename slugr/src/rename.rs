@@ -1,8 +1,15 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write as _};
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use fileslug::split_extension;
+use clap::ValueEnum;
+use filetime::FileTime;
+use fileslug::{split_extension, truncate_base};
+use sha2::{Digest, Sha256};
 
 /// Check if two paths refer to the same file (same inode on the same device).
 /// Returns false if either path doesn't exist.
@@ -15,9 +22,60 @@ fn same_file(a: &Path, b: &Path) -> bool {
     }
 }
 
-#[cfg(not(unix))]
+/// Windows: compare the volume serial number and 128-bit file ID
+/// (`FILE_ID_INFO`), which is what `dev`/`ino` are on Unix. When either
+/// path cannot be opened this falls back to [`same_canonical_path`]; on
+/// filesystems without stable IDs (some network shares report zeros) the
+/// ID check can be wrong, which is the same limitation `std` has.
+#[cfg(windows)]
 fn same_file(a: &Path, b: &Path) -> bool {
-    a == b
+    match (windows_file_id(a), windows_file_id(b)) {
+        (Some(ia), Some(ib)) => ia == ib,
+        _ => same_canonical_path(a, b),
+    }
+}
+
+/// The volume serial number and file ID of `path`, if it can be opened.
+#[cfg(windows)]
+fn windows_file_id(path: &Path) -> Option<(u64, [u8; 16])> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::{
+        FileIdInfo, GetFileInformationByHandleEx, FILE_FLAG_BACKUP_SEMANTICS, FILE_ID_INFO,
+    };
+
+    // No access rights are needed to query the ID; backup semantics lets directories be opened
+    let file = fs::OpenOptions::new().access_mode(0).custom_flags(FILE_FLAG_BACKUP_SEMANTICS).open(path).ok()?;
+    let mut info = std::mem::MaybeUninit::<FILE_ID_INFO>::uninit();
+    let size = u32::try_from(std::mem::size_of::<FILE_ID_INFO>()).ok()?;
+    // SAFETY: the handle is open for the duration of the call and `info` is a
+    // writable buffer of exactly `size` bytes
+    let ok = unsafe { GetFileInformationByHandleEx(file.as_raw_handle(), FileIdInfo, info.as_mut_ptr().cast(), size) };
+    if ok == 0 {
+        return None;
+    }
+    // SAFETY: the call succeeded, so it filled in `info`
+    let info = unsafe { info.assume_init() };
+    Some((info.VolumeSerialNumber, info.FileId.Identifier))
+}
+
+/// Other platforms: compare canonical paths, which resolves symlinks and, on
+/// case-insensitive filesystems, case differences. Paths that don't exist
+/// yet are compared as given.
+#[cfg(not(any(unix, windows)))]
+fn same_file(a: &Path, b: &Path) -> bool {
+    same_canonical_path(a, b)
+}
+
+/// Compare `a` and `b` after [`fs::canonicalize`], falling back to comparing
+/// them as given when either cannot be canonicalized (e.g. does not exist).
+// Unix uses inode numbers instead, but keeps this compiled so it is tested everywhere
+#[cfg_attr(unix, allow(dead_code))]
+fn same_canonical_path(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(ca), Ok(cb)) => ca == cb,
+        _ => a == b,
+    }
 }
 
 /// The result of a rename operation.
@@ -25,62 +83,336 @@ fn same_file(a: &Path, b: &Path) -> bool {
 pub enum RenameResult {
     /// File was renamed from old to new path.
     Renamed { from: PathBuf, to: PathBuf },
+    /// File was on another filesystem, so it was copied to the new path
+    /// and the original removed.
+    MovedViaCopy { from: PathBuf, to: PathBuf },
+    /// A hard link to the file was created at the new path.
+    Linked { from: PathBuf, to: PathBuf },
     /// File was already clean, no rename needed.
     Skipped(#[allow(dead_code)] PathBuf),
     /// Rename failed with an error.
     Failed { path: PathBuf, error: io::Error },
 }
 
-/// Maximum number of collision suffixes to try before giving up.
-const MAX_COLLISION_SUFFIX: u32 = 1_000;
+/// How many collision suffixes to try before giving up, unless
+/// [`RenameOptions::max_collisions`] says otherwise.
+pub const MAX_COLLISION_SUFFIX: u32 = 1_000;
+
+/// How `safe_target` disambiguates a name that is already taken.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollisionSuffix {
+    /// Append `-2`, `-3`, etc.
+    #[default]
+    Counter,
+    /// Append the current Unix time in milliseconds
+    Timestamp,
+    /// Append the first 8 hex chars of the SHA-256 of the source file's contents
+    Hash,
+}
+
+/// How much of a file `--content-hash` reads.
+pub const CONTENT_HASH_PREFIX_BYTES: u64 = 64 * 1024;
+
+/// Hex SHA-256 digest of the first `prefix_bytes` bytes of the file at `path`.
+pub fn compute_content_hash(path: &Path, prefix_bytes: u64) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut io::Read::take(fs::File::open(path)?, prefix_bytes), &mut hasher)?;
+    Ok(hasher.finalize().iter().fold(String::with_capacity(64), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    }))
+}
+
+/// First 8 hex chars of the SHA-256 digest of the file at `path`.
+fn content_hash(path: &Path) -> io::Result<String> {
+    let mut hash = compute_content_hash(path, u64::MAX)?;
+    hash.truncate(8);
+    Ok(hash)
+}
+
+/// `name` with the first `length` hex chars of the hash of `path`'s first
+/// [`CONTENT_HASH_PREFIX_BYTES`] appended to its base (`my-photo-a3f4b2c1.jpg`),
+/// shortening the base if needed to stay within 255 bytes. A name that
+/// already ends in that hash is returned unchanged, so renaming twice
+/// doesn't append it twice.
+///
+/// `None` if `path` is not a regular file: directories and symlinks are not hashed.
+pub fn content_hash_name(path: &Path, name: &str, length: usize) -> io::Result<Option<String>> {
+    if !fs::symlink_metadata(path)?.is_file() {
+        return Ok(None);
+    }
+    let mut hash = compute_content_hash(path, CONTENT_HASH_PREFIX_BYTES)?;
+    hash.truncate(length);
+
+    // A pure dotfile (e.g. ".bashrc") gets the hash after the name, as collision suffixes do
+    let (stem, ext) = match split_extension(name) {
+        ("", ext) => (ext, ""),
+        split => split,
+    };
+    let suffix = format!("-{hash}");
+    if stem.ends_with(&suffix) {
+        return Ok(Some(name.to_string()));
+    }
+    let tail = format!("{suffix}{ext}");
+    Ok(Some(format!("{}{tail}", truncate_base(stem, &tail, 255))))
+}
+
+/// The tag inserted before the counter for `suffix`, if any.
+fn collision_tag(suffix: CollisionSuffix, source: Option<&Path>) -> io::Result<Option<String>> {
+    match suffix {
+        CollisionSuffix::Counter => Ok(None),
+        CollisionSuffix::Timestamp => {
+            let millis = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(io::Error::other)?
+                .as_millis();
+            Ok(Some(millis.to_string()))
+        }
+        CollisionSuffix::Hash => {
+            let source = source.ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "hash suffix requires a source file")
+            })?;
+            content_hash(source)
+                .map(Some)
+                .map_err(|e| io::Error::new(e.kind(), format!("cannot hash '{}': {e}", source.display())))
+        }
+    }
+}
 
-/// Find a non-colliding target path, appending `-2`, `-3`, etc. if needed.
+/// Find a non-colliding target path, appending a suffix if needed.
+///
+/// With [`CollisionSuffix::Counter`] the suffix is `-2`, `-3`, etc. The
+/// timestamp and hash variants append `-<tag>` instead, falling back to
+/// `-<tag>-2`, `-<tag>-3` if that is taken too.
 ///
 /// `source` is excluded from collision checks so that case-only renames
 /// (e.g. `File.txt` → `file.txt`) don't falsely collide on case-insensitive
-/// filesystems. Returns an error after 1,000 suffixes are exhausted.
-pub fn safe_target(target: &Path, no_clobber: bool, source: Option<&Path>) -> io::Result<PathBuf> {
-    let collides = |p: &Path| p.exists() && !source.is_some_and(|s| same_file(s, p));
+/// filesystems. Returns an error once `max_collisions` counter suffixes are
+/// exhausted (at once if it is 0), or if the hash of `source` cannot be read.
+pub fn safe_target(
+    target: &Path,
+    no_clobber: bool,
+    source: Option<&Path>,
+    suffix: CollisionSuffix,
+    max_collisions: u32,
+) -> io::Result<PathBuf> {
+    safe_target_avoiding(target, no_clobber, source, suffix, max_collisions, None, &|_| false)
+}
+
+/// [`safe_target`] with counters zero-padded to `padding` digits, also
+/// treating paths for which `claimed` is true as taken.
+fn safe_target_avoiding(
+    target: &Path,
+    no_clobber: bool,
+    source: Option<&Path>,
+    suffix: CollisionSuffix,
+    max_collisions: u32,
+    padding: Option<u32>,
+    claimed: &dyn Fn(&Path) -> bool,
+) -> io::Result<PathBuf> {
+    let collides = |p: &Path| claimed(p) || (p.exists() && !source.is_some_and(|s| same_file(s, p)));
 
     if !no_clobber || !collides(target) {
         return Ok(target.to_path_buf());
     }
 
+    let too_many = || {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("too many collisions for '{}' (limit {max_collisions})", target.display()),
+        )
+    };
+    if max_collisions == 0 {
+        return Err(too_many());
+    }
+
     let filename = target.file_name().unwrap().to_string_lossy();
     let (base, ext) = split_extension(&filename);
     let parent = target.parent().unwrap_or(Path::new("."));
 
-    let format_candidate = |n: u32| -> PathBuf {
+    let format_candidate = |tag: &str| -> PathBuf {
         if base.is_empty() {
             // Pure dotfile (e.g. ".txt"): append suffix after name → .txt-2
-            parent.join(format!("{ext}-{n}"))
+            parent.join(format!("{ext}-{tag}"))
         } else {
-            parent.join(format!("{base}-{n}{ext}"))
+            parent.join(format!("{base}-{tag}{ext}"))
         }
     };
 
-    (2..=MAX_COLLISION_SUFFIX + 1)
-        .map(format_candidate)
-        .find(|candidate| !collides(candidate))
-        .ok_or_else(|| {
-            io::Error::new(
-                io::ErrorKind::AlreadyExists,
-                format!("too many collisions for '{}'", target.display()),
-            )
+    let tag = collision_tag(suffix, source)?;
+    if let Some(tag) = &tag {
+        let candidate = format_candidate(tag);
+        if !collides(&candidate) {
+            return Ok(candidate);
+        }
+    }
+
+    let width = padding.map_or(0, |digits| digits as usize);
+    (2..=max_collisions.saturating_add(1))
+        .map(|n| match &tag {
+            Some(tag) => format_candidate(&format!("{tag}-{n:0>width$}")),
+            None => format_candidate(&format!("{n:0>width$}")),
         })
+        .find(|candidate| !collides(candidate))
+        .ok_or_else(too_many)
 }
 
-/// Rename a single file/directory from `source` to `target`.
-/// If `no_clobber` is true and `target` exists, appends a numeric suffix.
-/// If `dry_run` is true, does not perform the rename.
+/// Targets already chosen during a batch, compared case-insensitively.
+///
+/// [`safe_target`] only sees what is on disk, so two sources slugified to
+/// names that differ only in case (`photo.JPG`, `photo.jpg`) both get their
+/// target: in a dry run because nothing has been renamed yet, and for real on
+/// a case-sensitive filesystem, leaving two names that collide once copied to
+/// macOS or Windows. Resolving every target through one detector gives the
+/// later source a suffix instead.
+#[derive(Debug, Default)]
+pub struct CaseCollisionDetector {
+    claimed: HashSet<String>,
+}
+
+impl CaseCollisionDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(path: &Path) -> String {
+        path.to_string_lossy().to_lowercase()
+    }
+
+    /// Whether a target equal to `path` ignoring case has been claimed.
+    pub fn is_claimed(&self, path: &Path) -> bool {
+        self.claimed.contains(&Self::key(path))
+    }
+
+    /// Choose the target for renaming `source` to `target`: `target` itself,
+    /// unless it is taken on disk or (ignoring case) by an earlier call, in
+    /// which case the suffix set by `options` is applied as [`safe_target`] does.
+    pub fn resolve(&mut self, source: &Path, target: &Path, options: &RenameOptions) -> io::Result<PathBuf> {
+        let resolved = if source == target {
+            target.to_path_buf()
+        } else {
+            safe_target_avoiding(
+                target,
+                options.no_clobber,
+                Some(source),
+                options.collision,
+                options.max_collisions,
+                options.collision_suffix_padding,
+                &|p| self.is_claimed(p),
+            )?
+        };
+        self.claimed.insert(Self::key(&resolved));
+        Ok(resolved)
+    }
+}
+
+/// Sources that a batch would rename to the same target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictReport {
+    /// The contested target, as first planned
+    pub target: PathBuf,
+    /// Every source planned to be renamed to `target` (ignoring case), in plan order
+    pub sources: Vec<PathBuf>,
+    /// Whether a file other than the sources already exists at `target`
+    pub target_exists: bool,
+}
+
+/// Find the `(source, target)` pairs in `plans` that would collide, before
+/// any is renamed: two or more sources sharing a target (compared ignoring
+/// case, as [`CaseCollisionDetector`] does), or a target that already exists
+/// and is not one of its sources. Pairs whose source equals the target are
+/// unchanged and never conflict themselves, but still occupy their name.
+///
+/// Touches the filesystem only to check whether targets exist. Reports come
+/// in the order their targets first appear in `plans`.
+pub fn collect_rename_conflicts(plans: &[(PathBuf, PathBuf)]) -> Vec<ConflictReport> {
+    let mut reports: Vec<ConflictReport> = Vec::new();
+    let mut by_target: HashMap<String, usize> = HashMap::new();
+
+    for (source, target) in plans.iter().filter(|(source, target)| source != target) {
+        match by_target.entry(CaseCollisionDetector::key(target)) {
+            Entry::Occupied(entry) => reports[*entry.get()].sources.push(source.clone()),
+            Entry::Vacant(entry) => {
+                entry.insert(reports.len());
+                reports.push(ConflictReport { target: target.clone(), sources: vec![source.clone()], target_exists: false });
+            }
+        }
+    }
+
+    for report in &mut reports {
+        report.target_exists =
+            report.target.exists() && !report.sources.iter().any(|source| same_file(source, &report.target));
+    }
+    reports.retain(|report| report.sources.len() > 1 || report.target_exists);
+    reports
+}
+
+/// How [`rename_file`] renames.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameOptions {
+    /// If `target` exists, append a suffix chosen by `collision` instead of overwriting.
+    pub no_clobber: bool,
+    /// Report what would happen without touching the filesystem.
+    pub dry_run: bool,
+    /// Create a hard link at `target` and leave `source` in place.
+    pub link_mode: bool,
+    /// The suffix used to resolve collisions.
+    pub collision: CollisionSuffix,
+    /// How many suffixes to try before the rename fails. `0` fails on any collision.
+    pub max_collisions: u32,
+    /// Zero-pad collision counters to this many digits (`-002` for 3), so
+    /// they sort in order. `None` leaves them unpadded.
+    pub collision_suffix_padding: Option<u32>,
+    /// Give the renamed path its original access and modification times,
+    /// which a move via copy across filesystems would otherwise reset.
+    pub preserve_timestamps: bool,
+}
+
+impl Default for RenameOptions {
+    /// The CLI's defaults: a no-clobber dry run that renames in place.
+    fn default() -> Self {
+        Self {
+            no_clobber: true,
+            dry_run: true,
+            link_mode: false,
+            collision: CollisionSuffix::Counter,
+            max_collisions: MAX_COLLISION_SUFFIX,
+            collision_suffix_padding: None,
+            preserve_timestamps: false,
+        }
+    }
+}
+
+/// Rename a single file/directory from `source` to `target` as set out by `options`.
 /// Handles case-only renames on case-insensitive filesystems (macOS).
+///
+/// `fs::rename` cannot move between filesystems (e.g. `--to-dir` on another
+/// mount). A regular file is then copied to `target` and the original
+/// removed, reported as [`RenameResult::MovedViaCopy`]; directories and
+/// other special files still fail.
 #[must_use]
-pub fn rename_file(source: &Path, target: &Path, no_clobber: bool, dry_run: bool) -> RenameResult {
+pub fn rename_file(source: &Path, target: &Path, options: &RenameOptions) -> RenameResult {
+    rename_file_using(source, target, options, &|from, to| fs::rename(from, to))
+}
+
+/// The rename primitive, swappable so tests can simulate a cross-filesystem move.
+type RenameFn = dyn Fn(&Path, &Path) -> io::Result<()>;
+
+fn rename_file_using(source: &Path, target: &Path, options: &RenameOptions, rename: &RenameFn) -> RenameResult {
     if source == target {
         return RenameResult::Skipped(source.to_path_buf());
     }
 
-    let final_target = match safe_target(target, no_clobber, Some(source)) {
+    let final_target = match safe_target_avoiding(
+        target,
+        options.no_clobber,
+        Some(source),
+        options.collision,
+        options.max_collisions,
+        options.collision_suffix_padding,
+        &|_| false,
+    ) {
         Ok(t) => t,
         Err(e) => {
             return RenameResult::Failed {
@@ -90,18 +422,189 @@ pub fn rename_file(source: &Path, target: &Path, no_clobber: bool, dry_run: bool
         }
     };
 
-    if dry_run {
+    if options.link_mode {
+        return link_file(source, final_target, options.dry_run);
+    }
+
+    if options.dry_run {
         return RenameResult::Renamed {
             from: source.to_path_buf(),
             to: final_target,
         };
     }
 
-    match fs::rename(source, &final_target) {
-        Ok(()) => RenameResult::Renamed {
+    // Read before the move, as a copy gets fresh times
+    let times = if options.preserve_timestamps {
+        match source.symlink_metadata() {
+            Ok(meta) => Some((FileTime::from_last_access_time(&meta), FileTime::from_last_modification_time(&meta))),
+            Err(e) => {
+                return RenameResult::Failed {
+                    path: source.to_path_buf(),
+                    error: e,
+                };
+            }
+        }
+    } else {
+        None
+    };
+
+    let result = match move_path(source, &final_target, rename) {
+        Ok(false) => RenameResult::Renamed {
             from: source.to_path_buf(),
             to: final_target,
         },
+        Ok(true) => RenameResult::MovedViaCopy {
+            from: source.to_path_buf(),
+            to: final_target,
+        },
+        Err(e) => RenameResult::Failed {
+            path: source.to_path_buf(),
+            error: e,
+        },
+    };
+
+    if let (Some((atime, mtime)), RenameResult::Renamed { to, .. } | RenameResult::MovedViaCopy { to, .. }) = (times, &result) {
+        // The move itself succeeded, so it is still reported as done
+        if let Err(e) = filetime::set_symlink_file_times(to, atime, mtime) {
+            eprintln!("slugr: warning: renamed to '{}', but cannot restore its timestamps: {e}", to.display());
+        }
+    }
+    result
+}
+
+/// Move `source` to `target` with `rename`, falling back to copy-and-delete
+/// when a regular file has to cross filesystems.
+/// Returns true if the fallback was used.
+fn move_path(source: &Path, target: &Path, rename: &RenameFn) -> io::Result<bool> {
+    match rename(source, target) {
+        Ok(()) => Ok(false),
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            if !source.symlink_metadata().is_ok_and(|m| m.is_file()) {
+                return Err(io::Error::new(e.kind(), "cannot move across filesystems: not a regular file"));
+            }
+            if let Err(e) = fs::copy(source, target) {
+                fs::remove_file(target).ok();
+                return Err(e);
+            }
+            if let Err(e) = fs::remove_file(source) {
+                // The copy stays: in clobber mode it has already replaced whatever was at `target`
+                return Err(io::Error::new(
+                    e.kind(),
+                    format!("copied to '{}', but cannot remove the original: {e}", target.display()),
+                ));
+            }
+            Ok(true)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// [`rename_file`] with the original positional arguments: a plain rename
+/// with counter suffixes.
+// Kept for callers of the old signature; slugr itself uses RenameOptions
+#[allow(dead_code)]
+#[must_use]
+pub fn rename_file_simple(source: &Path, target: &Path, no_clobber: bool, dry_run: bool) -> RenameResult {
+    rename_file(source, target, &RenameOptions { no_clobber, dry_run, ..RenameOptions::default() })
+}
+
+/// Undo a rename of `from` to `to` by renaming `to` back to `from`, with the
+/// same `no_clobber` and `dry_run` handling as [`rename_file_simple`].
+///
+/// Fails with [`io::ErrorKind::NotFound`] if `to` no longer exists, e.g.
+/// because it was moved or deleted since the rename.
+// Building block for journal-based undo, which slugr does not offer yet
+#[allow(dead_code)]
+#[must_use]
+pub fn undo_rename(from: &Path, to: &Path, no_clobber: bool, dry_run: bool) -> RenameResult {
+    if let Err(error) = fs::symlink_metadata(to) {
+        let error = if error.kind() == io::ErrorKind::NotFound {
+            io::Error::new(io::ErrorKind::NotFound, "renamed file no longer exists")
+        } else {
+            error
+        };
+        return RenameResult::Failed { path: to.to_path_buf(), error };
+    }
+    rename_file_simple(to, from, no_clobber, dry_run)
+}
+
+/// Undo each `(from, to)` rename in `journal` with [`undo_rename`], most
+/// recent first, so that chained renames unwind in order. Entries that
+/// cannot be undone are reported as [`RenameResult::Failed`] and the rest
+/// still run.
+#[allow(dead_code)]
+#[must_use]
+pub fn undo_rename_batch(journal: &[(PathBuf, PathBuf)], no_clobber: bool, dry_run: bool) -> Vec<RenameResult> {
+    journal.iter().rev().map(|(from, to)| undo_rename(from, to, no_clobber, dry_run)).collect()
+}
+
+/// [`rename_file`], then hand the result to `on_progress` before returning it.
+///
+/// Lets callers drive a progress bar or tally results without `rename.rs`
+/// knowing about either.
+pub fn rename_file_with_progress<F>(source: &Path, target: &Path, options: &RenameOptions, on_progress: F) -> RenameResult
+where
+    F: Fn(&RenameResult),
+{
+    let result = rename_file(source, target, options);
+    on_progress(&result);
+    result
+}
+
+/// Running totals of rename outcomes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RenameStats {
+    pub renamed: usize,
+    pub linked: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl RenameStats {
+    /// Count one result.
+    pub fn record(&mut self, result: &RenameResult) {
+        match result {
+            RenameResult::Renamed { .. } | RenameResult::MovedViaCopy { .. } => self.renamed += 1,
+            RenameResult::Linked { .. } => self.linked += 1,
+            RenameResult::Skipped(_) => self.skipped += 1,
+            RenameResult::Failed { .. } => self.failed += 1,
+        }
+    }
+}
+
+impl fmt::Display for RenameStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} renamed, ", self.renamed)?;
+        if self.linked > 0 {
+            write!(f, "{} linked, ", self.linked)?;
+        }
+        write!(f, "{} unchanged, {} failed", self.skipped, self.failed)
+    }
+}
+
+/// Create a hard link to `source` at `target`.
+/// A target that is already a link to `source` (e.g. from a previous run) is skipped.
+fn link_file(source: &Path, target: PathBuf, dry_run: bool) -> RenameResult {
+    if same_file(source, &target) {
+        return RenameResult::Skipped(source.to_path_buf());
+    }
+
+    if dry_run {
+        return RenameResult::Linked {
+            from: source.to_path_buf(),
+            to: target,
+        };
+    }
+
+    match fs::hard_link(source, &target) {
+        Ok(()) => RenameResult::Linked {
+            from: source.to_path_buf(),
+            to: target,
+        },
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => RenameResult::Failed {
+            path: source.to_path_buf(),
+            error: io::Error::new(e.kind(), "cannot hard link across filesystems"),
+        },
         Err(e) => RenameResult::Failed {
             path: source.to_path_buf(),
             error: e,
@@ -109,16 +612,174 @@ pub fn rename_file(source: &Path, target: &Path, no_clobber: bool, dry_run: bool
     }
 }
 
+/// Renames that have been carried out, in order, so they can be undone.
+#[derive(Debug, Default)]
+pub struct RenameJournal {
+    entries: Vec<(PathBuf, PathBuf)>,
+}
+
+impl RenameJournal {
+    /// Record that `from` was renamed to `to`.
+    pub fn record(&mut self, from: PathBuf, to: PathBuf) {
+        self.entries.push((from, to));
+    }
+
+    /// Record `result` if it renamed, moved, or linked a path.
+    pub fn record_result(&mut self, result: &RenameResult) {
+        if let RenameResult::Renamed { from, to } | RenameResult::MovedViaCopy { from, to } | RenameResult::Linked { from, to } =
+            result
+        {
+            self.record(from.clone(), to.clone());
+        }
+    }
+
+    /// The recorded `(from, to)` renames, oldest first.
+    pub fn entries(&self) -> &[(PathBuf, PathBuf)] {
+        &self.entries
+    }
+
+    /// Undo every recorded rename, most recent first.
+    /// Returns the renamed paths that could not be moved back.
+    pub fn rollback(self) -> Vec<(PathBuf, io::Error)> {
+        let mut failures = Vec::new();
+        for (from, to) in self.entries.into_iter().rev() {
+            if let Err(e) = move_path(&to, &from, &|a, b| fs::rename(a, b)) {
+                failures.push((to, e));
+            }
+        }
+        failures
+    }
+}
+
+/// A batch rename that failed and was rolled back.
+#[derive(Debug)]
+pub struct BatchRenameError {
+    /// The source path whose rename (or validation) failed.
+    pub path: PathBuf,
+    /// Why it failed.
+    pub error: io::Error,
+    /// Renamed paths that could not be restored during rollback.
+    pub partial_rollback_failures: Vec<(PathBuf, io::Error)>,
+}
+
+impl fmt::Display for BatchRenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}': {}", self.path.display(), self.error)?;
+        if !self.partial_rollback_failures.is_empty() {
+            write!(f, " ({} rename(s) could not be rolled back)", self.partial_rollback_failures.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BatchRenameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Rename every `(source, target)` pair with `options`, or none of them.
+///
+/// All sources and targets are validated with [`safe_target`] before anything
+/// is touched, and two sources planned onto the same target fail the batch.
+/// If a rename then fails, the renames already done are rolled back in
+/// reverse order; any that cannot be undone are listed in
+/// [`BatchRenameError::partial_rollback_failures`].
+pub fn atomic_rename_batch(ops: &[(PathBuf, PathBuf)], options: &RenameOptions) -> Result<Vec<RenameResult>, BatchRenameError> {
+    let mut planned: HashMap<&Path, &Path> = HashMap::new();
+    for (source, target) in ops {
+        let validated = match planned.insert(target, source) {
+            Some(other) => Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("'{}' is also the target of '{}'", target.display(), other.display()),
+            )),
+            None => fs::symlink_metadata(source).and_then(|_| {
+                safe_target(target, options.no_clobber, Some(source), options.collision, options.max_collisions)
+            }),
+        };
+        if let Err(error) = validated {
+            return Err(BatchRenameError {
+                path: source.clone(),
+                error,
+                partial_rollback_failures: Vec::new(),
+            });
+        }
+    }
+
+    let mut journal = RenameJournal::default();
+    let mut results = Vec::with_capacity(ops.len());
+    for (source, target) in ops {
+        match rename_file(source, target, options) {
+            RenameResult::Failed { path, error } => {
+                return Err(BatchRenameError {
+                    path,
+                    error,
+                    partial_rollback_failures: journal.rollback(),
+                });
+            }
+            result => {
+                if let RenameResult::Renamed { from, to } | RenameResult::MovedViaCopy { from, to } = &result {
+                    journal.record(from.clone(), to.clone());
+                }
+                results.push(result);
+            }
+        }
+    }
+    Ok(results)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
 
+    /// Default options, but actually renaming.
+    fn executing() -> RenameOptions {
+        RenameOptions { dry_run: false, ..RenameOptions::default() }
+    }
+
+    #[test]
+    fn test_rename_options_default() {
+        let options = RenameOptions::default();
+        assert!(options.no_clobber);
+        assert!(options.dry_run);
+        assert!(!options.link_mode);
+        assert_eq!(options.collision, CollisionSuffix::Counter);
+        assert_eq!(options.max_collisions, MAX_COLLISION_SUFFIX);
+    }
+
+    #[test]
+    fn test_rename_file_simple_matches_options() {
+        /// Rename "My File.txt" onto an existing "my-file.txt"; return the
+        /// outcome relative to the directory and the files left behind.
+        fn run(rename: impl Fn(&Path, &Path) -> RenameResult) -> (String, Vec<String>) {
+            let dir = tempfile::tempdir().unwrap();
+            let source = dir.path().join("My File.txt");
+            let target = dir.path().join("my-file.txt");
+            fs::write(&source, "a").unwrap();
+            fs::write(&target, "b").unwrap();
+            let outcome = format!("{:?}", rename(&source, &target)).replace(&*dir.path().to_string_lossy(), "");
+            let mut files: Vec<_> =
+                fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().into_owned()).collect();
+            files.sort();
+            (outcome, files)
+        }
+
+        for (no_clobber, dry_run) in [(true, true), (true, false), (false, true), (false, false)] {
+            let options = RenameOptions { no_clobber, dry_run, ..RenameOptions::default() };
+            assert_eq!(
+                run(|s, t| rename_file_simple(s, t, no_clobber, dry_run)),
+                run(|s, t| rename_file(s, t, &options)),
+                "no_clobber={no_clobber} dry_run={dry_run}"
+            );
+        }
+    }
+
     #[test]
     fn test_safe_target_no_collision() {
         let dir = tempfile::tempdir().unwrap();
         let target = dir.path().join("clean-file.txt");
-        assert_eq!(safe_target(&target, true, None).unwrap(), target);
+        assert_eq!(safe_target(&target, true, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX).unwrap(), target);
     }
 
     #[test]
@@ -126,7 +787,7 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let target = dir.path().join("file.txt");
         fs::write(&target, "existing").unwrap();
-        let result = safe_target(&target, true, None).unwrap();
+        let result = safe_target(&target, true, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX).unwrap();
         assert_eq!(result, dir.path().join("file-2.txt"));
     }
 
@@ -136,7 +797,7 @@ mod tests {
         fs::write(dir.path().join("file.txt"), "a").unwrap();
         fs::write(dir.path().join("file-2.txt"), "b").unwrap();
         fs::write(dir.path().join("file-3.txt"), "c").unwrap();
-        let result = safe_target(&dir.path().join("file.txt"), true, None).unwrap();
+        let result = safe_target(&dir.path().join("file.txt"), true, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX).unwrap();
         assert_eq!(result, dir.path().join("file-4.txt"));
     }
 
@@ -145,7 +806,7 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let target = dir.path().join("archive.tar.gz");
         fs::write(&target, "existing").unwrap();
-        let result = safe_target(&target, true, None).unwrap();
+        let result = safe_target(&target, true, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX).unwrap();
         assert_eq!(result, dir.path().join("archive-2.tar.gz"));
     }
 
@@ -154,7 +815,103 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let target = dir.path().join("file.txt");
         fs::write(&target, "existing").unwrap();
-        assert_eq!(safe_target(&target, false, None).unwrap(), target);
+        assert_eq!(safe_target(&target, false, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX).unwrap(), target);
+    }
+
+    #[test]
+    fn test_collect_rename_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let d = dir.path();
+        for name in ["My Photo.jpg", "my_photo.jpg", "MY-PHOTO.JPG", "Notes.txt", "Taken File.txt", "taken-file.txt", "clean.md"] {
+            fs::write(d.join(name), name).unwrap();
+        }
+        let plans = vec![
+            (d.join("My Photo.jpg"), d.join("my-photo.jpg")),
+            (d.join("Notes.txt"), d.join("notes.txt")),
+            (d.join("my_photo.jpg"), d.join("my-photo.jpg")),
+            (d.join("Taken File.txt"), d.join("taken-file.txt")),
+            (d.join("MY-PHOTO.JPG"), d.join("my-photo.JPG")),
+            (d.join("clean.md"), d.join("clean.md")),
+        ];
+
+        let conflicts = collect_rename_conflicts(&plans);
+
+        assert_eq!(conflicts.len(), 2, "{conflicts:?}");
+        assert_eq!(conflicts[0].target, d.join("my-photo.jpg"));
+        assert_eq!(conflicts[0].sources, [d.join("My Photo.jpg"), d.join("my_photo.jpg"), d.join("MY-PHOTO.JPG")]);
+        assert!(!conflicts[0].target_exists);
+        assert_eq!(conflicts[1].target, d.join("taken-file.txt"));
+        assert_eq!(conflicts[1].sources, [d.join("Taken File.txt")]);
+        assert!(conflicts[1].target_exists);
+    }
+
+    #[test]
+    fn test_collect_rename_conflicts_unchanged_name_is_taken() {
+        let dir = tempfile::tempdir().unwrap();
+        let d = dir.path();
+        fs::write(d.join("a-b.txt"), "kept").unwrap();
+        fs::write(d.join("A B.txt"), "renamed").unwrap();
+        let plans = vec![(d.join("a-b.txt"), d.join("a-b.txt")), (d.join("A B.txt"), d.join("a-b.txt"))];
+
+        let conflicts = collect_rename_conflicts(&plans);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].sources, [d.join("A B.txt")]);
+        assert!(conflicts[0].target_exists);
+    }
+
+    #[test]
+    fn test_case_collision_detector_suffixes_case_variants() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["PHOTO.jpg", "Photo.JPG"] {
+            fs::write(dir.path().join(name), name).unwrap();
+        }
+        let mut detector = CaseCollisionDetector::new();
+        let opts = RenameOptions::default();
+
+        let first = detector.resolve(&dir.path().join("PHOTO.jpg"), &dir.path().join("photo.jpg"), &opts).unwrap();
+        let second = detector.resolve(&dir.path().join("Photo.JPG"), &dir.path().join("photo.JPG"), &opts).unwrap();
+
+        assert_eq!(first, dir.path().join("photo.jpg"));
+        assert_eq!(second, dir.path().join("photo-2.JPG"));
+        assert!(detector.is_claimed(&dir.path().join("PHOTO-2.jpg")));
+    }
+
+    #[test]
+    fn test_case_collision_detector_dry_run_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut detector = CaseCollisionDetector::new();
+        let opts = RenameOptions::default();
+        let target = dir.path().join("my-file.txt");
+
+        // Nothing is renamed, so only the detector knows the first claim
+        assert_eq!(detector.resolve(&dir.path().join("My File.txt"), &target, &opts).unwrap(), target);
+        assert_eq!(
+            detector.resolve(&dir.path().join("my file.txt"), &target, &opts).unwrap(),
+            dir.path().join("my-file-2.txt")
+        );
+    }
+
+    #[test]
+    fn test_case_collision_detector_claims_unchanged_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut detector = CaseCollisionDetector::new();
+        let opts = RenameOptions::default();
+        let clean = dir.path().join("notes.txt");
+
+        assert_eq!(detector.resolve(&clean, &clean, &opts).unwrap(), clean);
+        assert_eq!(detector.resolve(&dir.path().join("Notes.txt"), &clean, &opts).unwrap(), dir.path().join("notes-2.txt"));
+    }
+
+    #[test]
+    fn test_case_collision_detector_clobber_keeps_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut detector = CaseCollisionDetector::new();
+        let opts = RenameOptions { no_clobber: false, ..RenameOptions::default() };
+        let target = dir.path().join("a.txt");
+
+        detector.resolve(&dir.path().join("A.txt"), &target, &opts).unwrap();
+        assert_eq!(detector.resolve(&dir.path().join("A .txt"), &target, &opts).unwrap(), target);
     }
 
     #[test]
@@ -164,7 +921,7 @@ mod tests {
         fs::write(&source, "hello").unwrap();
         let target = dir.path().join("my-file.txt");
 
-        let result = rename_file(&source, &target, true, false);
+        let result = rename_file(&source, &target, &executing());
         match result {
             RenameResult::Renamed { from, to } => {
                 assert_eq!(from, source);
@@ -184,7 +941,7 @@ mod tests {
         fs::write(&source, "hello").unwrap();
         let target = dir.path().join("my-file.txt");
 
-        let result = rename_file(&source, &target, true, true);
+        let result = rename_file(&source, &target, &RenameOptions::default());
         match result {
             RenameResult::Renamed { from, to } => {
                 assert_eq!(from, source);
@@ -197,13 +954,57 @@ mod tests {
         assert!(!target.exists());
     }
 
+    #[test]
+    fn test_rename_file_link_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My File.txt");
+        fs::write(&source, "hello").unwrap();
+        let target = dir.path().join("my-file.txt");
+
+        let result = rename_file(&source, &target, &RenameOptions { link_mode: true, ..executing() });
+        match result {
+            RenameResult::Linked { from, to } => {
+                assert_eq!(from, source);
+                assert_eq!(to, target);
+            }
+            other => panic!("expected Linked, got {other:?}"),
+        }
+        assert!(source.exists());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_rename_file_link_mode_dry_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My File.txt");
+        fs::write(&source, "hello").unwrap();
+        let target = dir.path().join("my-file.txt");
+
+        let result = rename_file(&source, &target, &RenameOptions { link_mode: true, ..RenameOptions::default() });
+        assert!(matches!(result, RenameResult::Linked { .. }), "expected Linked, got {result:?}");
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn test_rename_file_link_mode_existing_link_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My File.txt");
+        fs::write(&source, "hello").unwrap();
+        let target = dir.path().join("my-file.txt");
+        fs::hard_link(&source, &target).unwrap();
+
+        let result = rename_file(&source, &target, &RenameOptions { link_mode: true, ..executing() });
+        assert!(matches!(result, RenameResult::Skipped(_)), "expected Skipped, got {result:?}");
+        assert!(!dir.path().join("my-file-2.txt").exists());
+    }
+
     #[test]
     fn test_rename_file_same_name() {
         let dir = tempfile::tempdir().unwrap();
         let source = dir.path().join("already-clean.txt");
         fs::write(&source, "hello").unwrap();
 
-        let result = rename_file(&source, &source, true, false);
+        let result = rename_file(&source, &source, &executing());
         match result {
             RenameResult::Skipped(path) => assert_eq!(path, source),
             other => panic!("expected Skipped, got {other:?}"),
@@ -221,7 +1022,7 @@ mod tests {
         fs::write(&source, "hello").unwrap();
         let target = dir.path().join("file.txt");
 
-        let result = rename_file(&source, &target, true, false);
+        let result = rename_file(&source, &target, &executing());
         match result {
             RenameResult::Renamed { from, to } => {
                 assert_eq!(from, source);
@@ -250,7 +1051,7 @@ mod tests {
         fs::write(&source, "hello").unwrap();
         let target = dir.path().join("file.txt");
 
-        let result = safe_target(&target, true, Some(&source)).unwrap();
+        let result = safe_target(&target, true, Some(&source), CollisionSuffix::Counter, MAX_COLLISION_SUFFIX).unwrap();
         // Should return file.txt, not file-2.txt
         assert_eq!(result, target);
     }
@@ -261,7 +1062,7 @@ mod tests {
         let source = dir.path().join("nonexistent.txt");
         let target = dir.path().join("target.txt");
 
-        let result = rename_file(&source, &target, true, false);
+        let result = rename_file(&source, &target, &executing());
         match result {
             RenameResult::Failed { path, error } => {
                 assert_eq!(path, source);
@@ -271,6 +1072,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_same_canonical_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file.txt");
+        fs::write(&file, "").unwrap();
+        fs::write(dir.path().join("other.txt"), "").unwrap();
+
+        assert!(same_canonical_path(&file, &dir.path().join("./file.txt")));
+        assert!(!same_canonical_path(&file, &dir.path().join("other.txt")));
+        // Paths that don't exist yet are compared as given
+        assert!(same_canonical_path(&dir.path().join("new.txt"), &dir.path().join("new.txt")));
+        assert!(!same_canonical_path(&file, &dir.path().join("new.txt")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_same_canonical_path_follows_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file.txt");
+        fs::write(&file, "").unwrap();
+        std::os::unix::fs::symlink(&file, dir.path().join("link.txt")).unwrap();
+
+        assert!(same_canonical_path(&file, &dir.path().join("link.txt")));
+        assert!(same_file(&file, &dir.path().join("link.txt")));
+    }
+
+    #[cfg(unix)]
     #[test]
     fn test_rename_readonly_directory() {
         use std::os::unix::fs::PermissionsExt;
@@ -284,7 +1112,7 @@ mod tests {
         // Make directory read-only
         fs::set_permissions(&sub, fs::Permissions::from_mode(0o555)).unwrap();
 
-        let result = rename_file(&source, &target, true, false);
+        let result = rename_file(&source, &target, &executing());
 
         // Restore permissions for cleanup
         fs::set_permissions(&sub, fs::Permissions::from_mode(0o755)).unwrap();
@@ -308,7 +1136,7 @@ mod tests {
         std::os::unix::fs::symlink(&real_file, &link).unwrap();
         let target = dir.path().join("my-link.txt");
 
-        let result = rename_file(&link, &target, true, false);
+        let result = rename_file(&link, &target, &executing());
         match result {
             RenameResult::Renamed { from, to } => {
                 assert_eq!(from, link);
@@ -330,7 +1158,7 @@ mod tests {
         // .txt is treated as a dotfile with no base → base is empty
         let target = dir.path().join(".txt");
         fs::write(&target, "existing").unwrap();
-        let result = safe_target(&target, true, None).unwrap();
+        let result = safe_target(&target, true, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX).unwrap();
         // Pure dotfile: suffix appended after the name → .txt-2
         assert_eq!(result, dir.path().join(".txt-2"));
     }
@@ -341,7 +1169,7 @@ mod tests {
         fs::write(dir.path().join(".env"), "a").unwrap();
         fs::write(dir.path().join(".env-2"), "b").unwrap();
         fs::write(dir.path().join(".env-3"), "c").unwrap();
-        let result = safe_target(&dir.path().join(".env"), true, None).unwrap();
+        let result = safe_target(&dir.path().join(".env"), true, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX).unwrap();
         assert_eq!(result, dir.path().join(".env-4"));
     }
 
@@ -356,7 +1184,7 @@ mod tests {
         let target = dir.path().to_path_buf();
 
         // This should not panic regardless of OS behavior
-        let _result = rename_file(&source, &target, true, false);
+        let _result = rename_file(&source, &target, &executing());
     }
 
     #[test]
@@ -367,7 +1195,7 @@ mod tests {
         for i in 2..=1001u32 {
             fs::write(dir.path().join(format!("file-{i}.txt")), "").unwrap();
         }
-        let result = safe_target(&dir.path().join("file.txt"), true, None);
+        let result = safe_target(&dir.path().join("file.txt"), true, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX);
         assert!(result.is_err(), "should error after 1000 collisions");
     }
 
@@ -379,8 +1207,558 @@ mod tests {
         for i in 2..=1000u32 {
             fs::write(dir.path().join(format!("file-{i}.txt")), "").unwrap();
         }
-        let result = safe_target(&dir.path().join("file.txt"), true, None);
+        let result = safe_target(&dir.path().join("file.txt"), true, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX);
         assert!(result.is_ok(), "should succeed when slot 1001 is free");
         assert_eq!(result.unwrap(), dir.path().join("file-1001.txt"));
     }
+
+    /// Run `safe_target` for `file.txt` with it and `file-2.txt` ..
+    /// `file-{taken}.txt` already taken, so `taken` names collide.
+    fn safe_target_with_taken(taken: u32) -> (tempfile::TempDir, io::Result<PathBuf>) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("file.txt"), "").unwrap();
+        for i in 2..=taken {
+            fs::write(dir.path().join(format!("file-{i}.txt")), "").unwrap();
+        }
+        let result = safe_target(&dir.path().join("file.txt"), true, None, CollisionSuffix::Counter, MAX_COLLISION_SUFFIX);
+        (dir, result)
+    }
+
+    #[test]
+    fn test_safe_target_collision_cap_boundary() {
+        for taken in [1, 999, 1000, 1001] {
+            let (dir, result) = safe_target_with_taken(taken);
+            if taken <= MAX_COLLISION_SUFFIX {
+                let expected = dir.path().join(format!("file-{}.txt", taken + 1));
+                assert_eq!(result.unwrap(), expected, "{taken} collisions");
+            } else {
+                let err = result.unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::AlreadyExists, "{taken} collisions");
+                let target = dir.path().join("file.txt");
+                assert!(err.to_string().contains(&target.display().to_string()), "{taken} collisions: {err}");
+                assert!(err.to_string().contains(&format!("(limit {MAX_COLLISION_SUFFIX})")), "got: {err}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_collision_suffix_padding() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("File.txt");
+        let target = dir.path().join("file.txt");
+        fs::write(&source, "new").unwrap();
+        fs::write(&target, "old").unwrap();
+        let options = RenameOptions { collision_suffix_padding: Some(3), ..executing() };
+
+        // The first collision
+        let result = rename_file(&source, &target, &options);
+        assert!(matches!(result, RenameResult::Renamed { ref to, .. } if *to == dir.path().join("file-002.txt")), "{result:?}");
+
+        // The 99th: file.txt and file-002.txt .. file-099.txt are taken
+        for i in 3..=99 {
+            fs::write(dir.path().join(format!("file-{i:03}.txt")), "").unwrap();
+        }
+        fs::write(&source, "new").unwrap();
+        let result = rename_file(&source, &target, &options);
+        assert!(matches!(result, RenameResult::Renamed { ref to, .. } if *to == dir.path().join("file-100.txt")), "{result:?}");
+    }
+
+    #[test]
+    fn test_case_collision_detector_pads() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = RenameOptions { collision_suffix_padding: Some(3), ..RenameOptions::default() };
+        let mut detector = CaseCollisionDetector::new();
+        let target = dir.path().join("photo.jpg");
+        assert_eq!(detector.resolve(&dir.path().join("Photo.jpg"), &target, &options).unwrap(), target);
+        let second = detector.resolve(&dir.path().join("PHOTO.jpg"), &target, &options).unwrap();
+        assert_eq!(second, dir.path().join("photo-002.jpg"));
+    }
+
+    #[test]
+    fn test_safe_target_max_collisions_one() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("file.txt"), "").unwrap();
+        let target = dir.path().join("file.txt");
+        assert_eq!(safe_target(&target, true, None, CollisionSuffix::Counter, 1).unwrap(), dir.path().join("file-2.txt"));
+
+        // Only -2 is tried
+        fs::write(dir.path().join("file-2.txt"), "").unwrap();
+        let err = safe_target(&target, true, None, CollisionSuffix::Counter, 1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert!(err.to_string().contains("(limit 1)"), "got: {err}");
+    }
+
+    #[test]
+    fn test_safe_target_max_collisions_zero() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("file.txt");
+        assert_eq!(safe_target(&target, true, None, CollisionSuffix::Counter, 0).unwrap(), target);
+
+        fs::write(&target, "").unwrap();
+        let err = safe_target(&target, true, None, CollisionSuffix::Timestamp, 0).unwrap_err();
+        assert!(err.to_string().contains("(limit 0)"), "got: {err}");
+    }
+
+    #[test]
+    fn test_rename_file_max_collisions_zero_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My File.txt");
+        let target = dir.path().join("my-file.txt");
+        fs::write(&source, "new").unwrap();
+        fs::write(&target, "old").unwrap();
+
+        let result = rename_file(&source, &target, &RenameOptions { max_collisions: 0, ..executing() });
+        assert!(matches!(result, RenameResult::Failed { .. }));
+        assert!(source.exists());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "old");
+    }
+
+    #[test]
+    fn test_safe_target_timestamp() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("my-file.txt");
+        fs::write(&target, "existing").unwrap();
+        let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+        let result = safe_target(&target, true, None, CollisionSuffix::Timestamp, MAX_COLLISION_SUFFIX).unwrap();
+        let after = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+
+        let name = result.file_name().unwrap().to_string_lossy().into_owned();
+        let stamp: u128 = name
+            .strip_prefix("my-file-")
+            .and_then(|rest| rest.strip_suffix(".txt"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((before..=after).contains(&stamp), "{name}");
+    }
+
+    #[test]
+    fn test_safe_target_timestamp_no_collision_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("my-file.txt");
+        assert_eq!(safe_target(&target, true, None, CollisionSuffix::Timestamp, MAX_COLLISION_SUFFIX).unwrap(), target);
+    }
+
+    #[test]
+    fn test_safe_target_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My File.txt");
+        fs::write(&source, "hello").unwrap();
+        let target = dir.path().join("my-file.txt");
+        fs::write(&target, "existing").unwrap();
+
+        // sha256("hello") = 2cf24dba...
+        let result = safe_target(&target, true, Some(&source), CollisionSuffix::Hash, MAX_COLLISION_SUFFIX).unwrap();
+        assert_eq!(result, dir.path().join("my-file-2cf24dba.txt"));
+    }
+
+    #[test]
+    fn test_safe_target_hash_taken_falls_back_to_counter() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My File.txt");
+        fs::write(&source, "hello").unwrap();
+        fs::write(dir.path().join("my-file.txt"), "a").unwrap();
+        fs::write(dir.path().join("my-file-2cf24dba.txt"), "b").unwrap();
+
+        let target = dir.path().join("my-file.txt");
+        let result = safe_target(&target, true, Some(&source), CollisionSuffix::Hash, MAX_COLLISION_SUFFIX).unwrap();
+        assert_eq!(result, dir.path().join("my-file-2cf24dba-2.txt"));
+    }
+
+    #[test]
+    fn test_safe_target_hash_unreadable_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("file.txt");
+        fs::write(&target, "existing").unwrap();
+        let missing = dir.path().join("missing.txt");
+        let result = safe_target(&target, true, Some(&missing), CollisionSuffix::Hash, MAX_COLLISION_SUFFIX);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+        let result = safe_target(&target, true, None, CollisionSuffix::Hash, MAX_COLLISION_SUFFIX);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_rename_hash_directory_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My Dir");
+        fs::create_dir(&source).unwrap();
+        let target = dir.path().join("my-dir");
+        fs::create_dir(&target).unwrap();
+
+        let result = rename_file(&source, &target, &RenameOptions { collision: CollisionSuffix::Hash, ..executing() });
+        assert!(matches!(result, RenameResult::Failed { .. }));
+        assert!(source.exists(), "source should be left in place");
+    }
+
+    #[test]
+    fn test_atomic_batch_success() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A File.txt");
+        let b = dir.path().join("B File.txt");
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+        let ops = vec![(a.clone(), dir.path().join("a-file.txt")), (b.clone(), dir.path().join("b-file.txt"))];
+
+        let results = atomic_rename_batch(&ops, &executing()).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(!a.exists() && !b.exists());
+        assert_eq!(fs::read_to_string(dir.path().join("a-file.txt")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(dir.path().join("b-file.txt")).unwrap(), "b");
+    }
+
+    #[test]
+    fn test_atomic_batch_validation_touches_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A File.txt");
+        fs::write(&a, "a").unwrap();
+        let missing = dir.path().join("Missing.txt");
+        let ops = vec![(a.clone(), dir.path().join("a-file.txt")), (missing.clone(), dir.path().join("missing.txt"))];
+
+        let err = atomic_rename_batch(&ops, &executing()).unwrap_err();
+        assert_eq!(err.path, missing);
+        assert_eq!(err.error.kind(), io::ErrorKind::NotFound);
+        assert!(a.exists(), "nothing should be renamed when validation fails");
+    }
+
+    #[test]
+    fn test_atomic_batch_rejects_shared_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("My File.txt");
+        let b = dir.path().join("my  file.txt");
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+        let ops = vec![(a.clone(), dir.path().join("my-file.txt")), (b.clone(), dir.path().join("my-file.txt"))];
+
+        let err = atomic_rename_batch(&ops, &RenameOptions { no_clobber: false, ..executing() }).unwrap_err();
+        assert_eq!(err.path, b);
+        assert_eq!(err.error.kind(), io::ErrorKind::AlreadyExists);
+        assert!(a.exists() && b.exists(), "nothing should be renamed");
+    }
+
+    #[test]
+    fn test_atomic_batch_uses_collision_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A File.txt");
+        fs::write(&a, "a").unwrap();
+        fs::write(dir.path().join("a-file.txt"), "taken").unwrap();
+        let ops = vec![(a, dir.path().join("a-file.txt"))];
+
+        let options = RenameOptions { collision_suffix_padding: Some(3), ..executing() };
+        let results = atomic_rename_batch(&ops, &options).unwrap();
+        assert!(matches!(&results[0], RenameResult::Renamed { to, .. } if to.ends_with("a-file-002.txt")), "{results:?}");
+
+        let b = dir.path().join("B File.txt");
+        fs::write(&b, "b").unwrap();
+        fs::write(dir.path().join("b-file.txt"), "taken").unwrap();
+        let ops = vec![(b.clone(), dir.path().join("b-file.txt"))];
+        assert!(atomic_rename_batch(&ops, &RenameOptions { max_collisions: 0, ..executing() }).is_err());
+        assert!(b.exists());
+    }
+
+    #[test]
+    fn test_atomic_batch_rolls_back_on_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A File.txt");
+        let b = dir.path().join("B File.txt");
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+        // A file cannot replace a non-empty directory, even as root
+        let blocker = dir.path().join("b-file.txt");
+        fs::create_dir(&blocker).unwrap();
+        fs::write(blocker.join("inner"), "x").unwrap();
+        let ops = vec![(a.clone(), dir.path().join("a-file.txt")), (b.clone(), blocker)];
+
+        let err = atomic_rename_batch(&ops, &RenameOptions { no_clobber: false, ..executing() }).unwrap_err();
+        assert_eq!(err.path, b);
+        assert!(err.partial_rollback_failures.is_empty());
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a", "first rename should be rolled back");
+        assert!(!dir.path().join("a-file.txt").exists());
+        assert!(b.exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_atomic_batch_rolls_back_readonly_target_dir() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A File.txt");
+        let b = dir.path().join("B File.txt");
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+        let readonly = dir.path().join("readonly");
+        fs::create_dir(&readonly).unwrap();
+        fs::set_permissions(&readonly, fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Permissions are not enforced for privileged users
+        if fs::write(readonly.join("probe"), "").is_ok() {
+            fs::set_permissions(&readonly, fs::Permissions::from_mode(0o755)).unwrap();
+            return;
+        }
+
+        let ops = vec![(a.clone(), dir.path().join("a-file.txt")), (b.clone(), readonly.join("b-file.txt"))];
+        let result = atomic_rename_batch(&ops, &executing());
+        fs::set_permissions(&readonly, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let err = result.unwrap_err();
+        assert_eq!(err.path, b);
+        assert!(a.exists(), "first rename should be rolled back");
+        assert!(b.exists());
+    }
+
+    #[test]
+    fn test_journal_rollback_reports_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut journal = RenameJournal::default();
+        journal.record(dir.path().join("old.txt"), dir.path().join("gone.txt"));
+        let failures = journal.rollback();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, dir.path().join("gone.txt"));
+    }
+
+    #[test]
+    fn test_undo_rename_restores_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("My Report (Final).txt");
+        let slug = dir.path().join("my-report-final.txt");
+        fs::write(&original, "contents").unwrap();
+        assert!(matches!(rename_file(&original, &slug, &executing()), RenameResult::Renamed { .. }));
+
+        // A dry run leaves the slug in place
+        assert!(matches!(undo_rename(&original, &slug, true, true), RenameResult::Renamed { .. }));
+        assert!(slug.exists());
+
+        match undo_rename(&original, &slug, true, false) {
+            RenameResult::Renamed { from, to } => {
+                assert_eq!(from, slug);
+                assert_eq!(to, original);
+            }
+            other => panic!("expected Renamed, got {other:?}"),
+        }
+        assert!(!slug.exists());
+        assert_eq!(fs::read_to_string(&original).unwrap(), "contents");
+    }
+
+    #[test]
+    fn test_undo_rename_missing_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let slug = dir.path().join("my-file.txt");
+        for dry_run in [true, false] {
+            match undo_rename(&dir.path().join("My File.txt"), &slug, true, dry_run) {
+                RenameResult::Failed { path, error } => {
+                    assert_eq!(path, slug);
+                    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+                    assert_eq!(error.to_string(), "renamed file no longer exists");
+                }
+                other => panic!("expected Failed, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_undo_rename_batch_reverse_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A.txt");
+        let b = dir.path().join("b.txt");
+        let c = dir.path().join("c.txt");
+        let gone = dir.path().join("gone.txt");
+        fs::write(&a, "a").unwrap();
+        // A.txt -> b.txt -> c.txt, plus a rename whose result was since deleted
+        let mut journal = Vec::new();
+        for (from, to) in [(&a, &b), (&b, &c)] {
+            assert!(matches!(rename_file(from, to, &executing()), RenameResult::Renamed { .. }));
+            journal.push((from.clone(), to.clone()));
+        }
+        journal.push((dir.path().join("Gone.txt"), gone.clone()));
+
+        let results = undo_rename_batch(&journal, true, false);
+        assert_eq!(results.len(), 3);
+        assert!(matches!(&results[0], RenameResult::Failed { path, .. } if *path == gone));
+        assert!(matches!(&results[1], RenameResult::Renamed { from, to } if *from == c && *to == b));
+        assert!(matches!(&results[2], RenameResult::Renamed { from, to } if *from == b && *to == a));
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a");
+        assert!(!b.exists() && !c.exists());
+    }
+
+    #[test]
+    fn test_rename_with_progress_collects_results() {
+        use std::cell::RefCell;
+
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A File.txt");
+        let clean = dir.path().join("clean.txt");
+        fs::write(&a, "a").unwrap();
+        fs::write(&clean, "c").unwrap();
+        let missing = dir.path().join("Missing.txt");
+
+        let seen = RefCell::new(Vec::new());
+        let collect = |r: &RenameResult| seen.borrow_mut().push(format!("{r:?}"));
+        let ops = [
+            (a.clone(), dir.path().join("a-file.txt")),
+            (clean.clone(), clean.clone()),
+            (missing.clone(), dir.path().join("missing.txt")),
+        ];
+        let results: Vec<RenameResult> = ops
+            .iter()
+            .map(|(from, to)| rename_file_with_progress(from, to, &executing(), collect))
+            .collect();
+
+        let seen = seen.into_inner();
+        assert_eq!(seen.len(), 3);
+        assert_eq!(seen, results.iter().map(|r| format!("{r:?}")).collect::<Vec<_>>());
+        assert!(matches!(results[0], RenameResult::Renamed { .. }));
+        assert!(matches!(results[1], RenameResult::Skipped(_)));
+        assert!(matches!(results[2], RenameResult::Failed { .. }));
+        assert!(dir.path().join("a-file.txt").exists());
+    }
+
+    #[test]
+    fn test_rename_stats() {
+        let mut stats = RenameStats::default();
+        stats.record(&RenameResult::Renamed { from: "a".into(), to: "b".into() });
+        stats.record(&RenameResult::Skipped("c".into()));
+        stats.record(&RenameResult::Failed { path: "d".into(), error: io::ErrorKind::NotFound.into() });
+        assert_eq!(stats, RenameStats { renamed: 1, linked: 0, skipped: 1, failed: 1 });
+        assert_eq!(stats.to_string(), "1 renamed, 1 unchanged, 1 failed");
+        stats.record(&RenameResult::Linked { from: "e".into(), to: "f".into() });
+        assert_eq!(stats.to_string(), "1 renamed, 1 linked, 1 unchanged, 1 failed");
+    }
+
+    fn cross_device(_: &Path, _: &Path) -> io::Result<()> {
+        Err(io::ErrorKind::CrossesDevices.into())
+    }
+
+    #[test]
+    fn test_rename_cross_device_falls_back_to_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My File.txt");
+        fs::write(&source, "hello").unwrap();
+        let target = dir.path().join("my-file.txt");
+
+        let result = rename_file_using(&source, &target, &executing(), &cross_device);
+        assert!(matches!(result, RenameResult::MovedViaCopy { ref to, .. } if *to == target), "got {result:?}");
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "hello");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_rename_cross_device_keeps_copy_when_source_stays() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let readonly = dir.path().join("readonly");
+        fs::create_dir(&readonly).unwrap();
+        let source = readonly.join("My File.txt");
+        fs::write(&source, "new").unwrap();
+        let target = dir.path().join("my-file.txt");
+        fs::write(&target, "old").unwrap();
+        fs::set_permissions(&readonly, fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Permissions are not enforced for privileged users
+        if fs::write(readonly.join("probe"), "").is_ok() {
+            fs::set_permissions(&readonly, fs::Permissions::from_mode(0o755)).unwrap();
+            return;
+        }
+
+        let result = rename_file_using(&source, &target, &RenameOptions { no_clobber: false, ..executing() }, &cross_device);
+        fs::set_permissions(&readonly, fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert!(
+            matches!(result, RenameResult::Failed { ref error, .. } if error.to_string().contains("cannot remove the original")),
+            "got {result:?}"
+        );
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new", "the copy replaced the old target and should stay");
+        assert!(source.exists());
+    }
+
+    #[test]
+    fn test_rename_cross_device_dry_run_is_plain_rename() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My File.txt");
+        fs::write(&source, "hello").unwrap();
+
+        let result = rename_file_using(&source, &dir.path().join("my-file.txt"), &RenameOptions::default(), &cross_device);
+        assert!(matches!(result, RenameResult::Renamed { .. }), "got {result:?}");
+        assert!(source.exists());
+    }
+
+    #[test]
+    fn test_rename_cross_device_directory_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My Dir");
+        fs::create_dir(&source).unwrap();
+
+        let result = rename_file_using(&source, &dir.path().join("my-dir"), &executing(), &cross_device);
+        assert!(matches!(result, RenameResult::Failed { ref error, .. } if error.kind() == io::ErrorKind::CrossesDevices));
+        assert!(source.is_dir());
+    }
+
+    #[test]
+    fn test_rename_cross_device_preserve_timestamps() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("My Photo.jpg");
+        fs::write(&source, "jpeg").unwrap();
+        let taken = FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_times(&source, taken, taken).unwrap();
+        let target = dir.path().join("my-photo.jpg");
+
+        let options = RenameOptions { preserve_timestamps: true, ..executing() };
+        let result = rename_file_using(&source, &target, &options, &cross_device);
+        assert!(matches!(result, RenameResult::MovedViaCopy { .. }), "got {result:?}");
+        let meta = fs::metadata(&target).unwrap();
+        assert_eq!(FileTime::from_last_modification_time(&meta), taken);
+        assert_eq!(FileTime::from_last_access_time(&meta), taken);
+    }
+
+    #[test]
+    fn test_rename_stats_counts_copy_as_renamed() {
+        let mut stats = RenameStats::default();
+        stats.record(&RenameResult::MovedViaCopy { from: PathBuf::from("a"), to: PathBuf::from("b") });
+        assert_eq!(stats.renamed, 1);
+    }
+
+    #[test]
+    fn test_compute_content_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hello.txt");
+        fs::write(&path, "hello world").unwrap();
+        assert_eq!(
+            compute_content_hash(&path, 5).unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(compute_content_hash(&path, u64::MAX).unwrap().len(), 64);
+        assert_ne!(compute_content_hash(&path, u64::MAX).unwrap(), compute_content_hash(&path, 5).unwrap());
+    }
+
+    #[test]
+    fn test_content_hash_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("My Photo.jpg");
+        fs::write(&path, "hello").unwrap();
+
+        assert_eq!(content_hash_name(&path, "my-photo.jpg", 8).unwrap().as_deref(), Some("my-photo-2cf24dba.jpg"));
+        assert_eq!(content_hash_name(&path, "my-photo-2cf24dba.jpg", 8).unwrap().as_deref(), Some("my-photo-2cf24dba.jpg"));
+        assert_eq!(content_hash_name(&path, ".bashrc", 4).unwrap().as_deref(), Some(".bashrc-2cf2"));
+        let full = content_hash_name(&path, "my-photo.jpg", 64).unwrap().unwrap();
+        assert_eq!(full, "my-photo-2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824.jpg");
+
+        let long = format!("{}.jpg", "a".repeat(251));
+        let hashed = content_hash_name(&path, &long, 8).unwrap().unwrap();
+        assert!(hashed.len() <= 255 && hashed.ends_with("-2cf24dba.jpg"), "{hashed}");
+    }
+
+    #[test]
+    fn test_content_hash_name_skips_non_regular_files() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(content_hash_name(dir.path(), "dir", 8).unwrap(), None);
+        assert_eq!(content_hash_name(&dir.path().join("missing"), "missing", 8).unwrap_err().kind(), io::ErrorKind::NotFound);
+
+        #[cfg(unix)]
+        {
+            let target = dir.path().join("target.txt");
+            fs::write(&target, "hello").unwrap();
+            let link = dir.path().join("link.txt");
+            std::os::unix::fs::symlink(&target, &link).unwrap();
+            assert_eq!(content_hash_name(&link, "link.txt", 8).unwrap(), None);
+        }
+    }
 }
@@ -0,0 +1,304 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+
+use fileslug::{slugify, SlugifyOptions};
+
+/// One planned rename in the preview table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameRow {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// Whether the rename will be applied
+    pub enabled: bool,
+    /// Whether the target name was edited by hand
+    pub edited: bool,
+}
+
+impl RenameRow {
+    fn source_name(&self) -> String {
+        file_name(&self.from)
+    }
+
+    fn target_name(&self) -> String {
+        file_name(&self.to)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// Plan a rename for every path whose name changes when slugified.
+pub fn plan_rows(paths: impl IntoIterator<Item = PathBuf>, options: &SlugifyOptions) -> Vec<RenameRow> {
+    paths
+        .into_iter()
+        .filter_map(|from| {
+            let filename = from.file_name()?.to_string_lossy().into_owned();
+            let new_name = slugify(&filename, options);
+            if *new_name == *filename || new_name.is_empty() || *new_name == *"." || *new_name == *".." {
+                return None;
+            }
+            let to = from.parent().unwrap_or(Path::new(".")).join(&*new_name);
+            Some(RenameRow { from, to, enabled: true, edited: false })
+        })
+        .collect()
+}
+
+/// What the event loop should do after a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Continue,
+    /// Apply the enabled renames
+    Execute,
+    /// Leave without renaming anything
+    Quit,
+}
+
+/// The preview table and the selected row, plus the name being typed while
+/// a target is edited.
+#[derive(Debug)]
+pub struct TuiState {
+    pub rows: Vec<RenameRow>,
+    pub selected: usize,
+    pub editing: Option<String>,
+}
+
+impl TuiState {
+    pub fn new(rows: Vec<RenameRow>) -> Self {
+        Self { rows, selected: 0, editing: None }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn toggle_selected(&mut self) {
+        if let Some(row) = self.rows.get_mut(self.selected) {
+            row.enabled = !row.enabled;
+        }
+    }
+
+    /// Start editing the selected row's target name.
+    pub fn start_edit(&mut self) {
+        if let Some(row) = self.rows.get(self.selected) {
+            self.editing = Some(row.target_name());
+        }
+    }
+
+    pub fn cancel_edit(&mut self) {
+        self.editing = None;
+    }
+
+    /// Use the typed name as the selected row's target. Names that are empty,
+    /// `.`/`..`, or contain a path separator are rejected and editing continues.
+    pub fn finish_edit(&mut self) -> bool {
+        let Some(name) = self.editing.as_deref() else {
+            return false;
+        };
+        if name.is_empty() || name == "." || name == ".." || name.contains(std::path::is_separator) {
+            return false;
+        }
+        if let Some(row) = self.rows.get_mut(self.selected) {
+            if name != row.target_name() {
+                row.to = row.to.with_file_name(name);
+                row.edited = true;
+            }
+            row.enabled = true;
+        }
+        self.editing = None;
+        true
+    }
+
+    pub fn enabled_count(&self) -> usize {
+        self.rows.iter().filter(|row| row.enabled).count()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+
+        if let Some(name) = &mut self.editing {
+            match key.code {
+                KeyCode::Enter => {
+                    self.finish_edit();
+                }
+                KeyCode::Esc => self.cancel_edit(),
+                KeyCode::Backspace => {
+                    name.pop();
+                }
+                KeyCode::Char(c) => name.push(c),
+                _ => {}
+            }
+            return Action::Continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Enter => return Action::Execute,
+            KeyCode::Down | KeyCode::Char('j') => self.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.select_previous(),
+            KeyCode::Char(' ') => self.toggle_selected(),
+            KeyCode::Char('e') => self.start_edit(),
+            _ => {}
+        }
+        Action::Continue
+    }
+}
+
+/// Show the preview table until the user executes or quits. Returns the rows
+/// to apply, or `None` if the user quit.
+pub fn run(rows: Vec<RenameRow>) -> io::Result<Option<Vec<RenameRow>>> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, TuiState::new(rows));
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, mut state: TuiState) -> io::Result<Option<Vec<RenameRow>>> {
+    loop {
+        terminal.draw(|frame| draw(frame, &state))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match state.handle_key(key) {
+            Action::Continue => {}
+            Action::Execute => return Ok(Some(state.rows)),
+            Action::Quit => return Ok(None),
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, state: &TuiState) {
+    let [table_area, footer_area] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+    let rows = state.rows.iter().map(|row| {
+        let mark = if row.enabled { "[x]" } else { "[ ]" };
+        let to = if row.edited { format!("{} (edited)", row.target_name()) } else { row.target_name() };
+        let style = if row.enabled { Style::default() } else { Style::default().add_modifier(Modifier::DIM) };
+        Row::new([mark.to_string(), row.source_name(), to]).style(style)
+    });
+    let title = format!(" slugr: {} of {} renames enabled ", state.enabled_count(), state.rows.len());
+    let table = Table::new(rows, [Constraint::Length(3), Constraint::Percentage(50), Constraint::Percentage(50)])
+        .header(Row::new(["", "From", "To"]).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut table_state = TableState::default().with_selected(Some(state.selected));
+    frame.render_stateful_widget(table, table_area, &mut table_state);
+
+    let footer = match &state.editing {
+        Some(name) => format!("New name: {name}_   (enter: save, esc: cancel)"),
+        None => "↑/↓ move  space: toggle  e: edit name  enter: rename  q: quit".to_string(),
+    };
+    frame.render_widget(Line::from(footer), footer_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn state() -> TuiState {
+        let paths = ["dir/My File.txt", "dir/clean.txt", "dir/Other File.txt"].map(PathBuf::from);
+        TuiState::new(plan_rows(paths, &SlugifyOptions::default()))
+    }
+
+    #[test]
+    fn test_plan_rows_skips_unchanged() {
+        let state = state();
+        assert_eq!(state.rows.len(), 2);
+        assert_eq!(state.rows[0].to, PathBuf::from("dir/my-file.txt"));
+        assert!(state.rows.iter().all(|row| row.enabled && !row.edited));
+    }
+
+    #[test]
+    fn test_navigation_stays_in_bounds() {
+        let mut state = state();
+        state.handle_key(key(KeyCode::Up));
+        assert_eq!(state.selected, 0);
+        for _ in 0..5 {
+            state.handle_key(key(KeyCode::Char('j')));
+        }
+        assert_eq!(state.selected, 1);
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut state = state();
+        state.handle_key(key(KeyCode::Char(' ')));
+        assert!(!state.rows[0].enabled);
+        assert_eq!(state.enabled_count(), 1);
+        state.handle_key(key(KeyCode::Char(' ')));
+        assert!(state.rows[0].enabled);
+    }
+
+    #[test]
+    fn test_edit_target_name() {
+        let mut state = state();
+        state.handle_key(key(KeyCode::Char('e')));
+        assert_eq!(state.editing.as_deref(), Some("my-file.txt"));
+        for _ in 0.."file.txt".len() {
+            state.handle_key(key(KeyCode::Backspace));
+        }
+        for c in "doc.txt".chars() {
+            state.handle_key(key(KeyCode::Char(c)));
+        }
+        // Keys edit the name instead of acting on the table
+        assert_eq!(state.handle_key(key(KeyCode::Char('q'))), Action::Continue);
+        state.handle_key(key(KeyCode::Backspace));
+        state.handle_key(key(KeyCode::Enter));
+
+        assert_eq!(state.editing, None);
+        assert_eq!(state.rows[0].to, PathBuf::from("dir/my-doc.txt"));
+        assert!(state.rows[0].edited);
+    }
+
+    #[test]
+    fn test_edit_rejects_invalid_names() {
+        let mut state = state();
+        for name in ["", "..", "a/b"] {
+            state.editing = Some(name.to_string());
+            assert!(!state.finish_edit(), "{name:?}");
+            assert!(state.editing.is_some());
+        }
+        state.handle_key(key(KeyCode::Esc));
+        assert_eq!(state.editing, None);
+        assert_eq!(state.rows[0].to, PathBuf::from("dir/my-file.txt"));
+        assert!(!state.rows[0].edited);
+    }
+
+    #[test]
+    fn test_unchanged_edit_is_not_marked() {
+        let mut state = state();
+        state.start_edit();
+        assert!(state.finish_edit());
+        assert!(!state.rows[0].edited);
+    }
+
+    #[test]
+    fn test_execute_and_quit() {
+        let mut state = state();
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), Action::Execute);
+        assert_eq!(state.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+        assert_eq!(state.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)), Action::Quit);
+    }
+}
@@ -1,11 +1,70 @@
-use std::path::PathBuf;
+use std::cmp::Ordering;
+use std::fs;
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::vec;
+
+use clap::ValueEnum;
 use walkdir::WalkDir;
 
-/// Collect all file and directory paths under `root`, bottom-up.
-/// Bottom-up ensures children are renamed before parents.
+/// How recursive traversal discovers paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkBackend {
+    /// Visit every entry under the root.
+    WalkDir,
+    /// Skip entries excluded by `.gitignore`, `.ignore`, the global gitignore,
+    /// and hidden files (including `.git` itself).
+    #[cfg(feature = "ignore")]
+    Ignore,
+}
+
+/// The order in which recursive traversal lists entries.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WalkOrder {
+    /// Children before their parent directory. Required when directories are
+    /// renamed: renaming a parent first would invalidate its children's paths.
+    #[default]
+    BottomUp,
+    /// Parents before their children. Only safe when no directory is renamed.
+    TopDown,
+}
+
+/// Which kinds of entry to rename.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntryFilter {
+    #[default]
+    All,
+    /// Directories only, leaving the files inside them alone.
+    DirsOnly,
+    /// Everything except directories.
+    FilesOnly,
+}
+
+impl EntryFilter {
+    /// Whether `path` should be kept. Symlinks to directories count as directories.
+    pub fn matches(self, path: &Path) -> bool {
+        match self {
+            Self::All => true,
+            Self::DirsOnly => path.is_dir(),
+            Self::FilesOnly => !path.is_dir(),
+        }
+    }
+}
+
+/// Collect all file and directory paths under `root` in the given `order`.
+/// [`WalkOrder::BottomUp`] ensures children are renamed before parents.
 /// If `recursive` is false, only collects the given paths directly.
+///
+/// With `sort`, the result is in a fixed lexicographic order rather than the
+/// filesystem's, still respecting `order` (see [`sort_paths`]).
 #[must_use]
-pub fn collect_paths(paths: &[PathBuf], recursive: bool) -> Vec<PathBuf> {
+pub fn collect_paths(
+    paths: &[PathBuf],
+    recursive: bool,
+    backend: WalkBackend,
+    order: WalkOrder,
+    sort: bool,
+) -> Vec<PathBuf> {
     let mut result = Vec::new();
 
     for path in paths {
@@ -19,24 +78,202 @@ pub fn collect_paths(paths: &[PathBuf], recursive: bool) -> Vec<PathBuf> {
             continue;
         }
 
-        // Recursive directory traversal, bottom-up (contents_first)
-        for entry in WalkDir::new(path).contents_first(true) {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(e) => {
-                    eprintln!("slugr: warning: {e}");
-                    continue;
+        match backend {
+            WalkBackend::WalkDir => walk_walkdir(path, order, &mut result),
+            #[cfg(feature = "ignore")]
+            WalkBackend::Ignore => walk_ignore(path, order, &mut result),
+        }
+    }
+
+    if sort {
+        sort_paths(&mut result, order);
+    }
+    result
+}
+
+/// Sort `paths` lexicographically by component without breaking `order`:
+/// for [`WalkOrder::BottomUp`] a directory still sorts after everything
+/// beneath it, for [`WalkOrder::TopDown`] before.
+pub fn sort_paths(paths: &mut [PathBuf], order: WalkOrder) {
+    match order {
+        WalkOrder::BottomUp => paths.sort_by(|a, b| bottom_up_cmp(a, b)),
+        // Path's own ordering already puts a prefix first
+        WalkOrder::TopDown => paths.sort(),
+    }
+}
+
+/// Component-wise ordering in which a path sorts after its descendants.
+fn bottom_up_cmp(a: &Path, b: &Path) -> Ordering {
+    let mut a = a.components();
+    let mut b = b.components();
+    loop {
+        match (a.next(), b.next()) {
+            (Some(x), Some(y)) => match x.cmp(&y) {
+                Ordering::Equal => {}
+                unequal => return unequal,
+            },
+            // The shorter path is an ancestor of the longer one
+            (None, Some(_)) => return Ordering::Greater,
+            (Some(_), None) => return Ordering::Less,
+            (None, None) => return Ordering::Equal,
+        }
+    }
+}
+
+/// Lazily yield all file and directory paths under `paths`, bottom-up.
+/// `max_depth` limits how far below each root the walk descends (`Some(1)`
+/// lists direct children only). If `recursive` is false, yields the given
+/// paths directly.
+///
+/// Unlike [`collect_paths`], memory does not grow with the size of the whole
+/// tree, only with the directories currently being walked: each one is read
+/// in full before any of its entries is yielded. That costs the entries of
+/// one directory per level, but lets the caller rename entries as they come.
+/// readdir makes no promise about entries changed mid-listing, and reading
+/// lazily would hand back a renamed entry again under its new name.
+pub fn collect_paths_iter(paths: &[PathBuf], recursive: bool, max_depth: Option<usize>) -> impl Iterator<Item = PathBuf> {
+    // Owned, so the iterator does not borrow the caller's slice
+    let roots = paths.to_vec();
+    roots.into_iter().flat_map(move |path| -> Box<dyn Iterator<Item = PathBuf>> {
+        let Ok(meta) = path.metadata() else {
+            eprintln!("slugr: warning: '{}': not found", path.display());
+            return Box::new(iter::empty());
+        };
+
+        if !recursive || meta.is_file() {
+            return Box::new(iter::once(path));
+        }
+
+        Box::new(BufferedWalk::new(path, max_depth))
+    })
+}
+
+/// The bottom-up walk behind [`collect_paths_iter`]: depth-first, in
+/// directory order like `WalkDir`'s `contents_first`, without the root.
+struct BufferedWalk {
+    /// Each directory being walked, innermost last
+    stack: Vec<Frame>,
+    max_depth: Option<usize>,
+}
+
+/// A directory in a [`BufferedWalk`].
+struct Frame {
+    dir: PathBuf,
+    /// How far below the root `dir` is
+    depth: usize,
+    /// Entries not yet visited, `true` for subdirectories
+    entries: vec::IntoIter<(PathBuf, bool)>,
+}
+
+impl BufferedWalk {
+    fn new(root: PathBuf, max_depth: Option<usize>) -> Self {
+        let mut walk = BufferedWalk { stack: Vec::new(), max_depth };
+        walk.enter(root, 0);
+        walk
+    }
+
+    /// Read all of `dir`'s entries and visit them next.
+    fn enter(&mut self, dir: PathBuf, depth: usize) {
+        let entries: Vec<(PathBuf, bool)> = match fs::read_dir(&dir) {
+            // Symlinks to directories are not followed, as in WalkDir
+            Ok(entries) => entries
+                .filter_map(|entry| match entry.and_then(|e| Ok((e.path(), e.file_type()?.is_dir()))) {
+                    Ok(entry) => Some(entry),
+                    Err(e) => {
+                        eprintln!("slugr: warning: cannot read '{}': {e}", dir.display());
+                        None
+                    }
+                })
+                .collect(),
+            Err(e) => {
+                eprintln!("slugr: warning: cannot read '{}': {e}", dir.display());
+                Vec::new()
+            }
+        };
+        self.stack.push(Frame { dir, depth, entries: entries.into_iter() });
+    }
+}
+
+impl Iterator for BufferedWalk {
+    type Item = PathBuf;
+
+    fn next(&mut self) -> Option<PathBuf> {
+        loop {
+            let frame = self.stack.last_mut()?;
+            let depth = frame.depth + 1;
+            match frame.entries.next() {
+                Some((path, true)) if self.max_depth.is_none_or(|max| depth < max) => self.enter(path, depth),
+                Some((path, _)) => return Some(path),
+                None => {
+                    let frame = self.stack.pop()?;
+                    // Depth 0 is the root directory itself, which is skipped
+                    if frame.depth > 0 {
+                        return Some(frame.dir);
+                    }
                 }
-            };
-            // Skip the root directory itself
-            if entry.path() == path {
+            }
+        }
+    }
+}
+
+/// Paths to rename, in the given `order`. Walks lazily where the backend
+/// allows it ([`collect_paths_iter`]) and falls back to [`collect_paths`];
+/// sorting always needs the full list.
+pub fn walk_paths(
+    paths: &[PathBuf],
+    recursive: bool,
+    backend: WalkBackend,
+    order: WalkOrder,
+    sort: bool,
+) -> Box<dyn Iterator<Item = PathBuf>> {
+    if backend == WalkBackend::WalkDir && order == WalkOrder::BottomUp && !sort {
+        Box::new(collect_paths_iter(paths, recursive, None))
+    } else {
+        Box::new(collect_paths(paths, recursive, backend, order, sort).into_iter())
+    }
+}
+
+fn walk_walkdir(path: &Path, order: WalkOrder, result: &mut Vec<PathBuf>) {
+    // Bottom-up is WalkDir's contents_first mode
+    for entry in WalkDir::new(path).contents_first(order == WalkOrder::BottomUp) {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                eprintln!("slugr: warning: {e}");
                 continue;
             }
-            result.push(entry.path().to_path_buf());
+        };
+        // Skip the root directory itself
+        if entry.path() == path {
+            continue;
         }
+        result.push(entry.path().to_path_buf());
     }
+}
 
-    result
+#[cfg(feature = "ignore")]
+fn walk_ignore(path: &Path, order: WalkOrder, result: &mut Vec<PathBuf>) {
+    // WalkBuilder only walks top-down; for bottom-up, reversing the pre-order
+    // listing puts every entry before all of its ancestors.
+    let mut entries = Vec::new();
+    for entry in ignore::WalkBuilder::new(path).build() {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                eprintln!("slugr: warning: {e}");
+                continue;
+            }
+        };
+        // Skip the root directory itself
+        if entry.depth() == 0 {
+            continue;
+        }
+        entries.push(entry.into_path());
+    }
+    match order {
+        WalkOrder::BottomUp => result.extend(entries.into_iter().rev()),
+        WalkOrder::TopDown => result.extend(entries),
+    }
 }
 
 #[cfg(test)]
@@ -50,7 +287,7 @@ mod tests {
         let file = dir.path().join("test.txt");
         fs::write(&file, "hello").unwrap();
 
-        let result = collect_paths(std::slice::from_ref(&file), false);
+        let result = collect_paths(std::slice::from_ref(&file), false, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
         assert_eq!(result, vec![file]);
     }
 
@@ -62,7 +299,7 @@ mod tests {
         fs::write(&file_a, "a").unwrap();
         fs::write(&file_b, "b").unwrap();
 
-        let result = collect_paths(&[file_a.clone(), file_b.clone()], false);
+        let result = collect_paths(&[file_a.clone(), file_b.clone()], false, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
         assert_eq!(result.len(), 2);
         assert!(result.contains(&file_a));
         assert!(result.contains(&file_b));
@@ -76,7 +313,7 @@ mod tests {
         let file = sub.join("Nested File.txt");
         fs::write(&file, "nested").unwrap();
 
-        let result = collect_paths(&[dir.path().to_path_buf()], true);
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
 
         // File should come before directory (bottom-up)
         let file_pos = result.iter().position(|p| p == &file).unwrap();
@@ -90,21 +327,21 @@ mod tests {
         let file = dir.path().join("test.txt");
         fs::write(&file, "hello").unwrap();
 
-        let result = collect_paths(&[dir.path().to_path_buf()], true);
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
         // Should not include the root directory itself
         assert!(!result.contains(&dir.path().to_path_buf()));
     }
 
     #[test]
     fn test_collect_non_existent_path() {
-        let result = collect_paths(&[PathBuf::from("/tmp/definitely-does-not-exist-slug-test")], false);
+        let result = collect_paths(&[PathBuf::from("/tmp/definitely-does-not-exist-slug-test")], false, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
         assert!(result.is_empty());
     }
 
     #[test]
     fn test_collect_empty_directory_recursive() {
         let dir = tempfile::tempdir().unwrap();
-        let result = collect_paths(&[dir.path().to_path_buf()], true);
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
         assert!(result.is_empty());
     }
 
@@ -117,7 +354,7 @@ mod tests {
         let link = dir.path().join("link.txt");
         std::os::unix::fs::symlink(&real_file, &link).unwrap();
 
-        let result = collect_paths(&[dir.path().to_path_buf()], true);
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
         // Symlink should appear in results
         assert!(result.contains(&link));
         assert!(result.contains(&real_file));
@@ -134,7 +371,7 @@ mod tests {
         let deep_file = current.join("deep.txt");
         fs::write(&deep_file, "deep").unwrap();
 
-        let result = collect_paths(&[dir.path().to_path_buf()], true);
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
         assert!(result.contains(&deep_file), "deep file should be collected");
         // File should come before all its parent dirs (bottom-up)
         let file_pos = result.iter().position(|p| p == &deep_file).unwrap();
@@ -158,7 +395,7 @@ mod tests {
         fs::write(&file_a, "a").unwrap();
         fs::write(&file_b, "b").unwrap();
 
-        let result = collect_paths(&[dir.path().to_path_buf()], true);
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
         assert!(result.contains(&file_a));
         assert!(result.contains(&file_b));
         assert!(result.contains(&sub));
@@ -167,4 +404,189 @@ mod tests {
         let sub_pos = result.iter().position(|p| p == &sub).unwrap();
         assert!(b_pos < sub_pos, "files in subdir must come before subdir");
     }
+
+    #[test]
+    fn test_collect_walkdir_includes_git_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = dir.path().join(".git");
+        fs::create_dir(&git).unwrap();
+        fs::write(git.join("HEAD"), "ref").unwrap();
+
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
+        assert!(result.contains(&git.join("HEAD")));
+    }
+
+    #[cfg(feature = "ignore")]
+    #[test]
+    fn test_collect_ignore_skips_git_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = dir.path().join(".git");
+        fs::create_dir(&git).unwrap();
+        fs::write(git.join("HEAD"), "ref").unwrap();
+        let file = dir.path().join("My File.txt");
+        fs::write(&file, "x").unwrap();
+
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::Ignore, WalkOrder::BottomUp, false);
+        assert_eq!(result, vec![file]);
+    }
+
+    #[cfg(feature = "ignore")]
+    #[test]
+    fn test_collect_ignore_respects_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        let target = dir.path().join("target");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("Build Output.o"), "x").unwrap();
+        let file = dir.path().join("Kept File.txt");
+        fs::write(&file, "x").unwrap();
+
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::Ignore, WalkOrder::BottomUp, false);
+        assert_eq!(result, vec![file]);
+    }
+
+    #[cfg(feature = "ignore")]
+    #[test]
+    fn test_collect_ignore_bottom_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("Sub Dir");
+        fs::create_dir(&sub).unwrap();
+        let file = sub.join("Nested File.txt");
+        fs::write(&file, "nested").unwrap();
+
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::Ignore, WalkOrder::BottomUp, false);
+        assert_eq!(result, vec![file, sub]);
+    }
+
+    /// Nested tree: root/a/b/file.txt plus root/top.txt.
+    fn nested_tree() -> (tempfile::TempDir, PathBuf, PathBuf, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        let b = a.join("b");
+        fs::create_dir_all(&b).unwrap();
+        let file = b.join("file.txt");
+        fs::write(&file, "x").unwrap();
+        fs::write(dir.path().join("top.txt"), "x").unwrap();
+        (dir, a, b, file)
+    }
+
+    fn position(result: &[PathBuf], path: &Path) -> usize {
+        result.iter().position(|p| p == path).unwrap()
+    }
+
+    #[test]
+    fn test_walk_order_bottom_up() {
+        let (dir, a, b, file) = nested_tree();
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::BottomUp, false);
+        assert_eq!(result.len(), 4);
+        assert!(position(&result, &file) < position(&result, &b));
+        assert!(position(&result, &b) < position(&result, &a));
+    }
+
+    #[test]
+    fn test_walk_order_top_down() {
+        let (dir, a, b, file) = nested_tree();
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::TopDown, false);
+        assert_eq!(result.len(), 4);
+        assert!(position(&result, &a) < position(&result, &b));
+        assert!(position(&result, &b) < position(&result, &file));
+    }
+
+    #[test]
+    fn test_collect_paths_iter_matches_eager() {
+        let (dir, ..) = nested_tree();
+        let root = [dir.path().to_path_buf()];
+        let lazy: Vec<_> = collect_paths_iter(&root, true, None).collect();
+        assert_eq!(lazy, collect_paths(&root, true, WalkBackend::WalkDir, WalkOrder::BottomUp, false));
+    }
+
+    #[test]
+    fn test_collect_paths_iter_reads_directory_before_renaming() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..2000 {
+            fs::write(dir.path().join(format!("file {i}.txt")), "").unwrap();
+        }
+
+        let mut seen = 0;
+        for path in collect_paths_iter(&[dir.path().to_path_buf()], true, None) {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            assert!(!name.starts_with("renamed"), "{name} came back after its rename");
+            fs::rename(&path, dir.path().join(format!("renamed {name}"))).unwrap();
+            seen += 1;
+        }
+        assert_eq!(seen, 2000);
+    }
+
+    #[test]
+    fn test_collect_paths_iter_max_depth() {
+        let (dir, a, ..) = nested_tree();
+        let mut result: Vec<_> = collect_paths_iter(&[dir.path().to_path_buf()], true, Some(1)).collect();
+        result.sort();
+        assert_eq!(result, vec![a, dir.path().join("top.txt")]);
+    }
+
+    #[test]
+    fn test_collect_paths_iter_non_recursive_and_missing() {
+        let (dir, a, ..) = nested_tree();
+        let missing = dir.path().join("missing");
+        let result: Vec<_> = collect_paths_iter(&[a.clone(), missing], false, None).collect();
+        assert_eq!(result, vec![a]);
+    }
+
+    #[test]
+    fn test_collect_sorted_independent_of_creation_order() {
+        let names = ["delta.txt", "Alpha.txt", "charlie", "charlie/zulu.txt", "charlie/bravo.txt", "echo.txt"];
+        // A fixed shuffle: creation order should not matter
+        let mut listings = Vec::new();
+        for order in [[0, 1, 2, 3, 4, 5], [5, 2, 4, 0, 3, 1]] {
+            let dir = tempfile::tempdir().unwrap();
+            for i in order {
+                let path = dir.path().join(names[i]);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                if names[i] == "charlie" {
+                    fs::create_dir_all(&path).unwrap();
+                } else {
+                    fs::write(&path, "x").unwrap();
+                }
+            }
+            let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::WalkDir, WalkOrder::BottomUp, true);
+            let relative: Vec<_> = result.iter().map(|p| p.strip_prefix(dir.path()).unwrap().to_path_buf()).collect();
+            listings.push(relative);
+        }
+        let expected: Vec<PathBuf> =
+            ["Alpha.txt", "charlie/bravo.txt", "charlie/zulu.txt", "charlie", "delta.txt", "echo.txt"]
+                .iter()
+                .map(PathBuf::from)
+                .collect();
+        assert_eq!(listings[0], expected);
+        assert_eq!(listings[1], expected);
+    }
+
+    #[test]
+    fn test_sort_paths_keeps_order() {
+        let mut paths: Vec<PathBuf> = ["b", "a/x", "a", "a/x/y"].iter().map(PathBuf::from).collect();
+        sort_paths(&mut paths, WalkOrder::BottomUp);
+        assert_eq!(paths, ["a/x/y", "a/x", "a", "b"].iter().map(PathBuf::from).collect::<Vec<_>>());
+        sort_paths(&mut paths, WalkOrder::TopDown);
+        assert_eq!(paths, ["a", "a/x", "a/x/y", "b"].iter().map(PathBuf::from).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_entry_filter() {
+        let (_dir, a, _b, file) = nested_tree();
+        assert!(EntryFilter::All.matches(&a) && EntryFilter::All.matches(&file));
+        assert!(EntryFilter::DirsOnly.matches(&a) && !EntryFilter::DirsOnly.matches(&file));
+        assert!(!EntryFilter::FilesOnly.matches(&a) && EntryFilter::FilesOnly.matches(&file));
+    }
+
+    #[cfg(feature = "ignore")]
+    #[test]
+    fn test_walk_order_top_down_ignore_backend() {
+        let (dir, a, b, file) = nested_tree();
+        let result = collect_paths(&[dir.path().to_path_buf()], true, WalkBackend::Ignore, WalkOrder::TopDown, false);
+        assert_eq!(result.len(), 4);
+        assert!(position(&result, &a) < position(&result, &b));
+        assert!(position(&result, &b) < position(&result, &file));
+    }
 }
@@ -1,9 +1,17 @@
 use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
 use std::process::Command;
 
 fn slug_bin() -> Command {
-    Command::new(env!("CARGO_BIN_EXE_slugr"))
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_slugr"));
+    // Keep the caller's configuration out of the tests
+    for var in ["SLUGR_STYLE", "SLUGR_KEEP_UNICODE", "SLUGR_MAX_LENGTH", "SLUGR_COLLISION"] {
+        cmd.env_remove(var);
+    }
+    // An absolute XDG_CONFIG_HOME with no slugr/config.toml in it
+    cmd.env("XDG_CONFIG_HOME", "/nonexistent");
+    cmd
 }
 
 #[test]
@@ -79,6 +87,23 @@ fn test_default_no_clobber_appends_suffix() {
     assert_eq!(fs::read_to_string(dir.path().join("my-file-2.txt")).unwrap(), "new");
 }
 
+#[test]
+fn test_collision_hash_suffix() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("my-file.txt"), "existing").unwrap();
+    let file = dir.path().join("My File.txt");
+    fs::write(&file, "hello").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--collision", "hash"])
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.path().join("my-file-2cf24dba.txt")).unwrap(), "hello");
+}
+
 #[test]
 fn test_snake_case_flag() {
     let dir = tempfile::tempdir().unwrap();
@@ -701,3 +726,1710 @@ fn test_pipe_conflicts_with_file_args() {
     let stderr = String::from_utf8(output.stderr).unwrap();
     assert!(stderr.contains("cannot be used with"), "expected conflict error, got: {stderr}");
 }
+
+// --- Count mode integration tests ---
+
+#[test]
+fn test_count_mode() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My File.txt"), "a").unwrap();
+    fs::write(dir.path().join("Other File.pdf"), "b").unwrap();
+    fs::write(dir.path().join("already-clean.txt"), "c").unwrap();
+
+    let output = slug_bin()
+        .arg("--count")
+        .arg("-r")
+        .arg(dir.path().to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), "2 files would be renamed");
+    // Nothing should be touched
+    assert!(dir.path().join("My File.txt").exists());
+    assert!(dir.path().join("Other File.pdf").exists());
+}
+
+#[test]
+fn test_count_mode_by_ext() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("A File.txt"), "a").unwrap();
+    fs::write(dir.path().join("B File.txt"), "b").unwrap();
+    fs::write(dir.path().join("C File.pdf"), "c").unwrap();
+    fs::write(dir.path().join("NO EXT"), "d").unwrap();
+
+    let output = slug_bin()
+        .arg("--count")
+        .arg("--count-by-ext")
+        .arg("-r")
+        .arg(dir.path().to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, vec!["4 files would be renamed", "  (none): 1", "  .pdf: 1", "  .txt: 2"]);
+}
+
+#[test]
+fn test_count_conflicts_with_execute_e2e() {
+    let output = slug_bin()
+        .arg("--count")
+        .arg("-x")
+        .arg("file.txt")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cannot be used with"), "expected conflict error, got: {stderr}");
+}
+
+// --- --log-jsonl integration tests ---
+
+#[test]
+fn test_log_jsonl_records_renames() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("renames.jsonl");
+    let file = dir.path().join("My File.txt");
+    fs::write(&file, "a").unwrap();
+    fs::write(dir.path().join("clean.txt"), "b").unwrap();
+    let before = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--log-jsonl")
+        .arg(log.to_str().unwrap())
+        .arg(file.to_str().unwrap())
+        .arg(dir.path().join("clean.txt").to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let text = fs::read_to_string(&log).unwrap();
+    let records: Vec<serde_json::Value> = text.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(records.len(), 1, "got: {text}");
+    assert_eq!(records[0]["from"], file.to_str().unwrap());
+    assert_eq!(records[0]["to"], dir.path().join("my-file.txt").to_str().unwrap());
+    assert_eq!(records[0]["status"], "ok");
+    assert!(records[0]["ms"].is_u64());
+    assert!(records[0]["ts"].as_u64().unwrap() >= before);
+}
+
+#[test]
+fn test_log_jsonl_requires_execute() {
+    let output = slug_bin()
+        .arg("--log-jsonl")
+        .arg("renames.jsonl")
+        .arg("file.txt")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("--execute"), "expected requires error, got: {stderr}");
+}
+
+// --- --suffix-digits integration tests ---
+
+#[test]
+fn test_suffix_digits_numbers_every_file() {
+    let dir = tempfile::tempdir().unwrap();
+    for i in 0..10 {
+        let sub = dir.path().join(format!("batch{i}"));
+        fs::create_dir(&sub).unwrap();
+        fs::write(sub.join("scan.pdf"), format!("{i}")).unwrap();
+    }
+
+    let output = slug_bin()
+        .args(["-rx", "--sort", "--files-only", "--suffix-digits", "2"])
+        .arg(dir.path().to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    for i in 0..10 {
+        let sub = dir.path().join(format!("batch{i}"));
+        let renamed = sub.join(format!("scan-{:02}.pdf", i + 1));
+        assert_eq!(fs::read_to_string(&renamed).unwrap(), format!("{i}"), "{}", renamed.display());
+        assert!(!sub.join("scan.pdf").exists());
+    }
+}
+
+#[test]
+fn test_recursive_rename_renames_each_file_once() {
+    let dir = tempfile::tempdir().unwrap();
+    for i in 0..2000 {
+        fs::write(dir.path().join(format!("File Number {i}.txt")), "").unwrap();
+    }
+
+    let output = slug_bin()
+        .args(["-rx", "--stats", "--suffix-digits", "5"])
+        .arg(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("slugr: 2000 renamed,"));
+    for entry in fs::read_dir(dir.path()).unwrap() {
+        let name = entry.unwrap().file_name().into_string().unwrap();
+        // file-number-N-NNNNN.txt: one counter, not one per time the entry was seen
+        assert_eq!(name.matches('-').count(), 3, "{name}");
+    }
+}
+
+#[test]
+fn test_suffix_digits_start_at() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("My Scan.pdf");
+    fs::write(&file, "a").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--suffix-digits", "3", "--start-at", "42"])
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(dir.path().join("my-scan-042.pdf").exists());
+}
+
+// --- --extra-extensions integration tests ---
+
+#[test]
+fn test_extra_extensions_kept_together() {
+    let dir = tempfile::tempdir().unwrap();
+    let list = dir.path().join("compound.txt");
+    fs::write(&list, "# neuroimaging\n.nii.gz\n.ipynb.bak\n").unwrap();
+    let brain = dir.path().join("Brain Scan.nii.gz");
+    let notebook = dir.path().join("My Analysis.ipynb.bak");
+    fs::write(&brain, "a").unwrap();
+    fs::write(&notebook, "b").unwrap();
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--extra-extensions")
+        .arg(&list)
+        .args([&brain, &notebook])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join("brain-scan.nii.gz").exists());
+    assert!(dir.path().join("my-analysis.ipynb.bak").exists());
+}
+
+#[test]
+fn test_extra_extensions_invalid_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let list = dir.path().join("compound.txt");
+    fs::write(&list, ".nii.gz\ntar.gz\n").unwrap();
+    let file = dir.path().join("My File.txt");
+    fs::write(&file, "a").unwrap();
+
+    let output = slug_bin().arg("-x").arg("--extra-extensions").arg(&list).arg(&file).output().unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("line 2"), "stderr: {stderr}");
+    assert!(file.exists());
+}
+
+// --- --min-change integration tests ---
+
+#[test]
+fn test_min_change_skips_small_renames() {
+    let dir = tempfile::tempdir().unwrap();
+    let case_only = dir.path().join("File.PDF");
+    let spaced = dir.path().join("My Report.PDF");
+    fs::write(&case_only, "a").unwrap();
+    fs::write(&spaced, "b").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--min-change", "2"])
+        .args([&case_only, &spaced])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    // "File.PDF" -> "file.PDF" is one edit, below the threshold
+    let names: Vec<String> = fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+    assert!(names.contains(&"File.PDF".to_string()), "{names:?}");
+    assert!(names.contains(&"my-report.PDF".to_string()), "{names:?}");
+}
+
+#[test]
+fn test_min_change_count() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("File.PDF");
+    fs::write(&file, "a").unwrap();
+
+    let output = slug_bin().args(["--count", "--min-change", "2"]).arg(&file).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), "0 files would be renamed");
+}
+
+#[test]
+fn test_min_change_with_suffix_digits() {
+    let dir = tempfile::tempdir().unwrap();
+    let case_only = dir.path().join("File.PDF");
+    let spaced = dir.path().join("My Report.PDF");
+    fs::write(&case_only, "a").unwrap();
+    fs::write(&spaced, "b").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--min-change", "2", "--suffix-digits", "3"])
+        .args([&case_only, &spaced])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    // The kept name is not numbered, and doesn't use up a count
+    let names: Vec<String> = fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+    assert!(names.contains(&"File.PDF".to_string()), "{names:?}");
+    assert!(names.contains(&"my-report-001.PDF".to_string()), "{names:?}");
+}
+
+// --- --check-conflicts integration tests ---
+
+/// Run `slugr -x --sort --check-conflicts` on three names that all slug to
+/// `my-file.txt`, answering the prompt with `answer`.
+fn run_check_conflicts(dir: &std::path::Path, answer: &str) -> std::process::Output {
+    let names = ["My File.txt", "my_file.txt", "MY-FILE.txt"];
+    for name in names {
+        fs::write(dir.join(name), name).unwrap();
+    }
+    let mut child = slug_bin()
+        .args(["-x", "--sort", "--check-conflicts"])
+        .args(names.map(|name| dir.join(name)))
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.as_mut().unwrap().write_all(answer.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_check_conflicts_declined() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = run_check_conflicts(dir.path(), "n\n");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("3 files would be renamed to"), "stderr: {stderr}");
+    assert!(stderr.contains("My File.txt") && stderr.contains("my_file.txt") && stderr.contains("MY-FILE.txt"));
+    assert!(!dir.path().join("my-file.txt").exists());
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+}
+
+#[test]
+fn test_check_conflicts_accepted() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = run_check_conflicts(dir.path(), "y\n");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    for name in ["my-file.txt", "my-file-2.txt", "my-file-3.txt"] {
+        assert!(dir.path().join(name).exists(), "{name}");
+    }
+}
+
+#[test]
+fn test_check_conflicts_none_does_not_prompt() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("Only File.txt");
+    fs::write(&file, "a").unwrap();
+
+    let output = slug_bin().args(["-x", "--check-conflicts"]).arg(&file).output().unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(!stderr.contains("proceed"));
+    assert!(dir.path().join("only-file.txt").exists());
+}
+
+// --- --rename-to-hash integration tests ---
+
+const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+#[test]
+fn test_rename_to_hash_skips_duplicates() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("Photo A.JPG");
+    let copy = dir.path().join("photo copy.jpg");
+    let other = dir.path().join("Other.txt");
+    fs::write(&first, "hello").unwrap();
+    fs::write(&copy, "hello").unwrap();
+    fs::write(&other, "other").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--sort", "--rename-to-hash"])
+        .args([first.to_str().unwrap(), copy.to_str().unwrap(), other.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(stderr.contains("same content as"), "stderr: {stderr}");
+    assert!(dir.path().join(format!("{HELLO_SHA256}.JPG")).exists());
+    assert!(!first.exists());
+    assert!(copy.exists(), "duplicate must be left in place");
+    assert!(dir.path().join("d9298a10d1b0735837dc4bd85dac641b0f3cef27a47e5d53a54f2f3f5b2fcffa.txt").exists());
+}
+
+#[test]
+fn test_rename_to_hash_algorithm_and_prefix() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("notes.md");
+    fs::write(&file, "hello").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--rename-to-hash=sha256", "--hash-prefix", "12"])
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join(format!("{}.md", &HELLO_SHA256[..12])).exists());
+    assert!(!file.exists());
+}
+
+#[test]
+fn test_rename_to_hash_keeps_extra_extensions() {
+    let dir = tempfile::tempdir().unwrap();
+    let list = dir.path().join("compound.txt");
+    fs::write(&list, ".warc.gz\n").unwrap();
+    let file = dir.path().join("Crawl.warc.gz");
+    fs::write(&file, "hello").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--rename-to-hash", "--hash-prefix", "12", "--extra-extensions"])
+        .arg(&list)
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join(format!("{}.warc.gz", &HELLO_SHA256[..12])).exists());
+}
+
+#[test]
+fn test_rename_to_hash_applies_extension_map() {
+    let dir = tempfile::tempdir().unwrap();
+    let map = dir.path().join("extensions.toml");
+    fs::write(&map, "tiff = \"tif\"\n").unwrap();
+    let photo = dir.path().join("Photo.jpeg");
+    let scan = dir.path().join("Scan.tiff");
+    fs::write(&photo, "hello").unwrap();
+    fs::write(&scan, "hello world").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--rename-to-hash", "--hash-prefix", "12", "--extension-map"])
+        .arg(&map)
+        .arg(&photo)
+        .arg(&scan)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join(format!("{}.jpg", &HELLO_SHA256[..12])).exists());
+    assert!(!photo.exists());
+    assert!(!scan.exists());
+    let names: Vec<PathBuf> = fs::read_dir(dir.path()).unwrap().map(|e| PathBuf::from(e.unwrap().file_name())).collect();
+    assert!(names.iter().any(|n| n.extension().is_some_and(|ext| ext == "tif")), "{names:?}");
+}
+
+#[test]
+fn test_rename_to_hash_dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("notes.md");
+    fs::write(&file, "hello").unwrap();
+
+    let output = slug_bin().args(["--rename-to-hash=blake3"]).arg(file.to_str().unwrap()).output().unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(file.exists());
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_hash_prefix_requires_rename_to_hash() {
+    let output = slug_bin().args(["--hash-prefix", "8", "foo.txt"]).output().unwrap();
+    assert!(!output.status.success());
+}
+
+// --- Check mode integration tests ---
+
+#[test]
+fn test_check_clean_directory_exits_zero() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("already-clean.txt"), "a").unwrap();
+    fs::write(dir.path().join("notes-1.2.md"), "b").unwrap();
+
+    let output = slug_bin()
+        .arg("--check")
+        .arg("-r")
+        .arg(dir.path().to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(output.stderr.is_empty(), "got: {}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_check_mixed_directory_exits_one() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("already-clean.txt"), "a").unwrap();
+    fs::write(dir.path().join("My File.txt"), "b").unwrap();
+
+    let output = slug_bin()
+        .arg("--check")
+        .arg("-r")
+        .arg(dir.path().to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let expected = format!("{} -> my-file.txt\n", dir.path().join("My File.txt").display());
+    assert_eq!(stderr, expected);
+    // Nothing should be touched
+    assert!(dir.path().join("My File.txt").exists());
+}
+
+#[test]
+fn test_check_format_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("My File.txt");
+    fs::write(&file, "a").unwrap();
+
+    let output = slug_bin()
+        .arg("--check")
+        .arg("--check-format")
+        .arg("json")
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&stderr).unwrap();
+    assert_eq!(report, serde_json::json!([{ "path": file.to_str().unwrap(), "slug": "my-file.txt" }]));
+}
+
+#[test]
+fn test_check_conflicts_with_execute_e2e() {
+    let output = slug_bin()
+        .arg("--check")
+        .arg("-x")
+        .arg("file.txt")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cannot be used with"), "expected conflict error, got: {stderr}");
+}
+
+// --- --to-dir integration tests ---
+
+#[test]
+fn test_to_dir_moves_renamed_files() {
+    let src = tempfile::tempdir().unwrap();
+    let dst = tempfile::tempdir().unwrap();
+    let file = src.path().join("My File.txt");
+    let untouched = src.path().join("Other File.txt");
+    fs::write(&file, "hello").unwrap();
+    fs::write(&untouched, "other").unwrap();
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--to-dir")
+        .arg(dst.path().to_str().unwrap())
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dst.path().join("my-file.txt")).unwrap(), "hello");
+    assert!(!file.exists());
+    assert!(!src.path().join("my-file.txt").exists());
+    // Files not passed on the command line stay where they were
+    assert!(untouched.exists());
+}
+
+#[test]
+fn test_to_dir_created_if_missing() {
+    let src = tempfile::tempdir().unwrap();
+    let root = tempfile::tempdir().unwrap();
+    let dst = root.path().join("staging");
+    let file = src.path().join("My File.txt");
+    fs::write(&file, "hello").unwrap();
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--to-dir")
+        .arg(dst.to_str().unwrap())
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(dst.join("my-file.txt").exists());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("creating directory"), "expected creation warning, got: {stderr}");
+}
+
+#[test]
+fn test_to_dir_dry_run_does_not_create() {
+    let src = tempfile::tempdir().unwrap();
+    let root = tempfile::tempdir().unwrap();
+    let dst = root.path().join("staging");
+    let file = src.path().join("My File.txt");
+    fs::write(&file, "hello").unwrap();
+
+    let output = slug_bin()
+        .arg("--to-dir")
+        .arg(dst.to_str().unwrap())
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(!dst.exists());
+    assert!(file.exists());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("staging"), "expected target in staging dir, got: {stdout}");
+}
+
+#[test]
+fn test_to_dir_collision_checked_in_destination() {
+    let src = tempfile::tempdir().unwrap();
+    let dst = tempfile::tempdir().unwrap();
+    fs::write(dst.path().join("my-file.txt"), "existing").unwrap();
+    // A clashing name in the source dir must not matter
+    fs::write(src.path().join("my-file-2.txt"), "source sibling").unwrap();
+    let file = src.path().join("My File.txt");
+    fs::write(&file, "new").unwrap();
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--to-dir")
+        .arg(dst.path().to_str().unwrap())
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dst.path().join("my-file.txt")).unwrap(), "existing");
+    assert_eq!(fs::read_to_string(dst.path().join("my-file-2.txt")).unwrap(), "new");
+}
+
+// --- --link integration tests ---
+
+#[test]
+fn test_link_mode_keeps_original() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("My File.txt");
+    fs::write(&file, "hello").unwrap();
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--link")
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let linked = dir.path().join("my-file.txt");
+    assert!(file.exists());
+    assert!(linked.exists());
+    assert_eq!(fs::read_to_string(&file).unwrap(), fs::read_to_string(&linked).unwrap());
+}
+
+#[test]
+fn test_link_mode_skips_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    let sub = dir.path().join("Sub Dir");
+    fs::create_dir(&sub).unwrap();
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--link")
+        .arg(sub.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(sub.exists());
+    assert!(!dir.path().join("sub-dir").exists());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("skipping directory"), "expected skip warning, got: {stderr}");
+}
+
+#[test]
+fn test_pipe_mode_preset_url() {
+    let output = slug_bin()
+        .arg("--pipe")
+        .arg("--raw")
+        .arg("--preset")
+        .arg("url")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            child.stdin.take().unwrap().write_all(b"The Art of the Deal\n").unwrap();
+            child.wait_with_output()
+        })
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), "art-of-deal");
+}
+
+#[test]
+fn test_explain_prints_trace() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("My File (Draft).txt");
+    fs::write(&file, "hello").unwrap();
+
+    let output = slug_bin()
+        .arg("--explain")
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("transliterate"), "expected trace, got: {stdout}");
+    let last = stdout.lines().last().unwrap();
+    assert!(last.starts_with("reattach extension"), "expected final stage, got: {stdout}");
+    assert!(last.ends_with(" my-file-draft.txt"), "expected final slug, got: {stdout}");
+    // Explain never renames
+    assert!(file.exists());
+}
+
+// --- Rename pairs integration tests ---
+
+#[test]
+fn test_export_then_apply_pairs_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let pairs_file = tempfile::NamedTempFile::new().unwrap();
+    fs::write(dir.path().join("My File.txt"), "one").unwrap();
+    fs::write(dir.path().join("Other File.pdf"), "two").unwrap();
+
+    let output = slug_bin()
+        .arg("-r")
+        .arg("--export-pairs")
+        .arg(pairs_file.path())
+        .arg(dir.path().to_str().unwrap())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    // Export is a dry run — nothing moved yet
+    assert!(dir.path().join("My File.txt").exists());
+    let exported = fs::read_to_string(pairs_file.path()).unwrap();
+    assert_eq!(exported.lines().count(), 2, "got: {exported}");
+    assert!(exported.lines().all(|l| l.contains('\t')));
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--from-pairs")
+        .arg(pairs_file.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.path().join("my-file.txt")).unwrap(), "one");
+    assert_eq!(fs::read_to_string(dir.path().join("other-file.pdf")).unwrap(), "two");
+}
+
+#[test]
+fn test_from_pairs_hand_crafted_names() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("scan001.jpg");
+    fs::write(&source, "img").unwrap();
+    let target = dir.path().join("holiday-beach.jpg");
+    let pairs_file = dir.path().join("pairs.tsv");
+    fs::write(&pairs_file, format!("{}\t{}\n", source.display(), target.display())).unwrap();
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--from-pairs")
+        .arg(&pairs_file)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(!source.exists());
+    assert_eq!(fs::read_to_string(&target).unwrap(), "img");
+}
+
+#[test]
+fn test_from_pairs_respects_no_clobber_and_dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("a.txt");
+    let target = dir.path().join("b.txt");
+    fs::write(&source, "new").unwrap();
+    fs::write(&target, "existing").unwrap();
+    let pairs_file = dir.path().join("pairs.tsv");
+    fs::write(&pairs_file, format!("{}\t{}\n", source.display(), target.display())).unwrap();
+
+    let output = slug_bin().arg("--from-pairs").arg(&pairs_file).output().unwrap();
+    assert!(output.status.success());
+    assert!(source.exists(), "dry run must not rename");
+
+    let output = slug_bin().arg("-x").arg("--from-pairs").arg(&pairs_file).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(&target).unwrap(), "existing");
+    assert_eq!(fs::read_to_string(dir.path().join("b-2.txt")).unwrap(), "new");
+}
+
+#[test]
+fn test_from_pairs_malformed_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let pairs_file = dir.path().join("pairs.tsv");
+    fs::write(&pairs_file, "no tab on this line\n").unwrap();
+
+    let output = slug_bin().arg("--from-pairs").arg(&pairs_file).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("line 1"), "expected line number in error, got: {stderr}");
+}
+
+#[test]
+fn test_pipe_mode_filter_unchanged() {
+    let output = slug_bin()
+        .arg("--pipe")
+        .arg("--filter-unchanged")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            child.stdin.take().unwrap().write_all(b"already-clean.txt\nMy File.txt\n").unwrap();
+            child.wait_with_output()
+        })
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, vec!["my-file.txt"]);
+}
+
+#[test]
+fn test_pipe_mode_keeps_unchanged_by_default() {
+    let output = slug_bin()
+        .arg("--pipe")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            child.stdin.take().unwrap().write_all(b"already-clean.txt\nMy File.txt\n").unwrap();
+            child.wait_with_output()
+        })
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, vec!["already-clean.txt", "my-file.txt"]);
+}
+
+#[test]
+fn test_atomic_rolls_back_on_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("A File.txt");
+    let b = dir.path().join("B File.txt");
+    fs::write(&a, "a").unwrap();
+    fs::write(&b, "b").unwrap();
+    // Passes validation, but there is no directory to rename into
+    let unreachable = dir.path().join("missing").join("b-file.txt");
+    let plan = dir.path().join("plan.tsv");
+    fs::write(
+        &plan,
+        format!("{}\t{}\n{}\t{}\n", a.display(), dir.path().join("a-file.txt").display(), b.display(), unreachable.display()),
+    )
+    .unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--atomic", "--from-pairs"])
+        .arg(&plan)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no files were renamed"), "stderr: {stderr}");
+    assert!(a.exists());
+    assert!(!dir.path().join("a-file.txt").exists());
+}
+
+#[test]
+fn test_atomic_renames_all() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("A File.txt"), "a").unwrap();
+    fs::write(dir.path().join("B File.txt"), "b").unwrap();
+
+    let output = slug_bin()
+        .args(["-rx", "--atomic"])
+        .arg(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(dir.path().join("a-file.txt").exists());
+    assert!(dir.path().join("b-file.txt").exists());
+}
+
+#[test]
+fn test_pipe_mode_large_input() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    const LINES: usize = 100_000;
+    let mut child = slug_bin()
+        .args(["--pipe", "--pipe-batch-size", "4096"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = std::thread::spawn(move || {
+        for i in 0..LINES {
+            writeln!(stdin, "My File {i}.txt").unwrap();
+        }
+    });
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), LINES);
+    assert_eq!(stdout.lines().next(), Some("my-file-0.txt"));
+    assert_eq!(stdout.lines().last(), Some("my-file-99999.txt"));
+}
+
+#[test]
+fn test_no_version_dots_flag() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("app-1.2.3.tar.gz");
+    fs::write(&file, "x").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--no-version-dots"])
+        .arg(file.to_str().unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(dir.path().join("app-1-2-3.tar.gz").exists());
+}
+
+#[test]
+fn test_stats_summary() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My File.txt"), "a").unwrap();
+    fs::write(dir.path().join("clean.txt"), "b").unwrap();
+
+    let output = slug_bin()
+        .args(["-x", "--stats"])
+        .arg(dir.path().join("My File.txt"))
+        .arg(dir.path().join("clean.txt"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("slugr: 1 renamed, 1 unchanged, 0 failed"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_sort_output_is_ordered() {
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["Zeta File.txt", "Alpha File.txt", "Mid File.txt"] {
+        fs::write(dir.path().join(name), "x").unwrap();
+    }
+
+    let output = slug_bin().args(["-r", "--sort"]).arg(dir.path()).output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let targets: Vec<_> = stdout.lines().filter_map(|l| l.rsplit('/').next()).collect();
+    assert_eq!(targets, ["alpha-file.txt", "mid-file.txt", "zeta-file.txt"]);
+}
+
+#[test]
+fn test_diff_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("My File.txt");
+    fs::write(&file, "x").unwrap();
+
+    let output = slug_bin().arg("--diff").arg(&file).output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "[-M-]{+m+}y[- F-]{+-f+}ile.txt\n");
+}
+
+#[test]
+fn test_env_style_and_flag_override() {
+    let output = slug_bin()
+        .args(["--pipe"])
+        .env("SLUGR_STYLE", "snake")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            child.stdin.take().unwrap().write_all(b"My File.txt\n")?;
+            child.wait_with_output()
+        })
+        .unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "my_file.txt\n");
+
+    let output = slug_bin()
+        .args(["--pipe", "--pascal"])
+        .env("SLUGR_STYLE", "snake")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            child.stdin.take().unwrap().write_all(b"My File.txt\n")?;
+            child.wait_with_output()
+        })
+        .unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "MyFile.txt\n");
+}
+
+#[test]
+fn test_env_invalid_value_fails() {
+    let output = slug_bin().arg("file.txt").env("SLUGR_MAX_LENGTH", "lots").output().unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("slugr: invalid SLUGR_MAX_LENGTH value 'lots'"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_config_file_style_applied() {
+    let config_home = tempfile::tempdir().unwrap();
+    fs::create_dir(config_home.path().join("slugr")).unwrap();
+    fs::write(config_home.path().join("slugr/config.toml"), "style = \"snake\"\n").unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My File.txt"), "").unwrap();
+
+    let output = slug_bin().arg("-x").arg(dir.path().join("My File.txt")).env("XDG_CONFIG_HOME", config_home.path()).output().unwrap();
+
+    assert!(output.status.success());
+    assert!(dir.path().join("my_file.txt").exists());
+
+    // Environment variables take precedence over the config file
+    fs::write(dir.path().join("Other File.txt"), "").unwrap();
+    let output = slug_bin()
+        .arg("-x")
+        .arg(dir.path().join("Other File.txt"))
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .env("SLUGR_STYLE", "pascal")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(dir.path().join("OtherFile.txt").exists());
+}
+
+#[test]
+fn test_malformed_config_file_warns() {
+    let config_home = tempfile::tempdir().unwrap();
+    fs::create_dir(config_home.path().join("slugr")).unwrap();
+    fs::write(config_home.path().join("slugr/config.toml"), "style = [snake\n").unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My File.txt"), "").unwrap();
+
+    let output = slug_bin().arg("-x").arg(dir.path().join("My File.txt")).env("XDG_CONFIG_HOME", config_home.path()).output().unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("slugr: warning: ignoring"), "stderr: {stderr}");
+    assert!(dir.path().join("my-file.txt").exists());
+}
+
+#[test]
+fn test_generate_completions_bash() {
+    let output = slug_bin().args(["--generate-completions", "bash"]).output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("slugr"));
+    for flag in ["--execute", "--recursive", "--pipe", "--collision", "--generate-completions"] {
+        assert!(stdout.contains(flag), "{flag} missing from completions");
+    }
+}
+
+#[test]
+fn test_man_page() {
+    let output = slug_bin().arg("--man").output().unwrap();
+
+    assert!(output.status.success());
+    let page = String::from_utf8(output.stdout).unwrap();
+    assert!(page.starts_with(".TH slugr"), "{page}");
+    assert!(page.contains(env!("CARGO_PKG_VERSION")));
+    // roff escapes hyphens in flag names
+    for flag in ["execute", "recursive", "interactive", "clobber", "snake", "pascal", "keep\\-unicode", "pipe", "from\\-pairs"] {
+        assert!(page.contains(&format!("\\-\\-{flag}")), "--{flag} missing from man page");
+    }
+}
+
+/// Write an executable editor script that runs `body` with the plan file as `$1`.
+#[cfg(unix)]
+fn mock_editor(dir: &std::path::Path, body: &str) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let script = dir.join("editor.sh");
+    fs::write(&script, format!("#!/bin/sh\n{body}\n")).unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    script
+}
+
+#[cfg(unix)]
+#[test]
+fn test_edit_plan_changes_target_and_skips_deleted_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let files = dir.path().join("files");
+    fs::create_dir(&files).unwrap();
+    for name in ["My File.txt", "Other File.txt"] {
+        fs::write(files.join(name), name).unwrap();
+    }
+    let editor = mock_editor(
+        dir.path(),
+        r#"sed -e 's|my-file\.txt$|notes.txt|' -e '/Other File/d' "$1" > "$1.new" && mv "$1.new" "$1""#,
+    );
+
+    let output = slug_bin().args(["-x", "--edit-plan", "-r"]).arg(&files).env("EDITOR", &editor).output().unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(fs::read_to_string(files.join("notes.txt")).unwrap(), "My File.txt");
+    assert!(files.join("Other File.txt").exists());
+    assert!(!files.join("other-file.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_edit_plan_editor_run_by_shell() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My File.txt"), "").unwrap();
+
+    // Quoted arguments only survive if the shell parses the command
+    let output = slug_bin()
+        .args(["-x", "--edit-plan"])
+        .arg(dir.path().join("My File.txt"))
+        .env("EDITOR", r"sed -i 's|my-file\.txt$|my-notes.txt|'")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join("my-notes.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_edit_plan_unedited_plan_is_applied() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My File.txt"), "").unwrap();
+    let editor = mock_editor(dir.path(), "true");
+
+    let output =
+        slug_bin().args(["-x", "--edit-plan"]).arg(dir.path().join("My File.txt")).env("EDITOR", &editor).output().unwrap();
+
+    assert!(output.status.success());
+    assert!(dir.path().join("my-file.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_edit_plan_rejects_invalid_target() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My File.txt"), "").unwrap();
+    let editor = mock_editor(dir.path(), r#"sed -e 's|my-file\.txt$|Bad Name.txt|' "$1" > "$1.new" && mv "$1.new" "$1""#);
+
+    let output =
+        slug_bin().args(["-x", "--edit-plan"]).arg(dir.path().join("My File.txt")).env("EDITOR", &editor).output().unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'Bad Name.txt' is not a valid slug"), "stderr: {stderr}");
+    assert!(dir.path().join("My File.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_edit_plan_editor_failure_renames_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My File.txt"), "").unwrap();
+    let editor = mock_editor(dir.path(), "exit 1");
+
+    let output =
+        slug_bin().args(["-x", "--edit-plan"]).arg(dir.path().join("My File.txt")).env("EDITOR", &editor).output().unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("slugr: cannot edit the plan"));
+    assert!(dir.path().join("My File.txt").exists());
+}
+
+/// A directory holding `My Projects/Read Me.txt` and `Top File.txt`.
+fn mixed_tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("My Projects")).unwrap();
+    fs::write(dir.path().join("My Projects/Read Me.txt"), "").unwrap();
+    fs::write(dir.path().join("Top File.txt"), "").unwrap();
+    dir
+}
+
+#[test]
+fn test_dirs_only() {
+    let dir = mixed_tree();
+
+    let output = slug_bin().args(["-rx", "--dirs-only"]).arg(dir.path()).output().unwrap();
+
+    assert!(output.status.success());
+    assert!(dir.path().join("my-projects/Read Me.txt").exists());
+    assert!(dir.path().join("Top File.txt").exists());
+}
+
+#[test]
+fn test_files_only() {
+    let dir = mixed_tree();
+
+    let output = slug_bin().args(["-rx", "--files-only"]).arg(dir.path()).output().unwrap();
+
+    assert!(output.status.success());
+    assert!(dir.path().join("My Projects/read-me.txt").exists());
+    assert!(dir.path().join("top-file.txt").exists());
+}
+
+#[test]
+fn test_dry_run_plans_distinct_targets_for_case_variants() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My File.txt"), "").unwrap();
+    fs::write(dir.path().join("my  file.txt"), "").unwrap();
+
+    let output = slug_bin().args(["-r", "--sort"]).arg(dir.path()).output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("My File.txt -> ") && stdout.contains("my-file.txt\n"), "stdout: {stdout}");
+    assert!(stdout.contains("my-file-2.txt\n"), "stdout: {stdout}");
+}
+
+/// On a case-insensitive filesystem, `photo.JPG` and `photo.jpg` are the same
+/// name, so the second source needs a suffix.
+#[cfg(target_os = "macos")]
+#[test]
+fn test_case_insensitive_collision_gets_suffix() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("Photo 1.JPG"), "a").unwrap();
+    fs::write(dir.path().join("photo_1.jpg"), "b").unwrap();
+
+    let output = slug_bin().args(["-rx", "--sort"]).arg(dir.path()).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.path().join("photo-1.JPG")).unwrap(), "a");
+    assert_eq!(fs::read_to_string(dir.path().join("photo-1-2.jpg")).unwrap(), "b");
+}
+
+/// Set the modification time of `path` to `days` days after the Unix epoch, at noon UTC.
+fn set_mtime_days(path: &std::path::Path, days: u64) {
+    let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_hours(days * 24 + 12);
+    fs::File::options().write(true).open(path).unwrap().set_modified(mtime).unwrap();
+}
+
+#[test]
+fn test_template_prefixes_modification_date() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("Holiday Photo.JPG");
+    let second = dir.path().join("Beach Day.JPG");
+    fs::write(&first, "1").unwrap();
+    fs::write(&second, "2").unwrap();
+    // 2024-03-05
+    set_mtime_days(&first, 19_787);
+    set_mtime_days(&second, 19_787);
+
+    let output = slug_bin()
+        .args(["-x", "--template", "{mtime_year}-{mtime_month}-{mtime_day}-{slug}.{ext}"])
+        .arg(&first)
+        .arg(&second)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(fs::read_to_string(dir.path().join("2024-03-05-holiday-photo.JPG")).unwrap(), "1");
+    assert_eq!(fs::read_to_string(dir.path().join("2024-03-05-beach-day.JPG")).unwrap(), "2");
+}
+
+#[test]
+fn test_template_collisions_get_suffixes() {
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["a.txt", "b.txt"] {
+        fs::write(dir.path().join(name), name).unwrap();
+        set_mtime_days(&dir.path().join(name), 0);
+    }
+
+    let output =
+        slug_bin().args(["-x", "--template", "{mtime_year}.{ext}"]).arg(dir.path().join("a.txt")).arg(dir.path().join("b.txt")).output().unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(fs::read_to_string(dir.path().join("1970.txt")).unwrap(), "a.txt");
+    assert_eq!(fs::read_to_string(dir.path().join("1970-2.txt")).unwrap(), "b.txt");
+}
+
+#[test]
+fn test_template_dry_run_and_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("My File.txt");
+    fs::write(&file, "").unwrap();
+
+    let output = slug_bin().args(["--template", "{size_bytes}-{slug}.{ext}"]).arg(&file).output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("0-my-file.txt"));
+    assert!(file.exists());
+
+    let output = slug_bin().args(["-x", "--template", "{artist}-{slug}"]).arg(&file).output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("undefined template variable 'artist'"), "stderr: {stderr}");
+    assert!(file.exists());
+}
+
+#[test]
+fn test_template_trims_trailing_dot() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("Makefile");
+    fs::write(&file, "all:").unwrap();
+
+    // No extension leaves "makefile."
+    let output = slug_bin().args(["-x", "--template", "{slug}.{ext}"]).arg(&file).output().unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(fs::read_to_string(dir.path().join("makefile")).unwrap(), "all:");
+
+    let file = dir.path().join("makefile");
+    let output = slug_bin().args(["-x", "--template", "{ext}. "]).arg(&file).output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("template gives invalid name '. '"));
+    assert!(file.exists());
+}
+
+/// `Outer Dir/Inner Dir/My File.txt` under a fresh temp dir.
+fn nested_tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let inner = dir.path().join("Outer Dir").join("Inner Dir");
+    fs::create_dir_all(&inner).unwrap();
+    fs::write(inner.join("My File.txt"), "deep").unwrap();
+    dir
+}
+
+#[test]
+fn test_rename_order_bottom_up_renames_contents_first() {
+    let dir = nested_tree();
+
+    let output =
+        slug_bin().args(["-rxv", "--rename-order", "bottom-up"]).arg(dir.path()).output().unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(!stderr.contains("No such file"), "stderr: {stderr}");
+    assert_eq!(fs::read_to_string(dir.path().join("outer-dir/inner-dir/my-file.txt")).unwrap(), "deep");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let position = |name: &str| stdout.find(name).unwrap_or_else(|| panic!("{name} not renamed: {stdout}"));
+    assert!(position("My File.txt") < position("Inner Dir ->"));
+    assert!(position("Inner Dir ->") < position("Outer Dir ->"));
+}
+
+#[test]
+fn test_rename_order_defaults_to_bottom_up() {
+    let dir = nested_tree();
+    let output = slug_bin().arg("-rx").arg(dir.path()).output().unwrap();
+    assert!(output.status.success());
+    assert!(dir.path().join("outer-dir/inner-dir/my-file.txt").exists());
+}
+
+#[test]
+fn test_rename_order_top_down_breaks_paths_under_renamed_dirs() {
+    let dir = nested_tree();
+
+    let output = slug_bin().args(["-rx", "--rename-order", "top-down"]).arg(dir.path()).output().unwrap();
+
+    // The outer directory is renamed first, so the paths listed beneath it no longer exist
+    assert!(!output.status.success());
+    assert!(dir.path().join("outer-dir").exists());
+    assert!(dir.path().join("outer-dir/Inner Dir/My File.txt").exists());
+}
+
+#[test]
+fn test_rename_order_top_down_with_files_only() {
+    let dir = nested_tree();
+
+    let output =
+        slug_bin().args(["-rx", "--files-only", "--rename-order", "top-down"]).arg(dir.path()).output().unwrap();
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join("Outer Dir/Inner Dir/my-file.txt").exists());
+}
+
+#[test]
+fn test_rename_order_requires_recursive() {
+    let output = slug_bin().args(["--rename-order", "top-down", "file.txt"]).output().unwrap();
+    assert!(!output.status.success());
+}
+
+/// Run slugr with `args`, writing `input` to its stdin.
+fn run_with_stdin(args: &[&str], input: &[u8]) -> std::process::Output {
+    slug_bin()
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            child.stdin.take().unwrap().write_all(input).unwrap();
+            child.wait_with_output()
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_from_null_reads_names_with_newlines() {
+    let dir = tempfile::tempdir().unwrap();
+    let odd = dir.path().join("Two\nLines.txt");
+    let plain = dir.path().join("Plain Name.txt");
+    fs::write(&odd, "").unwrap();
+    fs::write(&plain, "").unwrap();
+
+    let mut input = Vec::new();
+    for path in [&odd, &plain] {
+        input.extend_from_slice(path.as_os_str().as_encoded_bytes());
+        input.push(0);
+    }
+    let output = run_with_stdin(&["-x", "-0"], &input);
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join("two-lines.txt").exists());
+    assert!(dir.path().join("plain-name.txt").exists());
+    assert!(!odd.exists());
+}
+
+#[test]
+fn test_print0_writes_nul_separated_new_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("First File.txt"), "").unwrap();
+    fs::write(dir.path().join("Second File.txt"), "").unwrap();
+    fs::write(dir.path().join("clean.txt"), "").unwrap();
+
+    let output = slug_bin().args(["-x", "--print0", "--sort", "-r"]).arg(dir.path()).output().unwrap();
+
+    assert!(output.status.success());
+    let printed: Vec<&[u8]> = output.stdout.split(|&b| b == 0).filter(|p| !p.is_empty()).collect();
+    let expected = [dir.path().join("first-file.txt"), dir.path().join("second-file.txt")];
+    assert_eq!(printed, expected.iter().map(|p| p.as_os_str().as_encoded_bytes()).collect::<Vec<_>>());
+    assert_eq!(output.stdout.last(), Some(&0));
+}
+
+#[test]
+fn test_from_null_print0_round_trip_dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("Draft\nNotes.md");
+    fs::write(&file, "").unwrap();
+
+    let mut input = file.as_os_str().as_encoded_bytes().to_vec();
+    input.push(0);
+    let output = run_with_stdin(&["-0", "--print0"], &input);
+
+    assert!(output.status.success());
+    let mut expected = dir.path().join("draft-notes.md").as_os_str().as_encoded_bytes().to_vec();
+    expected.push(0);
+    assert_eq!(output.stdout, expected);
+    assert!(file.exists(), "dry run must not rename");
+}
+
+#[test]
+fn test_stdin_paths_warn_on_invalid_utf8() {
+    let dir = tempfile::tempdir().unwrap();
+    let plain = dir.path().join("Plain Name.txt");
+    fs::write(&plain, "").unwrap();
+
+    for separator in [b'\n', b'\0'] {
+        let mut input = b"Bad\xff Name.txt".to_vec();
+        input.push(separator);
+        input.extend_from_slice(plain.as_os_str().as_encoded_bytes());
+        input.push(separator);
+        let args: &[&str] = if separator == 0 { &["-0"] } else { &[] };
+        let output = run_with_stdin(args, &input);
+
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("slugr: warning: skipping input path that is not valid UTF-8: Bad\u{fffd} Name.txt"), "{stderr}");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("plain-name.txt"), "later paths are still read: {stdout}");
+    }
+}
+
+#[test]
+fn test_from_null_conflicts_with_pipe() {
+    let output = slug_bin().args(["-0", "--pipe"]).output().unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_pipe_only_basename_keeps_directories() {
+    let output = run_with_stdin(
+        &["--pipe", "--only-basename"],
+        b"/home/user/My Documents/Report (Final).pdf\n/srv/Shared Files/Team Photo.JPG\n",
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, ["/home/user/My Documents/report-final.pdf", "/srv/Shared Files/team-photo.JPG"]);
+}
+
+#[test]
+fn test_pipe_without_only_basename_slugifies_whole_line() {
+    let output = run_with_stdin(&["--pipe"], b"/home/user/My Documents/Report.pdf\n");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), "home-user-my-documents-report.pdf");
+}
+
+#[test]
+fn test_only_basename_requires_pipe() {
+    let output = slug_bin().args(["--only-basename", "file.txt"]).output().unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_pipe_print_original_tab_separated() {
+    let input = "My Résumé (Final).pdf\n  Leading Space.txt\nclean.txt\n";
+    let output = run_with_stdin(&["--pipe", "--print-original"], input.as_bytes());
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<(&str, &str)> = stdout.lines().map(|line| line.split_once('\t').unwrap()).collect();
+    // The first field is each input line exactly as given
+    let originals: Vec<&str> = rows.iter().map(|(original, _)| *original).collect();
+    assert_eq!(originals, input.lines().collect::<Vec<_>>());
+    assert_eq!(rows[0].1, "my-resume-final.pdf");
+    assert_eq!(rows[1].1, "leading-space.txt");
+    assert_eq!(rows[2].1, "clean.txt");
+}
+
+#[test]
+fn test_pipe_print_original_print0() {
+    let output = run_with_stdin(&["--pipe", "--print-original", "--print0"], b"My File.txt\nOther One.md\n");
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"My File.txt\0my-file.txt\0Other One.md\0other-one.md\0");
+}
+
+#[test]
+fn test_print_original_requires_pipe() {
+    let output = slug_bin().args(["--print-original", "file.txt"]).output().unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_changelog_markdown_for_three_renames() {
+    let dir = tempfile::tempdir().unwrap();
+    let sub = dir.path().join("sub");
+    fs::create_dir(&sub).unwrap();
+    fs::write(dir.path().join("Beach Day.jpg"), "").unwrap();
+    fs::write(dir.path().join("My Notes.md"), "").unwrap();
+    fs::write(sub.join("Draft One.txt"), "").unwrap();
+    fs::write(dir.path().join("clean.txt"), "").unwrap();
+    let changelog = tempfile::NamedTempFile::new().unwrap();
+
+    let output = slug_bin()
+        .args(["-rx", "--sort", "--changelog"])
+        .arg(changelog.path())
+        .arg(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let root = dir.path().display();
+    assert_eq!(
+        fs::read_to_string(changelog.path()).unwrap(),
+        format!(
+            "## {root}\n- `Beach Day.jpg` → `beach-day.jpg`\n- `My Notes.md` → `my-notes.md`\n\n\
+             ## {root}/sub\n- `Draft One.txt` → `draft-one.txt`\n"
+        )
+    );
+}
+
+#[test]
+fn test_changelog_rst() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("A File.txt"), "").unwrap();
+    fs::write(dir.path().join("B File.txt"), "").unwrap();
+    fs::write(dir.path().join("C File.txt"), "").unwrap();
+    let changelog = tempfile::NamedTempFile::new().unwrap();
+
+    let output = slug_bin()
+        .args(["-rx", "--sort", "--changelog-fmt", "rst", "--changelog"])
+        .arg(changelog.path())
+        .arg(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let root = dir.path().display().to_string();
+    let expected = format!(
+        "{root}\n{}\n\n- ``A File.txt`` → ``a-file.txt``\n- ``B File.txt`` → ``b-file.txt``\n- ``C File.txt`` → ``c-file.txt``\n",
+        "=".repeat(root.chars().count())
+    );
+    assert_eq!(fs::read_to_string(changelog.path()).unwrap(), expected);
+}
+
+#[test]
+fn test_changelog_fmt_requires_changelog() {
+    let output = slug_bin().args(["--changelog-fmt", "org", "file.txt"]).output().unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_extension_map_normalizes_aliases() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("Beach Day.JPEG"), "").unwrap();
+    fs::write(dir.path().join("Index Page.htm"), "").unwrap();
+    fs::write(dir.path().join("scan.tiff"), "").unwrap();
+    let map = dir.path().join("extensions.toml");
+    fs::write(&map, "tiff = \"tif\"\n").unwrap();
+
+    let output = slug_bin()
+        .arg("-x")
+        .arg("--extension-map")
+        .arg(&map)
+        .arg(dir.path().join("Beach Day.JPEG"))
+        .arg(dir.path().join("Index Page.htm"))
+        .arg(dir.path().join("scan.tiff"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join("beach-day.jpg").exists());
+    assert!(dir.path().join("index-page.html").exists());
+    assert!(dir.path().join("scan.tif").exists());
+}
+
+#[test]
+fn test_extension_map_invalid_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let map = dir.path().join("extensions.toml");
+    fs::write(&map, "jpeg = 1\n").unwrap();
+    fs::write(dir.path().join("Photo.jpeg"), "").unwrap();
+
+    let output = slug_bin().arg("-x").arg("--extension-map").arg(&map).arg(dir.path().join("Photo.jpeg")).output().unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot read extension map"));
+    assert!(dir.path().join("Photo.jpeg").exists());
+}
+
+#[test]
+fn test_content_hash_appends_hash() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("My Photo.jpg"), "hello").unwrap();
+    fs::write(dir.path().join("my photo.jpg"), "world").unwrap();
+
+    let output = slug_bin().args(["-x", "--content-hash"]).arg(dir.path().join("My Photo.jpg")).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join("my-photo-2cf24dba.jpg").exists());
+
+    let output = slug_bin().args(["-x", "--content-hash=4"]).arg(dir.path().join("my photo.jpg")).output().unwrap();
+    assert!(output.status.success());
+    assert!(dir.path().join("my-photo-486e.jpg").exists());
+
+    // Already hashed: nothing to do
+    let output = slug_bin().args(["-xv", "--content-hash"]).arg(dir.path().join("my-photo-2cf24dba.jpg")).output().unwrap();
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty(), "{}", String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn test_content_hash_full() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("Notes.txt"), "hello").unwrap();
+
+    let output = slug_bin().args(["-x", "--content-hash-full"]).arg(dir.path().join("Notes.txt")).output().unwrap();
+
+    assert!(output.status.success());
+    assert!(dir.path().join("notes-2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824.txt").exists());
+}
+
+#[test]
+fn test_content_hash_skips_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    let sub = dir.path().join("My Album");
+    fs::create_dir(&sub).unwrap();
+    fs::write(sub.join("Cover.png"), "hello").unwrap();
+
+    let output = slug_bin().args(["-rx", "--content-hash"]).arg(dir.path()).output().unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.path().join("my-album").join("cover-2cf24dba.png").exists());
+}
+
+#[test]
+fn test_content_hash_length_out_of_range() {
+    for arg in ["--content-hash=0", "--content-hash=65"] {
+        let output = slug_bin().args([arg, "file.txt"]).output().unwrap();
+        assert!(!output.status.success(), "{arg}");
+    }
+}
+
+#[test]
+fn test_warn_unchanged() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("clean-name.txt"), "").unwrap();
+    fs::write(dir.path().join("Messy Name.txt"), "").unwrap();
+
+    let output = slug_bin().args(["-r", "--sort", "--warn-unchanged"]).arg(dir.path()).output().unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let clean = dir.path().join("clean-name.txt");
+    assert!(stderr.contains(&format!("slugr: warning: '{}' is already clean", clean.display())), "{stderr}");
+    assert!(!stderr.contains("Messy Name.txt"), "{stderr}");
+}
+
+#[test]
+fn test_warn_unchanged_checks_final_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let clean = dir.path().join("clean-name.txt");
+    fs::write(&clean, "").unwrap();
+
+    // The slug is unchanged, but the counter still renames it
+    let output = slug_bin().args(["--warn-unchanged", "--suffix-digits", "3"]).arg(&clean).output().unwrap();
+
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("already clean"));
+}
+
+#[test]
+fn test_no_unchanged_warning_by_default() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("clean-name.txt"), "").unwrap();
+
+    let output = slug_bin().arg(dir.path().join("clean-name.txt")).output().unwrap();
+
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("already clean"));
+}